@@ -4,15 +4,25 @@ mod state;
 
 use linera_sdk::{
     abi::WithContractAbi,
-    linera_base_types::AccountOwner,
+    linera_base_types::{Account, AccountOwner, Amount, ChainId, TimeDelta},
     views::{RootView, View},
     Contract, ContractRuntime,
 };
 
-use self::state::{FullGameState, GamePlatformState, PlayerStats};
+use self::state::{FullGameState, GamePlatformState, PlayerStats, SentInvite};
 use game_platform::{
-    BlackjackGame, ChessBoard, Clock, GameLobby, GameMode, GameOutcome, GameStatus,
-    GameType, LeaderboardEntry, LobbyStatus, Operation, Player, PokerGame, Timeouts, UserProfile,
+    blocks_either_way, bot_elo_adjustment, check_registration, combine_seeds, elo_delta,
+    find_matchmaking_opponent, hash_lobby_password, head_to_head_key, is_quick_checkmate,
+    is_valid_best_of, is_valid_bracket_size, lobby_seats_supported, push_chat_message,
+    rank_leaderboard_for_period, record_head_to_head, rematch_seating, unlock_achievement,
+    validate_avatar_url, validate_username, verify_seed_reveal, AchievementKind, BlackjackAction, BlackjackStakes,
+    ChatMessage, Clock, ColorChoice, GameCompletedEvent, GameDefaults, GameEndReason, GameLobby,
+    GameMode, GameOutcome, GameStatus, GameType, LeaderboardEntry, LobbyStatus, MatchState, MatchStatus, Message,
+    Operation, Player, PlayerReport, PokerAction, PokerStage, PokerStakes, Timeouts, Tournament,
+    TournamentPairing, TournamentStatus, UserProfile, ABORT_WINDOW_MICROS, CHAT_MAX_LEN,
+    GAME_COMPLETED_STREAM, INVITE_TTL_MICROS, LOBBY_TTL_MICROS, MAX_BLOCKED_PLAYERS,
+    MAX_LOBBY_INVITES, MIN_ACTION_INTERVAL_MICROS, REPORT_REASON_MAX_LEN, SEED_REVEAL_WINDOW_MICROS,
+    STATUS_MESSAGE_MAX_LEN, STREAK_ACHIEVEMENT_LENGTH,
 };
 
 pub struct GamePlatformContract {
@@ -27,10 +37,10 @@ impl WithContractAbi for GamePlatformContract {
 }
 
 impl Contract for GamePlatformContract {
-    type Message = ();
+    type Message = Message;
     type Parameters = ();
     type InstantiationArgument = ();
-    type EventValue = ();
+    type EventValue = GameCompletedEvent;
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
         let state = GamePlatformState::load(runtime.root_view_storage_context())
@@ -48,6 +58,8 @@ impl Contract for GamePlatformContract {
         self.state.active_lobby_ids.set(vec![]);
         self.state.total_games_played.set(0);
         self.state.total_users.set(0);
+        self.state.game_defaults.set(GameDefaults::default());
+        self.state.admin.set(self.runtime.authenticated_signer());
         
         eprintln!("✅ Game platform contract instantiated");
     }
@@ -55,6 +67,26 @@ impl Contract for GamePlatformContract {
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
         let timestamp = self.runtime.system_time().micros() as u64;
 
+        // Play in a game another chain hosts for one of our invites goes to that chain, signed by the same player
+        let host_chain = match relayed_game_id(&operation) {
+            Some(game_id) => self.state.sent_invites.get(game_id).await.ok().flatten().map(|invite| invite.host_chain),
+            None => None,
+        };
+        if let Some(host_chain) = host_chain {
+            self.runtime
+                .prepare_message(Message::RelayedOperation { operation })
+                .with_authentication()
+                .send_to(host_chain);
+            return GameOutcome::InProgress;
+        }
+
+        // A flag that has fallen decides the game before the late action is looked at
+        if let Some(game_id) = timed_game_id(&operation) {
+            if let Some(outcome) = self.resolve_expired_clock(game_id, timestamp).await {
+                return outcome;
+            }
+        }
+
         match operation {
             Operation::RegisterUser { username, eth_address, avatar_url } => {
                 // Get owner - fallback to deriving from ETH address if no signer
@@ -67,72 +99,68 @@ impl Contract for GamePlatformContract {
                             Some(o) => o,
                             None => {
                                 eprintln!("❌ Cannot parse ETH address as owner: {}", eth_address);
-                                return GameOutcome::InProgress;
+                                return GameOutcome::rejected("Cannot derive an owner from the ETH address");
                             }
                         }
                     }
                 };
 
+                let username = match validate_username(&username) {
+                    Ok(u) => u,
+                    Err(e) => return GameOutcome::Rejected(e),
+                };
+                let avatar_url = match validate_avatar_url(&avatar_url) {
+                    Ok(url) => url,
+                    Err(e) => return GameOutcome::Rejected(e),
+                };
+
+                // A first registration is free; re-registering counts against the rate limit
+                let registered = matches!(self.state.user_profiles.get(&owner).await, Ok(Some(_)));
+                if registered && !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
                 eprintln!("📝 Registering user: {} with ETH: {}", username, eth_address);
 
-                // Check if username is taken - graceful error handling
-                let existing_username = self.state
+                let existing_username_owner = self.state
                     .username_to_owner
                     .get(&username.to_lowercase())
                     .await
                     .unwrap_or(None);
-
-                if let Some(existing_owner) = existing_username {
-                    // Get the ETH address for the existing owner to check if it matches
-                    if let Ok(Some(existing_profile)) = self.state
-                        .user_profiles
-                        .get(&existing_owner)
-                        .await {
-                        if existing_profile.eth_address.to_lowercase() != eth_address.to_lowercase() {
-                            eprintln!("❌ Username already taken: {}", username);
-                            return GameOutcome::InProgress;
-                        }
-                    }
-                }
-
-                // Check if ETH address is already registered
                 let existing_eth_owner = self.state
                     .eth_to_owner
                     .get(&eth_address.to_lowercase())
                     .await
                     .unwrap_or(None);
+                let existing_profile = self.state.user_profiles.get(&owner).await.ok().flatten();
+
+                let old_username = match check_registration(
+                    &owner,
+                    &eth_address,
+                    &username,
+                    existing_eth_owner.as_ref(),
+                    existing_username_owner.as_ref(),
+                    existing_profile.as_ref(),
+                ) {
+                    Ok(old) => old,
+                    Err(e) => return GameOutcome::Rejected(e),
+                };
+                if registered {
+                    self.stamp_action(owner, timestamp);
+                }
 
-                let profile = if let Some(existing_owner) = existing_eth_owner {
-                    eprintln!("📝 ETH address already exists, updating profile");
-                    
-                    // ETH address already registered - update existing profile
-                    match self.state
-                        .user_profiles
-                        .get(&existing_owner)
-                        .await
-                    {
-                        Ok(Some(mut existing_profile)) => {
-                            if existing_owner != owner {
-                                eprintln!("❌ ETH address registered to different owner");
-                                return GameOutcome::InProgress;
-                            }
+                let profile = if let Some(mut existing_profile) = existing_profile {
+                    eprintln!("📝 Account already registered, updating profile");
 
-                            // Remove old username mapping if changed
-                            if existing_profile.username.to_lowercase() != username.to_lowercase() {
-                                let _ = self.state.username_to_owner
-                                    .remove(&existing_profile.username.to_lowercase());
-                            }
-                            
-                            existing_profile.username = username.clone();
-                            existing_profile.avatar_url = avatar_url;
-                            existing_profile.last_active = timestamp;
-                            existing_profile
-                        }
-                        _ => {
-                            eprintln!("❌ Could not load existing profile");
-                            return GameOutcome::InProgress;
-                        }
+                    // The old name is freed as soon as the account moves to a new one
+                    if let Some(old_username) = old_username {
+                        let _ = self.state.username_to_owner.remove(&old_username);
                     }
+
+                    existing_profile.username = username.clone();
+                    existing_profile.avatar_url = avatar_url;
+                    existing_profile.last_active = timestamp;
+                    existing_profile
                 } else {
                     eprintln!("✨ Creating new user profile");
                     
@@ -141,13 +169,14 @@ impl Contract for GamePlatformContract {
                     self.state.total_users.set(total + 1);
 
                     // Initialize stats
+                    let starting_elo = self.state.game_defaults.get().starting_elo;
                     let stats = PlayerStats {
-                        chess_elo: 1200,
+                        chess_elo: starting_elo,
                         ..Default::default()
                     };
                     let _ = self.state.stats.insert(&owner, stats);
 
-                    UserProfile::new(username.clone(), eth_address.clone(), avatar_url, timestamp)
+                    UserProfile::new(username.clone(), eth_address.clone(), avatar_url, timestamp, starting_elo)
                 };
 
                 // Save mappings - ignore errors
@@ -162,7 +191,7 @@ impl Contract for GamePlatformContract {
             Operation::UpdateProfile { username, avatar_url } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut profile = match self.state
@@ -173,10 +202,15 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(p) => p,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Player not registered"),
                 };
 
                 if let Some(new_username) = username {
+                    let new_username = match validate_username(&new_username) {
+                        Ok(u) => u,
+                        Err(e) => return GameOutcome::Rejected(e),
+                    };
+
                     // Check if new username is taken
                     let existing = self.state
                         .username_to_owner
@@ -186,7 +220,7 @@ impl Contract for GamePlatformContract {
 
                     if let Some(existing_owner) = existing {
                         if existing_owner != owner {
-                            return GameOutcome::InProgress;
+                            return GameOutcome::rejected("Username already taken");
                         }
                     }
 
@@ -200,7 +234,10 @@ impl Contract for GamePlatformContract {
                 }
 
                 if let Some(new_avatar) = avatar_url {
-                    profile.avatar_url = new_avatar;
+                    profile.avatar_url = match validate_avatar_url(&new_avatar) {
+                        Ok(url) => url,
+                        Err(e) => return GameOutcome::Rejected(e),
+                    };
                 }
 
                 profile.last_active = timestamp;
@@ -209,12 +246,72 @@ impl Contract for GamePlatformContract {
                 GameOutcome::InProgress
             }
 
-            Operation::CreateLobby { game_type, game_mode, is_public, password, time_control } => {
+            Operation::SetAvailability { looking_for_game, game_type, message } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
+                let mut profile = match self.state
+                    .user_profiles
+                    .get(&owner)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Player not registered"),
+                };
+
+                let message = message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+                if message.as_ref().is_some_and(|m| m.chars().count() > STATUS_MESSAGE_MAX_LEN) {
+                    return GameOutcome::rejected("Status message too long");
+                }
+
+                profile.looking_for_game = looking_for_game;
+                profile.looking_for = game_type;
+                profile.status_message = message;
+                profile.last_active = timestamp;
+                let _ = self.state.user_profiles.insert(&owner, profile);
+                if looking_for_game {
+                    let _ = self.state.available_since.insert(&owner, timestamp);
+                } else {
+                    let _ = self.state.available_since.remove(&owner);
+                }
+
+                GameOutcome::InProgress
+            }
+
+            Operation::CreateLobby {
+                game_type,
+                game_mode,
+                is_public,
+                password,
+                time_control,
+                max_players,
+                wager,
+                creator_color,
+                poker_stakes,
+                allow_hints,
+            } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
+                self.prune_expired_lobbies(timestamp).await;
+
+                if !lobby_seats_supported(game_type, max_players) {
+                    return GameOutcome::rejected("Unsupported number of lobby players");
+                }
+                if poker_stakes.is_some_and(|stakes| !stakes.is_valid()) {
+                    return GameOutcome::rejected("Invalid poker stakes");
+                }
+
                 let profile = match self.state
                     .user_profiles
                     .get(&owner)
@@ -223,30 +320,49 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(p) => p,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Player not registered"),
                 };
 
-                // Generate lobby ID
-                let lobby_id = format!("{:x}{:x}", timestamp, owner.to_string().len());
+                if !Self::wager_allowed(game_type, game_mode, wager) {
+                    return GameOutcome::rejected("Wagers are not allowed for this game");
+                }
+                if let Some(stake) = wager {
+                    if !self.escrow_stake(owner, stake) {
+                        return GameOutcome::rejected("Insufficient funds for the wager");
+                    }
+                }
+                self.stamp_action(owner, timestamp);
+
+                // The chain's own counter keeps ids unique when lobbies open in the same block
+                let lobby_number = *self.state.next_lobby_id.get();
+                self.state.next_lobby_id.set(lobby_number + 1);
+                let lobby_id = format!("{:x}_{:x}_{}", timestamp, lobby_number, owner);
 
-                let password_hash = password.map(|p| {
-                    format!("{:x}", p.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64)))
-                });
+                let password_salt = format!("{}:{:?}", lobby_id, owner);
+                let password_hash = password.map(|p| hash_lobby_password(&p, &password_salt));
 
                 let lobby = GameLobby {
                     lobby_id: lobby_id.clone(),
                     creator: format!("{:?}", owner),
-                    creator_name: profile.username,
+                    creator_name: profile.username.clone(),
                     game_type,
                     game_mode,
                     is_public,
                     password_hash,
+                    password_salt,
                     status: LobbyStatus::Open,
                     time_control,
                     created_at: timestamp,
-                    expires_at: timestamp + 900_000_000,
+                    expires_at: timestamp + LOBBY_TTL_MICROS,
                     players: vec![format!("{:?}", owner)],
+                    player_names: vec![profile.username],
+                    max_players,
                     game_id: None,
+                    wager,
+                    creator_color: creator_color.unwrap_or_default(),
+                    poker_stakes: poker_stakes.unwrap_or_default(),
+                    allow_hints: allow_hints.unwrap_or(true),
+                    invited: vec![],
                 };
 
                 let _ = self.state.lobbies.insert(&lobby_id, lobby);
@@ -261,9 +377,11 @@ impl Contract for GamePlatformContract {
             Operation::JoinLobby { lobby_id, password } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
+                self.prune_expired_lobbies(timestamp).await;
+
                 let profile = match self.state
                     .user_profiles
                     .get(&owner)
@@ -272,7 +390,7 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(p) => p,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Player not registered"),
                 };
 
                 let mut lobby = match self.state
@@ -283,121 +401,125 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(l) => l,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Lobby not found"),
                 };
 
                 // Check lobby status
                 if lobby.status != LobbyStatus::Open {
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Lobby is not open");
                 }
 
                 // Check expiration
                 if timestamp > lobby.expires_at {
                     lobby.status = LobbyStatus::Expired;
+                    self.refund_lobby(&lobby);
                     let _ = self.state.lobbies.insert(&lobby_id, lobby);
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Lobby has expired");
                 }
 
-                // Check password
+                // Check password; invitees are let in without it
                 if let Some(ref hash) = lobby.password_hash {
-                    let provided_hash = password.map(|p| {
-                        format!("{:x}", p.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64)))
-                    });
-                    if provided_hash.as_ref() != Some(hash) {
-                        return GameOutcome::InProgress;
+                    let provided_hash = password.map(|p| hash_lobby_password(&p, &lobby.password_salt));
+                    if provided_hash.as_ref() != Some(hash) && !lobby.invited.contains(&owner) {
+                        return GameOutcome::rejected("Wrong lobby password");
+                    }
+                }
+
+                // Nobody sits down with a player either of them has blocked
+                let mut member_blocks = Vec::with_capacity(lobby.players.len());
+                for member in &lobby.players {
+                    let blocks = match self.parse_owner_from_debug_str(member) {
+                        Some(member_owner) => self.blocked_players(member_owner).await,
+                        None => vec![],
+                    };
+                    member_blocks.push(blocks);
+                }
+                let joiner_blocks = self.blocked_players(owner).await;
+                if lobby.block_conflict(&owner, &joiner_blocks, &member_blocks) {
+                    return GameOutcome::rejected("A block is in place with a lobby member");
+                }
+
+                // A joiner who can't cover the stake is turned away
+                if let Some(stake) = lobby.wager {
+                    if !self.escrow_stake(owner, stake) {
+                        return GameOutcome::rejected("Insufficient funds for the wager");
                     }
                 }
 
                 // Add player
                 lobby.players.push(format!("{:?}", owner));
+                lobby.player_names.push(profile.username.clone());
+
+                // Wait for the remaining seats
+                if (lobby.players.len() as u32) < lobby.max_players {
+                    let _ = self.state.lobbies.insert(&lobby_id, lobby);
+                    return GameOutcome::InProgress;
+                }
                 lobby.status = LobbyStatus::Full;
 
                 // Create game
                 let game_id = format!("game_{}", lobby_id);
                 lobby.game_id = Some(game_id.clone());
 
-                let creator_str = &lobby.players[0];
-                let joiner_str = format!("{:?}", owner);
-
-                let timeouts = Timeouts {
-                    start_time: linera_sdk::linera_base_types::TimeDelta::from_secs(lobby.time_control),
-                    increment: linera_sdk::linera_base_types::TimeDelta::from_secs(10),
-                    block_delay: linera_sdk::linera_base_types::TimeDelta::from_secs(5),
-                };
-
-                let clock = Clock::new(self.runtime.system_time(), &timeouts);
-
-                let game_state = match lobby.game_type {
-                    GameType::Chess => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Chess,
-                        game_mode: lobby.game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![creator_str.clone(), joiner_str.clone()],
-                        player_names: vec![lobby.creator_name.clone(), profile.username.clone()],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: Some(ChessBoard::new()),
-                        poker_game: None,
-                        blackjack_game: None,
-                    },
-                    GameType::Poker => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Poker,
-                        game_mode: lobby.game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![creator_str.clone(), joiner_str.clone()],
-                        player_names: vec![lobby.creator_name.clone(), profile.username.clone()],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: None,
-                        poker_game: Some(PokerGame::new(1000, 10, 20, timestamp)),
-                        blackjack_game: None,
-                    },
-                    GameType::Blackjack => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Blackjack,
-                        game_mode: lobby.game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![creator_str.clone(), joiner_str.clone()],
-                        player_names: vec![lobby.creator_name.clone(), profile.username.clone()],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: None,
-                        poker_game: None,
-                        blackjack_game: Some(BlackjackGame::new(100, 1000, timestamp)),
-                    },
-                };
-
-                let _ = self.state.games.insert(&game_id, game_state);
+                let timeouts = Timeouts::symmetric(
+                    linera_sdk::linera_base_types::TimeDelta::from_secs(lobby.time_control),
+                    linera_sdk::linera_base_types::TimeDelta::from_secs(10),
+                    linera_sdk::linera_base_types::TimeDelta::from_secs(5),
+                );
+
+                let (players, player_names) = lobby.game_seating();
+                let mut game_state = FullGameState::new(
+                    game_id.clone(),
+                    lobby.game_type,
+                    lobby.game_mode,
+                    players,
+                    player_names,
+                    timestamp,
+                    timeouts,
+                );
+                game_state.set_poker_stakes(lobby.poker_stakes, timestamp);
+                game_state.rules.allow_hints = lobby.allow_hints;
+                // A random color is drawn from both players' revealed seeds, so neither can steer it
+                game_state.seating_pending = lobby.creator_color == ColorChoice::Random
+                    && lobby.players.len() == 2
+                    && lobby.players[0] != lobby.players[1];
+                game_state.wager = lobby.wager;
+                game_state.wager_funded = vec![lobby.wager.is_some(); lobby.players.len()];
+                self.settle_opening_deal(&mut game_state).await;
+
+                self.store_game(game_state).await;
+
+                // The creator follows the game through the lobby; joiners get it in their game list
+                let mut seated = Vec::new();
+                for player_str in lobby.players.iter().skip(1) {
+                    if let Some(seat_owner) = self.parse_owner_from_debug_str(player_str) {
+                        if !seated.contains(&seat_owner) {
+                            seated.push(seat_owner);
+                        }
+                    }
+                }
+                for seat_owner in seated {
+                    let mut seat_games = self.state
+                        .player_games
+                        .get(&seat_owner)
+                        .await
+                        .unwrap_or(None)
+                        .unwrap_or_default();
+                    if !seat_games.contains(&game_id) {
+                        seat_games.push(game_id.clone());
+                    }
+                    let _ = self.state.player_games.insert(&seat_owner, seat_games);
+                }
 
+                let game_type = lobby.game_type;
                 lobby.status = LobbyStatus::Started;
                 let _ = self.state.lobbies.insert(&lobby_id, lobby);
 
-                let mut joiner_games = self.state
-                    .player_games
-                    .get(&owner)
-                    .await
-                    .unwrap_or(None)
-                    .unwrap_or_default();
-                joiner_games.push(game_id.clone());
-                let _ = self.state.player_games.insert(&owner, joiner_games);
-
                 let mut lobby_ids = self.state.active_lobby_ids.get().clone();
                 lobby_ids.retain(|id| id != &lobby_id);
                 self.state.active_lobby_ids.set(lobby_ids);
 
-                let total = self.state.total_games_played.get().clone();
-                self.state.total_games_played.set(total + 1);
+                self.count_new_game(game_type).await;
 
                 GameOutcome::InProgress
             }
@@ -405,7 +527,7 @@ impl Contract for GamePlatformContract {
             Operation::CancelLobby { lobby_id } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut lobby = match self.state
@@ -416,15 +538,20 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(l) => l,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Lobby not found"),
                 };
 
                 let owner_str = format!("{:?}", owner);
                 if lobby.players.get(0) != Some(&owner_str) {
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Only the lobby creator can cancel it");
+                }
+
+                if lobby.status != LobbyStatus::Open {
+                    return GameOutcome::rejected("Lobby is not open");
                 }
 
                 lobby.status = LobbyStatus::Cancelled;
+                self.refund_lobby(&lobby);
                 let _ = self.state.lobbies.insert(&lobby_id, lobby);
 
                 let mut lobby_ids = self.state.active_lobby_ids.get().clone();
@@ -434,12 +561,80 @@ impl Contract for GamePlatformContract {
                 GameOutcome::InProgress
             }
 
-            Operation::CreateGame { game_type, game_mode, opponent, timeouts } => {
+            Operation::InviteToLobby { lobby_id, invitee } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
+                let mut lobby = match self.state
+                    .lobbies
+                    .get(&lobby_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(l) => l,
+                    None => return GameOutcome::rejected("Lobby not found"),
+                };
+
+                if lobby.creator != format!("{:?}", owner) {
+                    return GameOutcome::rejected("Only the lobby creator can invite players");
+                }
+                if lobby.status != LobbyStatus::Open {
+                    return GameOutcome::rejected("Lobby is not open");
+                }
+                if invitee == owner || lobby.invited.contains(&invitee) {
+                    return GameOutcome::rejected("Player is already invited");
+                }
+                if lobby.invited.len() >= MAX_LOBBY_INVITES {
+                    return GameOutcome::rejected("Too many invites for this lobby");
+                }
+
+                lobby.invited.push(invitee);
+                let _ = self.state.lobbies.insert(&lobby_id, lobby);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::PruneLobbies => {
+                self.prune_expired_lobbies(timestamp).await;
+                GameOutcome::InProgress
+            }
+
+            Operation::CreateGame {
+                game_type,
+                game_mode,
+                opponent,
+                creator_color,
+                timeouts,
+                allow_hints,
+                betting_mode,
+                poker_stakes,
+                hit_split_aces,
+                blackjack_stakes,
+                wager,
+            } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if let Some(opp) = opponent {
+                    if self.is_blocked_pair(owner, opp).await {
+                        return GameOutcome::rejected("A block is in place with this opponent");
+                    }
+                }
+                if poker_stakes.is_some_and(|stakes| !stakes.is_valid())
+                    || blackjack_stakes.is_some_and(|stakes| !stakes.is_valid())
+                {
+                    return GameOutcome::rejected("Invalid stakes");
+                }
+
+                if !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
                 let profile = match self.state
                     .user_profiles
                     .get(&owner)
@@ -448,12 +643,11 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(p) => p,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Player not registered"),
                 };
 
-                let game_id = format!("{:x}{:x}", timestamp, owner.to_string().len());
+                let game_id = self.new_game_id(timestamp);
                 let timeouts = timeouts.unwrap_or_default();
-                let clock = Clock::new(self.runtime.system_time(), &timeouts);
 
                 let (opponent_str, opponent_name) = if let Some(opp) = opponent {
                     match self.state
@@ -470,58 +664,62 @@ impl Contract for GamePlatformContract {
                     ("BOT".to_string(), "AI Bot".to_string())
                 };
 
-                let game_state = match game_type {
-                    GameType::Chess => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Chess,
-                        game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![format!("{:?}", owner), opponent_str],
-                        player_names: vec![profile.username, opponent_name],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: Some(ChessBoard::new()),
-                        poker_game: None,
-                        blackjack_game: None,
-                    },
-                    GameType::Poker => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Poker,
-                        game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![format!("{:?}", owner), opponent_str],
-                        player_names: vec![profile.username, opponent_name],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: None,
-                        poker_game: Some(PokerGame::new(1000, 10, 20, timestamp)),
-                        blackjack_game: None,
-                    },
-                    GameType::Blackjack => FullGameState {
-                        game_id: game_id.clone(),
-                        game_type: GameType::Blackjack,
-                        game_mode,
-                        status: GameStatus::InProgress,
-                        players: vec![format!("{:?}", owner), opponent_str],
-                        player_names: vec![profile.username, opponent_name],
-                        created_at: timestamp,
-                        updated_at: timestamp,
-                        winner: None,
-                        clock,
-                        draw_offered_by: None,
-                        chess_board: None,
-                        poker_game: None,
-                        blackjack_game: Some(BlackjackGame::new(100, 1000, timestamp)),
-                    },
-                };
-
-                let _ = self.state.games.insert(&game_id, game_state);
+                // Against a real opponent the creator may take the second seat or leave it to a draw from both
+                // players' revealed seeds
+                let opponent_is_bot = opponent_str == "BOT";
+                let seating_pending = !opponent_is_bot
+                    && opponent != Some(owner)
+                    && creator_color == Some(ColorChoice::Random);
+                let creator_second = !opponent_is_bot && creator_color == Some(ColorChoice::Black);
+                let mut players = vec![format!("{:?}", owner), opponent_str];
+                let mut player_names = vec![profile.username, opponent_name];
+                if creator_second {
+                    players.swap(0, 1);
+                    player_names.swap(0, 1);
+                }
+
+                let mut game_state = FullGameState::new(
+                    game_id.clone(),
+                    game_type,
+                    game_mode,
+                    players,
+                    player_names,
+                    timestamp,
+                    timeouts,
+                );
+                game_state.seating_pending = seating_pending;
+                game_state.rules.allow_hints = allow_hints.unwrap_or(true);
+                if let Some(stakes) = poker_stakes {
+                    game_state.set_poker_stakes(stakes, timestamp);
+                }
+                if let Some(poker) = game_state.poker_game.as_mut() {
+                    poker.betting_mode = betting_mode.unwrap_or_default();
+                    game_state.rules.poker = Some(poker.rules());
+                }
+                if let Some(stakes) = blackjack_stakes {
+                    game_state.set_blackjack_stakes(stakes);
+                }
+                if let Some(blackjack) = game_state.blackjack_game.as_mut() {
+                    blackjack.hit_split_aces = hit_split_aces.unwrap_or(false);
+                    game_state.rules.blackjack = Some(blackjack.rules());
+                }
+
+                // A staked game waits for the opponent to fund their side
+                if let Some(stake) = wager {
+                    if game_state.players[1] == "BOT" || !Self::wager_allowed(game_type, game_mode, wager) {
+                        return GameOutcome::rejected("Wagers are not allowed for this game");
+                    }
+                    if !self.escrow_stake(owner, stake) {
+                        return GameOutcome::rejected("Insufficient funds for the wager");
+                    }
+                    game_state.wager = wager;
+                    game_state.wager_funded = vec![!creator_second, creator_second];
+                    game_state.status = GameStatus::WaitingForOpponent;
+                }
+                self.stamp_action(owner, timestamp);
+                self.settle_opening_deal(&mut game_state).await;
+
+                self.store_game(game_state).await;
 
                 let mut player_games = self.state
                     .player_games
@@ -529,11 +727,22 @@ impl Contract for GamePlatformContract {
                     .await
                     .unwrap_or(None)
                     .unwrap_or_default();
-                player_games.push(game_id);
+                player_games.push(game_id.clone());
                 let _ = self.state.player_games.insert(&owner, player_games);
 
-                let total = self.state.total_games_played.get().clone();
-                self.state.total_games_played.set(total + 1);
+                // A challenged player sees the game in their own list
+                if let Some(opp) = opponent.filter(|opp| *opp != owner && !opponent_is_bot) {
+                    let mut opponent_games = self.state
+                        .player_games
+                        .get(&opp)
+                        .await
+                        .unwrap_or(None)
+                        .unwrap_or_default();
+                    opponent_games.push(game_id.clone());
+                    let _ = self.state.player_games.insert(&opp, opponent_games);
+                }
+
+                self.count_new_game(game_type).await;
 
                 GameOutcome::InProgress
             }
@@ -541,7 +750,7 @@ impl Contract for GamePlatformContract {
             Operation::ChessMove { game_id, from_square, to_square, promotion } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -552,69 +761,90 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
                 if game.status != GameStatus::InProgress {
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                if game.awaiting_seed_reveal() {
+                    return GameOutcome::rejected("Waiting for seeds to draw the colors");
                 }
 
                 let owner_str = format!("{:?}", owner);
                 let player_idx = match game.game_mode {
                     GameMode::VsBot => {
                         if game.players.get(0) != Some(&owner_str) {
-                            return GameOutcome::InProgress;
+                            return GameOutcome::rejected("Not a player in this game");
                         }
                         0
                     }
                     _ => match game.players.iter().position(|p| p == &owner_str) {
                         Some(idx) => idx,
-                        None => return GameOutcome::InProgress,
+                        None => return GameOutcome::rejected("Not a player in this game"),
                     }
                 };
 
                 let mut board = match game.chess_board {
                     Some(b) => b,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a chess game"),
                 };
 
                 let expected_player = if board.active_player == Player::One { 0 } else { 1 };
                 if player_idx != expected_player {
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Not your turn");
                 }
 
                 match board.make_move(from_square, to_square, promotion, timestamp) {
-                    Ok(outcome) => {
-                        game.chess_board = Some(board);
+                    Ok(mut outcome) => {
+                        board.sign_last_move(owner_str.clone());
                         game.updated_at = timestamp;
+                        game.last_move_at = timestamp;
+
+                        game.clock_before_last_move = Some(game.clock.clone());
+                        game.clock.make_move(self.runtime.system_time(), player_idx);
+                        game.takeback_requested_by = None;
+
+                        // The bot replies straight away; if it can't, the player's move is not kept either
+                        if game.game_mode == GameMode::VsBot && outcome == GameOutcome::InProgress {
+                            outcome = match board.make_bot_move(timestamp ^ board.move_history.len() as u64, timestamp) {
+                                Ok(reply) => reply,
+                                Err(e) => return GameOutcome::Rejected(format!("Bot move failed: {}", e)),
+                            };
+                            game.clock.make_move(self.runtime.system_time(), Player::Two.index());
+                        }
+                        game.chess_board = Some(board);
 
-                        let player = if player_idx == 0 { Player::One } else { Player::Two };
-                        game.clock.make_move(self.runtime.system_time(), player);
+                        // Any move ends a pending offer: the mover withdraws their own or passes over the opponent's
+                        game.clear_draw_offer();
 
                         match &outcome {
                             GameOutcome::Winner(winner) => {
                                 game.status = GameStatus::Completed;
                                 game.winner = Some(*winner);
-                                self.record_game_result(&game, *winner).await;
+                                let reason = game.play_end_reason();
+                                self.record_game_result(&mut game, *winner, reason).await;
                             }
                             GameOutcome::Draw => {
                                 game.status = GameStatus::Completed;
-                                self.record_draw_result(&game).await;
+                                let reason = game.play_end_reason();
+                                self.record_draw_result(&mut game, reason).await;
                             }
-                            GameOutcome::InProgress => {}
+                            GameOutcome::SeatWinners(_) | GameOutcome::InProgress | GameOutcome::Rejected(_) => {}
                         }
 
-                        let _ = self.state.games.insert(&game_id, game);
+                        self.store_game(game).await;
                         outcome
                     }
-                    Err(_) => GameOutcome::InProgress,
+                    Err(e) => GameOutcome::Rejected(e),
                 }
             }
 
             Operation::PokerAction { game_id, action, bet_amount } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -625,58 +855,69 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
                 if game.status != GameStatus::InProgress {
-                    return GameOutcome::InProgress;
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                if game.awaiting_seed_reveal() {
+                    return GameOutcome::rejected("Waiting for shuffle seeds to be revealed");
                 }
 
                 let owner_str = format!("{:?}", owner);
                 let player_idx = match game.players.iter().position(|p| p == &owner_str) {
                     Some(idx) => idx,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a player in this game"),
                 };
 
                 let mut poker = match game.poker_game {
                     Some(p) => p,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a poker game"),
                 };
 
-                let expected_player = if poker.active_player == Player::One { 0 } else { 1 };
-                if player_idx != expected_player {
-                    return GameOutcome::InProgress;
-                }
-
-                match poker.make_action(action, bet_amount, timestamp) {
+                match poker.make_action(player_idx, action, bet_amount, timestamp) {
                     Ok(outcome) => {
+                        poker.sign_last_action(owner_str);
                         game.poker_game = Some(poker);
+                        game.clear_draw_offer();
                         game.updated_at = timestamp;
+                        game.last_move_at = timestamp;
+
+                        game.clock.make_move(self.runtime.system_time(), player_idx);
 
                         match &outcome {
                             GameOutcome::Winner(winner) => {
                                 game.status = GameStatus::Completed;
                                 game.winner = Some(*winner);
-                                self.record_game_result(&game, *winner).await;
+                                let reason = game.play_end_reason();
+                                self.record_game_result(&mut game, *winner, reason).await;
+                            }
+                            GameOutcome::SeatWinners(seats) => {
+                                game.status = GameStatus::Completed;
+                                let reason = game.play_end_reason();
+                                self.record_seat_results(&mut game, seats, reason).await;
                             }
                             GameOutcome::Draw => {
                                 game.status = GameStatus::Completed;
-                                self.record_draw_result(&game).await;
+                                let reason = game.play_end_reason();
+                                self.record_draw_result(&mut game, reason).await;
                             }
-                            GameOutcome::InProgress => {}
+                            GameOutcome::InProgress | GameOutcome::Rejected(_) => {}
                         }
 
-                        let _ = self.state.games.insert(&game_id, game);
+                        self.store_game(game).await;
                         outcome
                     }
-                    Err(_) => GameOutcome::InProgress,
+                    Err(e) => GameOutcome::Rejected(e),
                 }
             }
 
             Operation::BlackjackAction { game_id, action } => {
-                let _owner = match self.runtime.authenticated_signer() {
+                let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -687,49 +928,64 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
-                if game.status != GameStatus::InProgress {
-                    return GameOutcome::InProgress;
+                if game.status != GameStatus::InProgress || game.awaiting_seed_reveal() {
+                    return GameOutcome::rejected("Game is not accepting actions");
+                }
+
+                // The hand belongs to the first seat, whose clock the action charges
+                if game.players.first() != Some(&format!("{:?}", owner)) {
+                    return GameOutcome::rejected("Not a player in this game");
                 }
 
                 let mut blackjack = match game.blackjack_game {
                     Some(bj) => bj,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a blackjack game"),
                 };
 
-                match blackjack.make_action(action) {
+                match blackjack.make_action(action, timestamp) {
                     Ok(outcome) => {
                         game.blackjack_game = Some(blackjack);
                         game.updated_at = timestamp;
+                        game.last_move_at = timestamp;
+
+                        // Only the first seat plays against the dealer
+                        game.clock.make_move(self.runtime.system_time(), Player::One.index());
 
                         match &outcome {
                             GameOutcome::Winner(winner) => {
                                 game.status = GameStatus::Completed;
                                 game.winner = Some(*winner);
-                                self.record_game_result(&game, *winner).await;
+                                let reason = game.play_end_reason();
+                                self.record_game_result(&mut game, *winner, reason).await;
                             }
                             GameOutcome::Draw => {
                                 game.status = GameStatus::Completed;
-                                self.record_draw_result(&game).await;
+                                let reason = game.play_end_reason();
+                                self.record_draw_result(&mut game, reason).await;
                             }
-                            GameOutcome::InProgress => {}
+                            GameOutcome::SeatWinners(_) | GameOutcome::InProgress | GameOutcome::Rejected(_) => {}
                         }
 
-                        let _ = self.state.games.insert(&game_id, game);
+                        self.store_game(game).await;
                         outcome
                     }
-                    Err(_) => GameOutcome::InProgress,
+                    Err(e) => GameOutcome::Rejected(e),
                 }
             }
 
-            Operation::ResignGame { game_id } => {
+            Operation::ResignGame { game_id, confirm } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
+                if !confirm {
+                    return GameOutcome::rejected("Resignation must be confirmed");
+                }
+
                 let mut game = match self.state
                     .games
                     .get(&game_id)
@@ -738,23 +994,40 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
                 let owner_str = format!("{:?}", owner);
                 let player_idx = match game.players.iter().position(|p| p == &owner_str) {
                     Some(idx) => idx,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a player in this game"),
                 };
 
+                // Only a running game can be resigned; a staked game waiting on funds is aborted instead
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                // Around a bigger table every seat the resigning player does not hold takes the win
+                if game.players.len() > 2 {
+                    let winners: Vec<usize> = (0..game.players.len())
+                        .filter(|&seat| game.players[seat] != owner_str)
+                        .collect();
+                    game.status = GameStatus::Completed;
+                    game.updated_at = timestamp;
+                    self.record_seat_results(&mut game, &winners, GameEndReason::Resignation).await;
+                    self.store_game(game).await;
+                    return GameOutcome::SeatWinners(winners);
+                }
+
                 let winner = if player_idx == 0 { Player::Two } else { Player::One };
 
                 game.status = GameStatus::Completed;
                 game.winner = Some(winner);
                 game.updated_at = timestamp;
 
-                self.record_game_result(&game, winner).await;
-                let _ = self.state.games.insert(&game_id, game);
+                self.record_game_result(&mut game, winner, GameEndReason::Resignation).await;
+                self.store_game(game).await;
 
                 GameOutcome::Winner(winner)
             }
@@ -762,7 +1035,7 @@ impl Contract for GamePlatformContract {
             Operation::OfferDraw { game_id } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -773,20 +1046,29 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
                 let owner_str = format!("{:?}", owner);
                 let player_idx = match game.players.iter().position(|p| p == &owner_str) {
                     Some(idx) => idx,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a player in this game"),
                 };
 
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+                // A draw ends the whole table, which one other seat cannot agree to for the rest
+                if game.players.len() > 2 {
+                    return GameOutcome::rejected("Draws can only be agreed heads-up");
+                }
+
                 let player = if player_idx == 0 { Player::One } else { Player::Two };
                 game.draw_offered_by = Some(player);
+                game.draw_offered_at = Some(timestamp);
                 game.updated_at = timestamp;
 
-                let _ = self.state.games.insert(&game_id, game);
+                self.store_game(game).await;
 
                 GameOutcome::InProgress
             }
@@ -794,7 +1076,7 @@ impl Contract for GamePlatformContract {
             Operation::AcceptDraw { game_id } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -805,38 +1087,46 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
-                if game.draw_offered_by.is_none() {
-                    return GameOutcome::InProgress;
+                // A finished game has already been settled, and a stale offer can no longer be taken up
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+                if game.players.len() > 2 {
+                    return GameOutcome::rejected("Draws can only be agreed heads-up");
                 }
+                let offered_by = match game.live_draw_offer(timestamp, self.state.game_defaults.get()) {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("No draw offer to accept"),
+                };
 
                 let owner_str = format!("{:?}", owner);
                 let player_idx = match game.players.iter().position(|p| p == &owner_str) {
                     Some(idx) => idx,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Not a player in this game"),
                 };
 
                 let player = if player_idx == 0 { Player::One } else { Player::Two };
 
-                if game.draw_offered_by == Some(player) {
-                    return GameOutcome::InProgress;
+                if offered_by == player {
+                    return GameOutcome::rejected("Cannot accept your own draw offer");
                 }
 
                 game.status = GameStatus::Completed;
                 game.updated_at = timestamp;
 
-                self.record_draw_result(&game).await;
-                let _ = self.state.games.insert(&game_id, game);
+                self.record_draw_result(&mut game, GameEndReason::DrawAgreed).await;
+                self.store_game(game).await;
 
                 GameOutcome::Draw
             }
 
-            Operation::ClaimTimeout { game_id } => {
+            Operation::ClaimFiftyMoveRule { game_id } => {
                 let owner = match self.runtime.authenticated_signer() {
                     Some(o) => o,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
                 let mut game = match self.state
@@ -847,119 +1137,2141 @@ impl Contract for GamePlatformContract {
                     .flatten()
                 {
                     Some(g) => g,
-                    None => return GameOutcome::InProgress,
+                    None => return GameOutcome::rejected("Game not found"),
                 };
 
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
                 let owner_str = format!("{:?}", owner);
-                let player_idx = match game.players.iter().position(|p| p == &owner_str) {
-                    Some(idx) => idx,
-                    None => return GameOutcome::InProgress,
+                if !game.players.contains(&owner_str) {
+                    return GameOutcome::rejected("Not a player in this game");
+                }
+
+                match &game.chess_board {
+                    Some(board) if board.can_claim_fifty_move_draw() => {}
+                    Some(_) => return GameOutcome::rejected("Fifty moves have not passed without a capture or pawn move"),
+                    None => return GameOutcome::rejected("Not a chess game"),
+                }
+
+                game.status = GameStatus::Completed;
+                game.clear_draw_offer();
+                game.updated_at = timestamp;
+
+                self.record_draw_result(&mut game, GameEndReason::FiftyMove).await;
+                self.store_game(game).await;
+
+                GameOutcome::Draw
+            }
+
+            Operation::DeclineDraw { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
-                let player = if player_idx == 0 { Player::One } else { Player::Two };
-                let opponent = player.other();
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
 
-                if !game.clock.timed_out(self.runtime.system_time(), opponent) {
-                    return GameOutcome::InProgress;
+                let offered_by = match game.draw_offered_by {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("No draw offer to decline"),
+                };
+
+                // Only the player facing the offer may decline it
+                let owner_str = format!("{:?}", owner);
+                let offerer_idx = if offered_by == Player::One { 0 } else { 1 };
+                let holds_other_seat = game.players
+                    .iter()
+                    .enumerate()
+                    .any(|(idx, p)| idx != offerer_idx && p == &owner_str);
+                if !holds_other_seat {
+                    return GameOutcome::rejected("Only the other player can decline the offer");
                 }
 
-                game.status = GameStatus::TimedOut;
-                game.winner = Some(player);
+                game.clear_draw_offer();
                 game.updated_at = timestamp;
 
-                self.record_game_result(&game, player).await;
-                let _ = self.state.games.insert(&game_id, game);
+                self.store_game(game).await;
 
-                GameOutcome::Winner(player)
+                GameOutcome::InProgress
             }
 
-            Operation::RecordBotGame { game_type, won, moves: _, eth_address } => {
-                let owner = match self.state.eth_to_owner.get(&eth_address.to_lowercase()).await {
-                    Ok(Some(owner)) => owner,
-                    _ => {
-                        return GameOutcome::InProgress;
-                    }
+            Operation::RequestTakeback { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
                 };
 
-                let mut stats = self.state
-                    .stats
-                    .get(&owner)
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
                     .await
-                    .unwrap_or(None)
-                    .unwrap_or_default();
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
 
-                if won {
-                    stats.record_win(game_type);
-                } else {
-                    stats.record_loss(game_type);
+                if game.game_mode != GameMode::VsFriend
+                    || game.status != GameStatus::InProgress
+                    || game.clock_before_last_move.is_none()
+                {
+                    return GameOutcome::rejected("Takebacks are not available in this game");
                 }
 
-                let _ = self.state.stats.insert(&owner, stats);
+                // Only the player who made the last move may ask for it back
+                let last_mover = match &game.chess_board {
+                    Some(board) if !board.move_history.is_empty() => board.active_player.other(),
+                    _ => return GameOutcome::rejected("No move to take back"),
+                };
+                let owner_str = format!("{:?}", owner);
+                if game.players.get(last_mover.index()) != Some(&owner_str) {
+                    return GameOutcome::rejected("Only the player who moved can ask for a takeback");
+                }
 
-                if let Ok(Some(mut profile)) = self.state.user_profiles.get(&owner).await {
-                    match game_type {
-                        GameType::Chess => {
-                            if won { profile.chess_wins += 1; } else { profile.chess_losses += 1; }
-                        }
-                        GameType::Poker => {
-                            if won { profile.poker_wins += 1; } else { profile.poker_losses += 1; }
+                game.takeback_requested_by = Some(last_mover);
+                game.updated_at = timestamp;
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::AcceptTakeback { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                let requested_by = match game.takeback_requested_by {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("No takeback requested"),
+                };
+
+                // Only the player facing the request may accept it
+                let owner_str = format!("{:?}", owner);
+                if game.players.get(requested_by.other().index()) != Some(&owner_str) {
+                    return GameOutcome::rejected("Only the opponent can accept the takeback");
+                }
+
+                let mut board = match game.chess_board.take() {
+                    Some(b) => b,
+                    None => return GameOutcome::rejected("Not a chess game"),
+                };
+                if board.undo_last_move().is_none() {
+                    return GameOutcome::rejected("No move to take back");
+                }
+                game.chess_board = Some(board);
+
+                // Give the mover back the time the move cost, with their clock restarting now
+                if let Some(mut clock) = game.clock_before_last_move.take() {
+                    clock.current_turn_start = self.runtime.system_time();
+                    game.clock = clock;
+                }
+                game.takeback_requested_by = None;
+                game.clear_draw_offer();
+                game.updated_at = timestamp;
+                game.last_move_at = timestamp;
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::ClaimTimeout { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let owner_str = format!("{:?}", owner);
+                let seat = game.players.iter().position(|p| p == &owner_str);
+
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                // Card tables can be claimed once no seat has played for a while; chat, draw offers and
+                // the like don't count as play
+                let inactive = game.game_type != GameType::Chess
+                    && self.state.game_defaults.get().inactivity_expired(game.last_move_at, timestamp);
+
+                // There is no one to forfeit to at a blackjack table, so an idle player stands on every hand.
+                // Anyone seated there, or the house, can close it out
+                if game.game_type == GameType::Blackjack && !game.awaiting_seed_reveal() {
+                    if seat.is_none() && !self.is_admin(&owner) {
+                        return GameOutcome::rejected("Not a player in this game");
+                    }
+                    if !inactive {
+                        return GameOutcome::rejected("Table has not been idle long enough");
+                    }
+                    let mut blackjack = match game.blackjack_game {
+                        Some(bj) => bj,
+                        None => return GameOutcome::rejected("Not a blackjack game"),
+                    };
+                    let outcome = match blackjack.make_action(BlackjackAction::StandAll, timestamp) {
+                        Ok(outcome) => outcome,
+                        Err(e) => return GameOutcome::Rejected(e),
+                    };
+                    game.blackjack_game = Some(blackjack);
+                    game.updated_at = timestamp;
+                    game.last_move_at = timestamp;
+
+                    match &outcome {
+                        GameOutcome::Winner(winner) => {
+                            game.status = GameStatus::Completed;
+                            game.winner = Some(*winner);
+                            let reason = game.play_end_reason();
+                            self.record_game_result(&mut game, *winner, reason).await;
                         }
-                        GameType::Blackjack => {
-                            if won { profile.blackjack_wins += 1; } else { profile.blackjack_losses += 1; }
+                        GameOutcome::Draw => {
+                            game.status = GameStatus::Completed;
+                            let reason = game.play_end_reason();
+                            self.record_draw_result(&mut game, reason).await;
                         }
+                        GameOutcome::SeatWinners(_) | GameOutcome::InProgress | GameOutcome::Rejected(_) => {}
                     }
-                    profile.total_games += 1;
-                    profile.last_active = timestamp;
 
-                    if won {
-                        if profile.current_streak >= 0 {
-                            profile.current_streak += 1;
-                        } else {
-                            profile.current_streak = 1;
-                        }
-                        if profile.current_streak > profile.best_streak as i32 {
-                            profile.best_streak = profile.current_streak as u32;
-                        }
-                    } else {
-                        if profile.current_streak <= 0 {
-                            profile.current_streak -= 1;
-                        } else {
-                            profile.current_streak = -1;
-                        }
-                    }
+                    self.store_game(game).await;
+                    return outcome;
+                }
+
+                let player_idx = match seat {
+                    Some(idx) => idx,
+                    None => return GameOutcome::rejected("Not a player in this game"),
+                };
+
+                // A seeded table that never gets its seeds is forfeited by whoever held it up
+                if game.awaiting_seed_reveal() {
+                    let outcome = self.forfeit_seed_holdouts(&mut game, player_idx, timestamp).await;
+                    if !matches!(outcome, GameOutcome::Rejected(_)) {
+                        self.store_game(game).await;
+                    }
+                    return outcome;
+                }
+
+                // Only the seat that is due to act can run out of time
+                let idle_seat = match game.seat_to_move() {
+                    Some(seat) if seat != player_idx => seat,
+                    _ => return GameOutcome::rejected("Opponent is not on the move"),
+                };
+
+                if !inactive && !game.clock.timed_out(self.runtime.system_time(), idle_seat) {
+                    return GameOutcome::rejected("Opponent still has time");
+                }
+
+                // Around a bigger table the idle seat folds and the others play on
+                if game.players.len() > 2 {
+                    let outcome = self.fold_idle_seat(&mut game, idle_seat, timestamp).await;
+                    self.store_game(game).await;
+                    return outcome;
+                }
+
+                let player = match Player::from_seat(player_idx) {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Not a player in this game"),
+                };
+                game.status = GameStatus::TimedOut;
+                game.winner = Some(player);
+                game.updated_at = timestamp;
+
+                self.record_game_result(&mut game, player, GameEndReason::Timeout).await;
+                self.store_game(game).await;
+
+                GameOutcome::Winner(player)
+            }
+
+            Operation::AbortGame { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let owner_str = format!("{:?}", owner);
+                if !game.players.contains(&owner_str) {
+                    return GameOutcome::rejected("Not a player in this game");
+                }
+
+                // Brackets and matches only advance on a result, so their games have to be played or forfeited
+                if game.tournament_id.is_some() || game.match_id.is_some() {
+                    return GameOutcome::rejected("Tournament and match games cannot be aborted");
+                }
+
+                // An unfunded staked game can be called off at any time
+                let awaiting_stake = game.status == GameStatus::WaitingForOpponent && game.wager.is_some();
+                if !awaiting_stake {
+                    if game.status != GameStatus::InProgress || game.has_play_started() {
+                        return GameOutcome::rejected("Game can no longer be aborted");
+                    }
+
+                    if timestamp > game.created_at + ABORT_WINDOW_MICROS {
+                        return GameOutcome::rejected("Abort window has passed");
+                    }
+                }
+
+                // No result is recorded for an aborted game
+                game.status = GameStatus::Cancelled;
+                game.updated_at = timestamp;
+                self.refund_game(&game);
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::WithdrawChallenge { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::WaitingForOpponent || game.wager.is_none() {
+                    return GameOutcome::rejected("No challenge to withdraw");
+                }
+
+                // The challenger is the seat that has already staked
+                let owner_str = format!("{:?}", owner);
+                let is_challenger = (0..game.players.len()).any(|i| game.players[i] == owner_str && game.wager_funded[i]);
+                if !is_challenger {
+                    return GameOutcome::rejected("Only the challenger can withdraw");
+                }
+
+                // Nothing was played, so no result is recorded
+                game.status = GameStatus::Cancelled;
+                game.updated_at = timestamp;
+                self.refund_game(&game);
+
+                for player in &game.players {
+                    if let Some(player_owner) = self.parse_owner_from_debug_str(player) {
+                        self.unlist_game(player_owner, &game_id).await;
+                    }
+                }
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::SendGameInvite { target_chain, invitee, game_type, timeouts } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if game_type == GameType::Blackjack || invitee == owner {
+                    return GameOutcome::rejected("Invalid invite");
+                }
+
+                if !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
+                let profile = match self.state
+                    .user_profiles
+                    .get(&owner)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Player not registered"),
+                };
+                self.stamp_action(owner, timestamp);
+
+                // The id is picked here, from this chain's own counter, so no two invites can share one
+                let invite_number = *self.state.next_invite_id.get();
+                self.state.next_invite_id.set(invite_number + 1);
+                let game_id = format!("invite_{}_{:x}", self.runtime.chain_id(), invite_number);
+                let expires_at = timestamp.saturating_add(INVITE_TTL_MICROS);
+
+                let invite = SentInvite {
+                    game_id: game_id.clone(),
+                    host_chain: target_chain,
+                    inviter: owner,
+                    invitee,
+                    game_type,
+                    expires_at,
+                    status: GameStatus::WaitingForOpponent,
+                    winner: None,
+                    result_reason: None,
+                };
+                let _ = self.state.sent_invites.insert(&game_id, invite);
+
+                let message = Message::GameInvite {
+                    game_id,
+                    game_type,
+                    timeouts: timeouts.unwrap_or_default(),
+                    inviter_name: profile.username,
+                    invitee,
+                    expires_at,
+                };
+                self.runtime
+                    .prepare_message(message)
+                    .with_authentication()
+                    .send_to(target_chain);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::AcceptGameInvite { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let origin = match game.invite_origin {
+                    Some(chain) if game.status == GameStatus::WaitingForOpponent => chain,
+                    _ => return GameOutcome::rejected("No pending invite"),
+                };
+
+                // Only the invitee, who holds the second seat, can accept
+                if game.players.get(1) != Some(&format!("{:?}", owner)) {
+                    return GameOutcome::rejected("Only the invitee can accept");
+                }
+
+                if game.invite_expires_at.is_some_and(|expires_at| timestamp >= expires_at) {
+                    self.close_invite(game, origin, timestamp).await;
+                    return GameOutcome::rejected("Invite has expired");
+                }
+
+                game.status = GameStatus::InProgress;
+                game.clock = Clock::with_seats(self.runtime.system_time(), &game.timeouts, game.players.len());
+                game.updated_at = timestamp;
+
+                self.runtime
+                    .prepare_message(Message::AcceptInvite { game_id: game_id.clone() })
+                    .with_authentication()
+                    .send_to(origin);
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::DeclineGameInvite { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let origin = match game.invite_origin {
+                    Some(chain) if game.status == GameStatus::WaitingForOpponent => chain,
+                    _ => return GameOutcome::rejected("No pending invite"),
+                };
+                if game.players.get(1) != Some(&format!("{:?}", owner)) {
+                    return GameOutcome::rejected("Only the invitee can decline");
+                }
+
+                self.close_invite(game, origin, timestamp).await;
+                GameOutcome::InProgress
+            }
+
+            Operation::FundWager { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let stake = match game.wager {
+                    Some(w) if game.status == GameStatus::WaitingForOpponent => w,
+                    _ => return GameOutcome::rejected("No stake to fund"),
+                };
+
+                let owner_str = format!("{:?}", owner);
+                let seat = match (0..game.players.len()).find(|&i| game.players[i] == owner_str && !game.wager_funded[i]) {
+                    Some(i) => i,
+                    None => return GameOutcome::rejected("No unfunded seat for you"),
+                };
+
+                if self.escrow_stake(owner, stake) {
+                    game.wager_funded[seat] = true;
+                    // Play starts, and the clock runs, once every stake is in
+                    if game.wager_funded.iter().all(|&funded| funded) {
+                        game.status = GameStatus::InProgress;
+                        game.clock = Clock::with_seats(self.runtime.system_time(), &game.timeouts, game.players.len());
+                    }
+                } else {
+                    game.status = GameStatus::Cancelled;
+                    self.refund_game(&game);
+                }
+                game.updated_at = timestamp;
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::SpectateGame { game_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::InProgress || game.spectators.contains(&owner) {
+                    return GameOutcome::rejected("Cannot spectate this game");
+                }
+
+                game.spectators.push(owner);
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::SendChatMessage { game_id, text } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let text = text.trim().to_string();
+                if text.is_empty() || text.chars().count() > CHAT_MAX_LEN {
+                    return GameOutcome::rejected("Chat message is empty or too long");
+                }
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                // Seated players chat under their game name, spectators under their profile name
+                let owner_str = format!("{:?}", owner);
+                let sender_name = match game.players.iter().position(|p| p == &owner_str) {
+                    Some(idx) => game.player_names.get(idx).cloned().unwrap_or_default(),
+                    None if game.spectators.contains(&owner) => {
+                        match self.state.user_profiles.get(&owner).await.ok().flatten() {
+                            Some(profile) => profile.username,
+                            None => return GameOutcome::rejected("Player not registered"),
+                        }
+                    }
+                    None => return GameOutcome::rejected("Not a player or spectator in this game"),
+                };
+
+                push_chat_message(&mut game.chat, ChatMessage { sender_name, text, timestamp });
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::ReportPlayer { game_id, target, reason } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let reason = reason.trim().to_string();
+                if reason.is_empty() || reason.chars().count() > REPORT_REASON_MAX_LEN {
+                    return GameOutcome::rejected("Report reason is empty or too long");
+                }
+
+                let game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                // Both sides of the report must have been seated in the game
+                let reporter = format!("{:?}", owner);
+                let target = format!("{:?}", target);
+                if !game.players.contains(&reporter) || !game.players.contains(&target) {
+                    return GameOutcome::rejected("Both players must have been seated in the game");
+                }
+                if reporter == target {
+                    return GameOutcome::rejected("Players cannot report themselves");
+                }
+
+                let report_key = (reporter.clone(), target.clone(), game_id.clone());
+                if self.state.reported.contains_key(&report_key).await.unwrap_or(false) {
+                    return GameOutcome::rejected("Player already reported for this game");
+                }
+
+                let report_id = *self.state.next_report_id.get();
+                let report = PlayerReport { report_id, reporter, target, game_id, reason, timestamp };
+                let _ = self.state.reports.insert(&report_id, report);
+                let _ = self.state.reported.insert(&report_key, report_id);
+                self.state.next_report_id.set(report_id + 1);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::RequestRematch { game_id, swap_colors } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::Completed || game.rematch_game_id.is_some() {
+                    return GameOutcome::rejected("Game is not open for a rematch");
+                }
+
+                let owners: Vec<AccountOwner> = game.players
+                    .iter()
+                    .filter_map(|p| self.parse_owner_from_debug_str(p))
+                    .collect();
+                if owners.len() != 2 || !owners.contains(&owner) {
+                    return GameOutcome::rejected("Not a player in this game");
+                }
+
+                for (i, seat) in owners.iter().enumerate() {
+                    if seat == &owner {
+                        game.rematch_requests[i] = true;
+                    }
+                }
+                if swap_colors {
+                    game.rematch_keep_seats = false;
+                }
+
+                if game.rematch_requests.iter().all(|requested| *requested) {
+                    // Colors and the button alternate unless both players asked to keep their seats
+                    let players = rematch_seating([owners[0], owners[1]], !game.rematch_keep_seats);
+                    let mut rematch = self
+                        .new_pvp_game(game.game_type, game.game_mode, &players, game.timeouts.clone(), timestamp)
+                        .await;
+                    // The table is dealt again at the stakes the finished game was played for
+                    if let Some(rules) = &game.rules.poker {
+                        let stakes = PokerStakes {
+                            starting_chips: rules.buy_in,
+                            small_blind: rules.small_blind,
+                            big_blind: rules.big_blind,
+                        };
+                        rematch.set_poker_stakes(stakes, timestamp);
+                    }
+                    if let Some(rules) = &game.rules.blackjack {
+                        rematch.set_blackjack_stakes(BlackjackStakes { bet: rules.bet, bankroll: rules.bankroll });
+                    }
+                    self.settle_opening_deal(&mut rematch).await;
+                    let rematch_id = self.insert_pvp_game(rematch, &players).await;
+                    game.rematch_game_id = Some(rematch_id);
+                }
+
+                self.store_game(game).await;
+                GameOutcome::InProgress
+            }
+
+            Operation::PokerRebuy { game_id, amount } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                // Only a finished friendly game outside any event or stake can be continued, and only once: play carries
+                // on at a new game so the finished one keeps the result it already booked
+                if game.status != GameStatus::Completed
+                    || game.game_mode != GameMode::VsFriend
+                    || game.wager.is_some()
+                    || game.tournament_id.is_some()
+                    || game.match_id.is_some()
+                    || game.rematch_game_id.is_some()
+                {
+                    return GameOutcome::rejected("Rebuys are not available in this game");
+                }
+
+                let mut poker = match game.poker_game.clone() {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Not a poker game"),
+                };
+                // Someone sitting in several seats buys back into the first of them that went bust
+                let owner_str = format!("{:?}", owner);
+                let seats: Vec<usize> = (0..game.players.len()).filter(|&i| game.players[i] == owner_str).collect();
+                let seat = match seats.iter().find(|&&i| poker.player_chips.get(i) == Some(&0)).or(seats.first()) {
+                    Some(&s) => s,
+                    None => return GameOutcome::rejected("Not a player in this game"),
+                };
+                let max_buy_in = match &game.rules.poker {
+                    Some(rules) => rules.buy_in,
+                    None => return GameOutcome::rejected("Not a poker game"),
+                };
+
+                // The placeholder deal is reshuffled from the seats' committed seeds, like any new table
+                if let Err(e) = poker.rebuy(seat, amount, max_buy_in, timestamp) {
+                    return GameOutcome::Rejected(e);
+                }
+
+                let mut next = FullGameState::new(
+                    self.new_game_id(timestamp),
+                    game.game_type,
+                    game.game_mode,
+                    game.players.clone(),
+                    game.player_names.clone(),
+                    timestamp,
+                    game.timeouts.clone(),
+                );
+                next.rules = game.rules.clone();
+                next.poker_game = Some(poker);
+
+                let owners: Vec<AccountOwner> = game
+                    .players
+                    .iter()
+                    .filter_map(|p| self.parse_owner_from_debug_str(p))
+                    .collect();
+                let next_id = self.insert_pvp_game(next, &owners).await;
+                game.rematch_game_id = Some(next_id);
+                self.store_game(game).await;
+                GameOutcome::InProgress
+            }
+
+            Operation::CommitSeed { game_id, commitment } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::InProgress || !game.uses_seed_shuffle() {
+                    return GameOutcome::rejected("Game does not use shuffle seeds");
+                }
+
+                let owner_str = format!("{:?}", owner);
+                let player_idx = match game.players.iter().position(|p| p == &owner_str) {
+                    Some(idx) => idx,
+                    None => return GameOutcome::rejected("Not a player in this game"),
+                };
+
+                // Commitments are locked in once anyone has revealed
+                if game.seed_reveals.iter().any(Option::is_some) || game.seed_commitments[player_idx].is_some() {
+                    return GameOutcome::rejected("Seed commitments are locked");
+                }
+
+                game.seed_commitments[player_idx] = Some(commitment.to_lowercase());
+                game.updated_at = timestamp;
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::RevealSeed { game_id, seed } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                let owner_str = format!("{:?}", owner);
+                let player_idx = match game.players.iter().position(|p| p == &owner_str) {
+                    Some(idx) => idx,
+                    None => return GameOutcome::rejected("Not a player in this game"),
+                };
+
+                // Reveals open only after every seated human has committed
+                let commitments_in = game.seed_seats().iter().all(|&seat| game.seed_commitments[seat].is_some());
+                if !game.awaiting_seed_reveal() || !commitments_in || game.seed_reveals[player_idx].is_some() {
+                    return GameOutcome::rejected("Seed reveals are not open");
+                }
+
+                let valid = game.seed_commitments[player_idx]
+                    .as_deref()
+                    .is_some_and(|commitment| verify_seed_reveal(commitment, seed));
+                if !valid {
+                    return GameOutcome::rejected("Seed does not match the commitment");
+                }
+
+                game.seed_reveals[player_idx] = Some(seed);
+
+                // The last reveal deals the table and starts the clock
+                if !game.awaiting_seed_reveal() {
+                    let reveals: Vec<u64> = game.seed_reveals.iter().flatten().copied().collect();
+                    let combined = combine_seeds(&reveals);
+                    game.settle_seating(combined);
+                    if let Some(poker) = game.poker_game.as_mut() {
+                        if let Err(e) = poker.reseed(combined) {
+                            return GameOutcome::Rejected(e);
+                        }
+                    }
+                    if let Some(blackjack) = game.blackjack_game.as_mut() {
+                        if let Err(e) = blackjack.reseed(combined) {
+                            return GameOutcome::Rejected(e);
+                        }
+                    }
+                    game.clock.current_turn_start = self.runtime.system_time();
+                    game.last_move_at = timestamp;
+                    self.settle_opening_deal(&mut game).await;
+                }
+
+                game.updated_at = timestamp;
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+
+            Operation::RecordBotGame { game_type, won, moves: _, eth_address } => {
+                let owner = match self.state.eth_to_owner.get(&eth_address.to_lowercase()).await {
+                    Ok(Some(owner)) => owner,
+                    _ => {
+                        return GameOutcome::rejected("Player not registered");
+                    }
+                };
+
+                // Bot chess results seed a rating, so only the player can vouch for their own
+                if game_type == GameType::Chess && self.runtime.authenticated_signer() != Some(owner) {
+                    return GameOutcome::rejected("Only the player can record their bot chess games");
+                }
+
+                let mut stats = self.state
+                    .stats
+                    .get(&owner)
+                    .await
+                    .unwrap_or(None)
+                    .unwrap_or_default();
+
+                if won {
+                    stats.record_win(game_type);
+                } else {
+                    stats.record_loss(game_type);
+                }
+
+                let _ = self.state.stats.insert(&owner, stats);
+
+                if let Ok(Some(mut profile)) = self.state.user_profiles.get(&owner).await {
+                    match game_type {
+                        GameType::Chess => {
+                            if won { profile.chess_wins += 1; } else { profile.chess_losses += 1; }
+                            if won { profile.bot_chess_wins += 1; } else { profile.bot_chess_losses += 1; }
+                        }
+                        GameType::Poker => {
+                            if won { profile.poker_wins += 1; } else { profile.poker_losses += 1; }
+                        }
+                        GameType::Blackjack => {
+                            if won { profile.blackjack_wins += 1; } else { profile.blackjack_losses += 1; }
+                        }
+                    }
+                    profile.total_games += 1;
+                    profile.last_active = timestamp;
+
+                    if won {
+                        if profile.current_streak >= 0 {
+                            profile.current_streak += 1;
+                        } else {
+                            profile.current_streak = 1;
+                        }
+                        if profile.current_streak > profile.best_streak as i32 {
+                            profile.best_streak = profile.current_streak as u32;
+                        }
+
+                        let mut earned = vec![AchievementKind::FirstWin];
+                        if profile.best_streak >= STREAK_ACHIEVEMENT_LENGTH {
+                            earned.push(AchievementKind::TenGameStreak);
+                        }
+                        self.unlock_achievements(owner, earned, None).await;
+                    } else {
+                        if profile.current_streak <= 0 {
+                            profile.current_streak -= 1;
+                        } else {
+                            profile.current_streak = -1;
+                        }
+                    }
+
+                    self.add_or_update_leaderboard_entry(&profile).await;
+                    let _ = self.state.user_profiles.insert(&owner, profile);
+                }
+
+                if won {
+                    GameOutcome::Winner(Player::One)
+                } else {
+                    GameOutcome::Winner(Player::Two)
+                }
+            }
+
+            Operation::JoinMatchmaking { game_type } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if game_type == GameType::Blackjack {
+                    return GameOutcome::rejected("Blackjack is played against the house");
+                }
+
+                let profile = match self.state.user_profiles.get(&owner).await.ok().flatten() {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Player not registered"),
+                };
+
+                let mut queue = self.state
+                    .matchmaking_queue
+                    .get(&game_type)
+                    .await
+                    .unwrap_or(None)
+                    .unwrap_or_default();
+
+                if queue.contains(&owner) {
+                    return GameOutcome::rejected("Already in the queue");
+                }
+
+                // Players are compared on the rating of the game they queue for; an unrated game pairs anyone
+                let rating = profile.rating(game_type).unwrap_or(0);
+                let mut queue_ratings = Vec::with_capacity(queue.len());
+                for queued in &queue {
+                    // A blocked pairing is kept out of reach like an unregistered entry
+                    if self.is_blocked_pair(owner, *queued).await {
+                        queue_ratings.push(u32::MAX);
+                        continue;
+                    }
+                    let queued_rating = match self.state.user_profiles.get(queued).await {
+                        Ok(Some(p)) => p.rating(game_type).unwrap_or(0),
+                        _ => u32::MAX,
+                    };
+                    queue_ratings.push(queued_rating);
+                }
+
+                match find_matchmaking_opponent(rating, &queue_ratings) {
+                    Some(idx) => {
+                        let opponent = queue.remove(idx);
+                        let timeouts = self.state.default_timeouts.get().clone();
+                        self.create_pvp_game(game_type, GameMode::VsFriend, [opponent, owner], timeouts, timestamp).await;
+                    }
+                    None => queue.push(owner),
+                }
+
+                let _ = self.state.matchmaking_queue.insert(&game_type, queue);
+                GameOutcome::InProgress
+            }
+
+            Operation::LeaveMatchmaking { game_type } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut queue = self.state
+                    .matchmaking_queue
+                    .get(&game_type)
+                    .await
+                    .unwrap_or(None)
+                    .unwrap_or_default();
+                queue.retain(|queued| queued != &owner);
+                let _ = self.state.matchmaking_queue.insert(&game_type, queue);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::SeedEloFromBots => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut profile = match self.state.user_profiles.get(&owner).await.ok().flatten() {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Player not registered"),
+                };
+
+                if profile.elo_seeded || profile.has_rated_chess_games() {
+                    return GameOutcome::rejected("Rating already established");
+                }
+
+                // No bot games means no signal, so the one-time seed is kept for later
+                if profile.bot_chess_games() == 0 {
+                    return GameOutcome::rejected("No bot games to seed from");
+                }
+
+                let delta = bot_elo_adjustment(profile.bot_chess_wins, profile.bot_chess_losses);
+                let mut stats = self.state.stats.get(&owner).await.ok().flatten().unwrap_or_default();
+                stats.chess_elo = profile.chess_elo;
+                stats.update_elo(delta, self.state.game_defaults.get());
+                profile.chess_elo = stats.chess_elo;
+                profile.elo_seeded = true;
+                profile.last_active = timestamp;
+
+                let _ = self.state.stats.insert(&owner, stats);
+                self.add_or_update_leaderboard_entry(&profile).await;
+                let _ = self.state.user_profiles.insert(&owner, profile);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::DeleteAccount => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let profile = match self.state
+                    .user_profiles
+                    .get(&owner)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(p) => p,
+                    None => return GameOutcome::rejected("Player not registered"),
+                };
+
+                // Unfinished games must be resigned first
+                let game_ids = self.state.player_games.get(&owner).await.unwrap_or(None).unwrap_or_default();
+                for game_id in &game_ids {
+                    if let Ok(Some(game)) = self.state.games.get(game_id).await {
+                        if game.status == GameStatus::InProgress || game.status == GameStatus::WaitingForOpponent {
+                            return GameOutcome::rejected("Finish or cancel open games first");
+                        }
+                    }
+                }
+
+                self.withdraw_player(owner).await;
+
+                for username in profile.merged_usernames.iter().chain([&profile.username]) {
+                    let username = username.to_lowercase();
+                    if self.state.username_to_owner.get(&username).await.ok().flatten() == Some(owner) {
+                        let _ = self.state.username_to_owner.remove(&username);
+                    }
+                }
+                let eth_address = profile.eth_address.to_lowercase();
+                if self.state.eth_to_owner.get(&eth_address).await.ok().flatten() == Some(owner) {
+                    let _ = self.state.eth_to_owner.remove(&eth_address);
+                }
+                let _ = self.state.user_profiles.remove(&owner);
+                let _ = self.state.stats.remove(&owner);
+                let _ = self.state.achievements.remove(&owner);
+                let _ = self.state.blocks.remove(&owner);
+                let _ = self.state.available_since.remove(&owner);
+
+                let mut entries = self.state.leaderboard.get().clone();
+                entries.retain(|e| e.eth_address != profile.eth_address);
+                self.state.leaderboard.set(entries);
+
+                let total = *self.state.total_users.get();
+                self.state.total_users.set(total.saturating_sub(1));
+
+                GameOutcome::InProgress
+            }
+
+            Operation::BlockPlayer { target } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut blocks = self.blocked_players(owner).await;
+                if target == owner || blocks.contains(&target) || blocks.len() >= MAX_BLOCKED_PLAYERS {
+                    return GameOutcome::rejected("Cannot block this player");
+                }
+                blocks.push(target);
+                let _ = self.state.blocks.insert(&owner, blocks);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::UnblockPlayer { target } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                let mut blocks = self.blocked_players(owner).await;
+                blocks.retain(|blocked| blocked != &target);
+                if blocks.is_empty() {
+                    let _ = self.state.blocks.remove(&owner);
+                } else {
+                    let _ = self.state.blocks.insert(&owner, blocks);
+                }
+
+                GameOutcome::InProgress
+            }
+
+            Operation::CreateTournament { game_type, max_participants, allow_hints } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
+                // Blackjack is played against the house, so it has no bracket
+                if game_type == GameType::Blackjack || !is_valid_bracket_size(max_participants) {
+                    return GameOutcome::rejected("Invalid tournament settings");
+                }
+
+                if self.state.user_profiles.get(&owner).await.ok().flatten().is_none() {
+                    return GameOutcome::rejected("Player not registered");
+                }
+
+                self.stamp_action(owner, timestamp);
+
+                // The chain's own counter keeps ids unique when tournaments open in the same block
+                let tournament_number = *self.state.next_tournament_id.get();
+                self.state.next_tournament_id.set(tournament_number + 1);
+                let tournament_id = format!("tournament_{:x}_{:x}", timestamp, tournament_number);
+
+                let owner_str = format!("{:?}", owner);
+                let mut tournament = Tournament::new(tournament_id.clone(), game_type, owner_str.clone(), max_participants, timestamp);
+                tournament.allow_hints = allow_hints.unwrap_or(true);
+                let _ = tournament.join(owner_str);
+
+                let _ = self.state.tournaments.insert(&tournament_id, tournament);
+                let mut tournament_ids = self.state.active_tournament_ids.get().clone();
+                tournament_ids.push(tournament_id);
+                self.state.active_tournament_ids.set(tournament_ids);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::CreateMatch { opponent, game_type, best_of } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.check_rate_limit(owner, timestamp).await {
+                    return GameOutcome::rejected("Too many actions, try again shortly");
+                }
+
+                // Blackjack is played against the house, so there is no one to score against
+                if game_type == GameType::Blackjack || !is_valid_best_of(best_of) {
+                    return GameOutcome::rejected("Invalid match settings");
+                }
+
+                for player in [owner, opponent] {
+                    if self.state.user_profiles.get(&player).await.ok().flatten().is_none() {
+                        return GameOutcome::rejected("Player not registered");
+                    }
+                }
+                if self.is_blocked_pair(owner, opponent).await {
+                    return GameOutcome::rejected("A block is in place with this opponent");
+                }
+
+                self.stamp_action(owner, timestamp);
+
+                // The chain's own counter keeps ids unique when matches start in the same block
+                let match_number = *self.state.next_match_id.get();
+                self.state.next_match_id.set(match_number + 1);
+                let match_id = format!("match_{:x}_{:x}", timestamp, match_number);
+
+                let players = vec![format!("{:?}", owner), format!("{:?}", opponent)];
+                let mut match_state = MatchState::new(match_id.clone(), game_type, players, best_of, timestamp);
+                self.start_match_game(&mut match_state, timestamp).await;
+
+                let _ = self.state.matches.insert(&match_id, match_state);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::JoinTournament { tournament_id } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if self.state.user_profiles.get(&owner).await.ok().flatten().is_none() {
+                    return GameOutcome::rejected("Player not registered");
+                }
+
+                let mut tournament = match self.state
+                    .tournaments
+                    .get(&tournament_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(t) => t,
+                    None => return GameOutcome::rejected("Tournament not found"),
+                };
+
+                if let Err(e) = tournament.join(format!("{:?}", owner)) {
+                    return GameOutcome::Rejected(e);
+                }
+
+                // The first round starts as soon as the bracket is full
+                if tournament.is_full() {
+                    self.start_tournament_round(&mut tournament, timestamp).await;
+                }
+
+                let _ = self.state.tournaments.insert(&tournament_id, tournament);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::UpdateGameDefaults { defaults } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.is_admin(&owner) || defaults.elo_floor > defaults.elo_ceiling {
+                    return GameOutcome::rejected("Admin only, and the Elo floor must not exceed the ceiling");
+                }
+
+                self.state.game_defaults.set(defaults);
+                GameOutcome::InProgress
+            }
+
+            Operation::MergeAccounts { keep_owner, merge_owner } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.is_admin(&owner) || keep_owner == merge_owner {
+                    return GameOutcome::rejected("Admin only, with two different accounts");
+                }
+
+                let (mut kept, merged) = match (
+                    self.state.user_profiles.get(&keep_owner).await.ok().flatten(),
+                    self.state.user_profiles.get(&merge_owner).await.ok().flatten(),
+                ) {
+                    (Some(k), Some(m)) => (k, m),
+                    _ => return GameOutcome::rejected("Both accounts must be registered"),
+                };
+
+                // Lobby seats, queue places and brackets are held by name, so they are given up before a merge
+                if self.has_open_commitments(merge_owner).await {
+                    return GameOutcome::rejected("Account is still in a lobby, queue, tournament or match");
+                }
+
+                // Combine stats
+                kept.merge_from(&merged);
+                let mut kept_stats = self.state.stats.get(&keep_owner).await.unwrap_or(None).unwrap_or_default();
+                if let Ok(Some(merged_stats)) = self.state.stats.get(&merge_owner).await {
+                    kept_stats.merge_from(&merged_stats);
+                }
+                let _ = self.state.stats.insert(&keep_owner, kept_stats);
+
+                // Reassign games
+                let keep_str = format!("{:?}", keep_owner);
+                let merge_str = format!("{:?}", merge_owner);
+                let merged_games = self.state.player_games.get(&merge_owner).await.unwrap_or(None).unwrap_or_default();
+                let mut kept_games = self.state.player_games.get(&keep_owner).await.unwrap_or(None).unwrap_or_default();
+                for game_id in merged_games {
+                    if let Ok(Some(mut game)) = self.state.games.get(&game_id).await {
+                        for (player, name) in game.players.iter_mut().zip(game.player_names.iter_mut()) {
+                            if *player == merge_str {
+                                *player = keep_str.clone();
+                                *name = kept.username.clone();
+                            }
+                        }
+                        self.store_game(game).await;
+                    }
+                    if !kept_games.contains(&game_id) {
+                        kept_games.push(game_id);
+                    }
+                }
+                let _ = self.state.player_games.insert(&keep_owner, kept_games);
+
+                self.merge_achievements(keep_owner, merge_owner).await;
+                self.merge_blocks(keep_owner, merge_owner).await;
+                self.merge_head_to_head(keep_owner, merge_owner).await;
+
+                // Remove the merged profile; its usernames now find the kept account
+                for username in merged.merged_usernames.iter().chain([&merged.username]) {
+                    let username = username.to_lowercase();
+                    if self.state.username_to_owner.get(&username).await.ok().flatten() == Some(merge_owner) {
+                        let _ = self.state.username_to_owner.insert(&username, keep_owner);
+                    }
+                }
+                let merged_eth = merged.eth_address.to_lowercase();
+                if self.state.eth_to_owner.get(&merged_eth).await.ok().flatten() == Some(merge_owner) {
+                    if merged_eth == kept.eth_address.to_lowercase() {
+                        let _ = self.state.eth_to_owner.insert(&merged_eth, keep_owner);
+                    } else {
+                        let _ = self.state.eth_to_owner.remove(&merged_eth);
+                    }
+                }
+                let _ = self.state.user_profiles.remove(&merge_owner);
+                let _ = self.state.stats.remove(&merge_owner);
+                let _ = self.state.player_games.remove(&merge_owner);
+                let _ = self.state.available_since.remove(&merge_owner);
+
+                let mut entries = self.state.leaderboard.get().clone();
+                entries.retain(|e| e.eth_address != merged.eth_address || e.eth_address == kept.eth_address);
+                self.state.leaderboard.set(entries);
+
+                let total = *self.state.total_users.get();
+                self.state.total_users.set(total.saturating_sub(1));
+
+                self.add_or_update_leaderboard_entry(&kept).await;
+                let _ = self.state.user_profiles.insert(&keep_owner, kept);
+
+                GameOutcome::InProgress
+            }
+
+            Operation::ForceResolve { game_id, winner_seat } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.is_admin(&owner) {
+                    return GameOutcome::rejected("Admin only");
+                }
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::InProgress && game.status != GameStatus::WaitingForOpponent {
+                    return GameOutcome::rejected("Game is already over");
+                }
+
+                let winner_seat = winner_seat.map(|seat| seat as usize);
+                if winner_seat.is_some_and(|seat| seat >= game.players.len()) {
+                    return GameOutcome::rejected("No such seat");
+                }
+
+                game.status = GameStatus::Completed;
+                game.updated_at = timestamp;
+
+                // A full poker table settles every seat: the named one wins and the rest lose
+                let outcome = match winner_seat {
+                    Some(seat) if game.players.len() > 2 => {
+                        self.record_seat_results(&mut game, &[seat], GameEndReason::AdminDecision).await;
+                        GameOutcome::SeatWinners(vec![seat])
+                    }
+                    Some(seat) => {
+                        let winner = match Player::from_seat(seat) {
+                            Some(winner) => winner,
+                            None => return GameOutcome::rejected("No such seat"),
+                        };
+                        game.winner = Some(winner);
+                        self.record_game_result(&mut game, winner, GameEndReason::AdminDecision).await;
+                        GameOutcome::Winner(winner)
+                    }
+                    None => {
+                        self.record_draw_result(&mut game, GameEndReason::AdminDecision).await;
+                        GameOutcome::Draw
+                    }
+                };
+
+                self.store_game(game).await;
+                outcome
+            }
+
+            Operation::SetArbiter { arbiter } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.is_admin(&owner) {
+                    return GameOutcome::rejected("Admin only");
+                }
+
+                self.state.arbiter.set(arbiter);
+                GameOutcome::InProgress
+            }
+
+            Operation::AddTime { game_id, seat, seconds } => {
+                let owner = match self.runtime.authenticated_signer() {
+                    Some(o) => o,
+                    None => return GameOutcome::rejected("Operation must be signed"),
+                };
+
+                if !self.is_arbiter(&owner) {
+                    return GameOutcome::rejected("Arbiter only");
+                }
+
+                let mut game = match self.state
+                    .games
+                    .get(&game_id)
+                    .await
+                    .ok()
+                    .flatten()
+                {
+                    Some(g) => g,
+                    None => return GameOutcome::rejected("Game not found"),
+                };
+
+                if game.status != GameStatus::InProgress {
+                    return GameOutcome::rejected("Game is not in progress");
+                }
+
+                let seat = seat as usize;
+                if seat >= game.players.len() {
+                    return GameOutcome::rejected("No such seat");
+                }
+
+                game.clock.add_time(seat, TimeDelta::from_secs(seconds));
+                game.updated_at = timestamp;
+
+                self.store_game(game).await;
+
+                GameOutcome::InProgress
+            }
+        }
+    }
+
+    async fn execute_message(&mut self, message: Self::Message) {
+        // Messages are only acted on with the sender's authentication and a known origin
+        let (sender, origin) = match (self.runtime.authenticated_signer(), self.runtime.message_origin_chain_id()) {
+            (Some(sender), Some(origin)) => (sender, origin),
+            _ => return,
+        };
+        let timestamp = self.runtime.system_time().micros() as u64;
+
+        match message {
+            Message::GameInvite { game_id, game_type, timeouts, inviter_name, invitee, expires_at } => {
+                // Blackjack is played against the house, so there is nobody to invite
+                if game_type == GameType::Blackjack || sender == invitee {
+                    return;
+                }
+                // Turned away invites read as declined on the inviter's chain
+                if self.is_blocked_pair(sender, invitee).await
+                    || timestamp >= expires_at
+                    || self.state.games.get(&game_id).await.ok().flatten().is_some()
+                {
+                    self.runtime
+                        .prepare_message(Message::DeclineInvite { game_id })
+                        .with_authentication()
+                        .send_to(origin);
+                    return;
+                }
+
+                let invitee_name = match self.state.user_profiles.get(&invitee).await {
+                    Ok(Some(profile)) => profile.username,
+                    _ => String::new(),
+                };
+                let mut game_state = FullGameState::new(
+                    game_id,
+                    game_type,
+                    GameMode::VsFriend,
+                    vec![format!("{:?}", sender), format!("{:?}", invitee)],
+                    vec![inviter_name, invitee_name],
+                    timestamp,
+                    timeouts,
+                );
+                // Nothing happens until the invitee accepts
+                game_state.status = GameStatus::WaitingForOpponent;
+                game_state.invite_origin = Some(origin);
+                game_state.invite_expires_at = Some(expires_at);
+                self.insert_pvp_game(game_state, &[sender, invitee]).await;
+            }
+
+            Message::AcceptInvite { game_id } => {
+                let mut game_ids = self.state
+                    .accepted_invites
+                    .get(&origin)
+                    .await
+                    .unwrap_or(None)
+                    .unwrap_or_default();
+                if !game_ids.contains(&game_id) {
+                    game_ids.push(game_id.clone());
+                    let _ = self.state.accepted_invites.insert(&origin, game_ids);
+                }
+                self.update_sent_invite(&game_id, origin, |invite| invite.status = GameStatus::InProgress).await;
+            }
+
+            Message::DeclineInvite { game_id } => {
+                self.update_sent_invite(&game_id, origin, |invite| invite.status = GameStatus::Cancelled).await;
+            }
+
+            Message::RelayedOperation { operation } => {
+                // Only games hosted for the relaying chain's own invites take play from it
+                let game_id = match relayed_game_id(&operation) {
+                    Some(game_id) => game_id.to_string(),
+                    None => return,
+                };
+                match self.state.games.get(&game_id).await.ok().flatten() {
+                    Some(game) if game.invite_origin == Some(origin) => {}
+                    _ => return,
+                }
+                self.execute_operation(operation).await;
+            }
+
+            Message::InviteResult { game_id, winner, reason } => {
+                self.update_sent_invite(&game_id, origin, |invite| {
+                    invite.status = GameStatus::Completed;
+                    invite.winner = winner;
+                    invite.result_reason = Some(reason);
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn store(mut self) {
+        let _ = self.state.save().await;
+    }
+}
+
+impl GamePlatformContract {
+    /// Cancels an invite that was declined or let lapse, and tells the inviter's chain
+    async fn close_invite(&mut self, mut game: FullGameState, origin: ChainId, timestamp: u64) {
+        game.status = GameStatus::Cancelled;
+        game.updated_at = timestamp;
+        for player_str in game.players.clone() {
+            if let Some(player_owner) = self.parse_owner_from_debug_str(&player_str) {
+                self.unlist_game(player_owner, &game.game_id).await;
+            }
+        }
+
+        self.runtime
+            .prepare_message(Message::DeclineInvite { game_id: game.game_id.clone() })
+            .with_authentication()
+            .send_to(origin);
+        self.store_game(game).await;
+    }
+
+    /// Applies news from the host chain to one of this chain's invites; other chains can't touch it
+    async fn update_sent_invite(
+        &mut self,
+        game_id: &str,
+        host_chain: ChainId,
+        update: impl FnOnce(&mut SentInvite),
+    ) {
+        let game_id = game_id.to_string();
+        if let Ok(Some(mut invite)) = self.state.sent_invites.get(&game_id).await {
+            if invite.host_chain == host_chain {
+                update(&mut invite);
+                let _ = self.state.sent_invites.insert(&game_id, invite);
+            }
+        }
+    }
+
+    fn is_admin(&self, owner: &AccountOwner) -> bool {
+        self.state.admin.get().as_ref() == Some(owner)
+    }
+
+    fn is_arbiter(&self, owner: &AccountOwner) -> bool {
+        self.is_admin(owner) || self.state.arbiter.get().as_ref() == Some(owner)
+    }
+
+    /// Starts a game between two registered players and returns its id
+    async fn create_pvp_game(
+        &mut self,
+        game_type: GameType,
+        game_mode: GameMode,
+        players: [AccountOwner; 2],
+        timeouts: Timeouts,
+        timestamp: u64,
+    ) -> String {
+        let mut game_state = self.new_pvp_game(game_type, game_mode, &players, timeouts, timestamp).await;
+        self.settle_opening_deal(&mut game_state).await;
+        self.insert_pvp_game(game_state, &players).await
+    }
+
+    /// Builds the state for a game between two players without storing it
+    async fn new_pvp_game(
+        &mut self,
+        game_type: GameType,
+        game_mode: GameMode,
+        players: &[AccountOwner; 2],
+        timeouts: Timeouts,
+        timestamp: u64,
+    ) -> FullGameState {
+        let game_id = self.new_game_id(timestamp);
+
+        let mut names = Vec::with_capacity(2);
+        for owner in players {
+            let name = match self.state.user_profiles.get(owner).await {
+                Ok(Some(profile)) => profile.username,
+                _ => String::new(),
+            };
+            names.push(name);
+        }
+
+        FullGameState::new(
+            game_id,
+            game_type,
+            game_mode,
+            players.iter().map(|owner| format!("{:?}", owner)).collect(),
+            names,
+            timestamp,
+            timeouts,
+        )
+    }
+
+    /// Saves a game, filing a summary and dropping its card state once it has finished
+    async fn store_game(&mut self, mut game: FullGameState) {
+        // Cards at a seeded table stay hidden until every seed is in
+        if game.awaiting_seed_reveal() {
+            game.withhold_deal();
+        }
+        self.index_game(&game).await;
+        if game.is_finished() {
+            let _ = self.state.game_summaries.insert(&game.game_id, game.summary());
+            game.compact();
+        }
+        let game_id = game.game_id.clone();
+        let _ = self.state.games.insert(&game_id, game);
+    }
+
+    /// Lists the game under its type and current status, moving it out of its old bucket when the status changed
+    async fn index_game(&mut self, game: &FullGameState) {
+        let listed = self.state.game_index_slots.get(&game.game_id).await.ok().flatten();
+        if listed.is_some_and(|(status, _)| status == game.status) {
+            return;
+        }
+        if let Some((status, slot)) = listed {
+            self.unlist_from_index(game.game_type, status, slot).await;
+        }
+
+        let bucket = (game.game_type, game.status);
+        let len = self.state.game_index_len.get(&bucket).await.ok().flatten().unwrap_or(0);
+        let _ = self.state.game_index.insert(&(game.game_type, game.status, len), game.game_id.clone());
+        let _ = self.state.game_index_len.insert(&bucket, len + 1);
+        let _ = self.state.game_index_slots.insert(&game.game_id, (game.status, len));
+    }
+
+    /// Drops a slot from its bucket, moving the bucket's last game into the gap
+    async fn unlist_from_index(&mut self, game_type: GameType, status: GameStatus, slot: u64) {
+        let bucket = (game_type, status);
+        let last = match self.state.game_index_len.get(&bucket).await.ok().flatten() {
+            Some(len) if len > slot => len - 1,
+            _ => return,
+        };
+        if slot != last {
+            if let Ok(Some(moved_id)) = self.state.game_index.get(&(game_type, status, last)).await {
+                let _ = self.state.game_index.insert(&(game_type, status, slot), moved_id.clone());
+                let _ = self.state.game_index_slots.insert(&moved_id, (status, slot));
+            }
+        }
+        let _ = self.state.game_index.remove(&(game_type, status, last));
+        let _ = self.state.game_index_len.insert(&bucket, last);
+    }
+
+    /// Stores a new game, lists it for both players and counts it
+    async fn insert_pvp_game(&mut self, game_state: FullGameState, players: &[AccountOwner]) -> String {
+        let game_id = game_state.game_id.clone();
+        let game_type = game_state.game_type;
+        self.store_game(game_state).await;
+
+        for owner in players {
+            let mut player_games = self.state
+                .player_games
+                .get(owner)
+                .await
+                .unwrap_or(None)
+                .unwrap_or_default();
+            // A player holding several seats lists the game once
+            if !player_games.contains(&game_id) {
+                player_games.push(game_id.clone());
+            }
+            let _ = self.state.player_games.insert(owner, player_games);
+        }
+
+        self.count_new_game(game_type).await;
+        game_id
+    }
+
+    /// Drops a game from a player's game list
+    async fn unlist_game(&mut self, owner: AccountOwner, game_id: &str) {
+        let mut player_games = self.state
+            .player_games
+            .get(&owner)
+            .await
+            .unwrap_or(None)
+            .unwrap_or_default();
+        player_games.retain(|id| id != game_id);
+        let _ = self.state.player_games.insert(&owner, player_games);
+    }
+
+    /// Marks open lobbies past their expiry as expired and drops them from the active list
+    async fn prune_expired_lobbies(&mut self, timestamp: u64) {
+        let lobby_ids = self.state.active_lobby_ids.get().clone();
+        let mut still_active = Vec::with_capacity(lobby_ids.len());
+
+        for lobby_id in lobby_ids {
+            match self.state.lobbies.get(&lobby_id).await {
+                Ok(Some(mut lobby)) if lobby.status == LobbyStatus::Open && timestamp > lobby.expires_at => {
+                    lobby.status = LobbyStatus::Expired;
+                    self.refund_lobby(&lobby);
+                    let _ = self.state.lobbies.insert(&lobby_id, lobby);
+                }
+                Ok(Some(_)) => still_active.push(lobby_id),
+                _ => {}
+            }
+        }
+
+        self.state.active_lobby_ids.set(still_active);
+    }
+
+    /// Feeds a finished tournament game into its bracket and pairs the next round when it completes.
+    /// Knockout games can't end level, so a draw sends the higher seed (first seat) through.
+    async fn advance_tournament(&mut self, game: &FullGameState, winner: Option<Player>) {
+        let tournament_id = match &game.tournament_id {
+            Some(id) => id.clone(),
+            None => return,
+        };
+        let mut tournament = match self.state.tournaments.get(&tournament_id).await {
+            Ok(Some(t)) => t,
+            _ => return,
+        };
+
+        let winner_idx = match winner {
+            Some(Player::Two) => 1,
+            _ => 0,
+        };
+        let winner_str = match game.players.get(winner_idx) {
+            Some(p) => p.clone(),
+            None => return,
+        };
+
+        if tournament.record_result(&game.game_id, winner_str) {
+            let timestamp = self.runtime.system_time().micros();
+            self.start_tournament_round(&mut tournament, timestamp).await;
+        }
+
+        if tournament.status == TournamentStatus::Completed {
+            let mut tournament_ids = self.state.active_tournament_ids.get().clone();
+            tournament_ids.retain(|id| id != &tournament_id);
+            self.state.active_tournament_ids.set(tournament_ids);
+        }
+
+        let _ = self.state.tournaments.insert(&tournament_id, tournament);
+    }
+
+    /// Creates one game per pairing of the next tournament round
+    async fn start_tournament_round(&mut self, tournament: &mut Tournament, timestamp: u64) {
+        let timeouts = self.state.default_timeouts.get().clone();
+        let mut pairings = Vec::new();
+
+        for (first, second) in tournament.next_round_pairs() {
+            let players = match (self.parse_owner_from_debug_str(&first), self.parse_owner_from_debug_str(&second)) {
+                (Some(a), Some(b)) => [a, b],
+                _ => continue,
+            };
+            let mut game_state = self
+                .new_pvp_game(tournament.game_type, GameMode::VsFriend, &players, timeouts.clone(), timestamp)
+                .await;
+            game_state.tournament_id = Some(tournament.tournament_id.clone());
+            game_state.rules.allow_hints = tournament.allow_hints;
+            let game_id = self.insert_pvp_game(game_state, &players).await;
+
+            pairings.push(TournamentPairing {
+                game_id,
+                players: vec![first, second],
+                winner: None,
+            });
+        }
+
+        if !pairings.is_empty() {
+            tournament.add_round(pairings);
+        }
+    }
+
+    /// Ends a running game as a timeout loss for the side to move if their clock has run out. Around a bigger table
+    /// the idle seat folds instead, and the late action goes ahead if the hand plays on
+    async fn resolve_expired_clock(&mut self, game_id: &str, timestamp: u64) -> Option<GameOutcome> {
+        let mut game = self.state.games.get(game_id).await.ok()??;
+        let idle_seat = game.timed_out_seat(self.runtime.system_time())?;
 
-                    self.add_or_update_leaderboard_entry(&profile).await;
-                    let _ = self.state.user_profiles.insert(&owner, profile);
-                }
+        if game.players.len() > 2 {
+            let outcome = self.fold_idle_seat(&mut game, idle_seat, timestamp).await;
+            if let GameOutcome::Rejected(_) = outcome {
+                return None;
+            }
+            self.store_game(game).await;
+            return (outcome != GameOutcome::InProgress).then_some(outcome);
+        }
 
-                if won {
-                    GameOutcome::Winner(Player::One)
-                } else {
-                    GameOutcome::Winner(Player::Two)
-                }
+        let winner = Player::from_seat(idle_seat)?.other();
+
+        game.status = GameStatus::TimedOut;
+        game.winner = Some(winner);
+        game.updated_at = timestamp;
+
+        self.record_game_result(&mut game, winner, GameEndReason::Timeout).await;
+        self.store_game(game).await;
+
+        Some(GameOutcome::Winner(winner))
+    }
+
+    /// Ends a seeded table whose commit or reveal window has passed, with the seats still owing a seed losing to
+    /// those that played their part; `claimant` must be one of the latter
+    async fn forfeit_seed_holdouts(
+        &mut self,
+        game: &mut FullGameState,
+        claimant: usize,
+        timestamp: u64,
+    ) -> GameOutcome {
+        let holdouts = game.seed_holdouts();
+        if holdouts.contains(&claimant) {
+            return GameOutcome::rejected("Your own seed is still owed");
+        }
+        let window_start = game.clock.current_turn_start.micros();
+        if timestamp < window_start.saturating_add(SEED_REVEAL_WINDOW_MICROS) {
+            return GameOutcome::rejected("Seed window has not closed");
+        }
+
+        // Every seat of a holdout loses, however many that player holds
+        let owing: Vec<&String> = holdouts.iter().map(|&seat| &game.players[seat]).collect();
+        let winners: Vec<usize> = (0..game.players.len())
+            .filter(|&seat| !owing.contains(&&game.players[seat]))
+            .collect();
+        game.status = GameStatus::TimedOut;
+        game.updated_at = timestamp;
+
+        if game.players.len() > 2 {
+            self.record_seat_results(game, &winners, GameEndReason::Timeout).await;
+            return GameOutcome::SeatWinners(winners);
+        }
+        let winner = match winners.first().copied().and_then(Player::from_seat) {
+            Some(winner) => winner,
+            None => return GameOutcome::rejected("Not a player in this game"),
+        };
+        game.winner = Some(winner);
+        self.record_game_result(game, winner, GameEndReason::Timeout).await;
+        GameOutcome::Winner(winner)
+    }
+
+    /// Folds a poker seat that ran out of time, booking the hand if that ends it
+    async fn fold_idle_seat(&mut self, game: &mut FullGameState, seat: usize, timestamp: u64) -> GameOutcome {
+        let mut poker = match game.poker_game.take() {
+            Some(p) => p,
+            None => return GameOutcome::rejected("Not a poker game"),
+        };
+        let outcome = poker.make_action(seat, PokerAction::Fold, None, timestamp);
+        game.poker_game = Some(poker);
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(e) => return GameOutcome::Rejected(e),
+        };
+
+        game.clock.make_move(self.runtime.system_time(), seat);
+        game.updated_at = timestamp;
+        game.last_move_at = timestamp;
+
+        if let GameOutcome::SeatWinners(seats) = &outcome {
+            game.status = GameStatus::Completed;
+            self.record_seat_results(game, seats, GameEndReason::Timeout).await;
+        }
+        outcome
+    }
+
+    /// Scores a finished match game and starts the next one until the match is decided
+    async fn advance_match(&mut self, game: &FullGameState, winner: Option<Player>) {
+        let match_id = match &game.match_id {
+            Some(id) => id.clone(),
+            None => return,
+        };
+        let mut match_state = match self.state.matches.get(&match_id).await {
+            Ok(Some(m)) => m,
+            _ => return,
+        };
+        if match_state.game_ids.last() != Some(&game.game_id) {
+            return;
+        }
+
+        if !match_state.record_result(&game.game_id, winner) {
+            let timestamp = self.runtime.system_time().micros();
+            self.start_match_game(&mut match_state, timestamp).await;
+        }
+
+        let _ = self.state.matches.insert(&match_id, match_state);
+    }
+
+    /// Creates the match's next game, swapping seats from the previous one
+    async fn start_match_game(&mut self, match_state: &mut MatchState, timestamp: u64) {
+        let seating = match_state.next_seating();
+        let players = match (self.parse_owner_from_debug_str(&seating[0]), self.parse_owner_from_debug_str(&seating[1])) {
+            (Some(a), Some(b)) => [a, b],
+            _ => return,
+        };
+        let timeouts = self.state.default_timeouts.get().clone();
+        let mut game_state = self
+            .new_pvp_game(match_state.game_type, GameMode::VsFriend, &players, timeouts, timestamp)
+            .await;
+        game_state.match_id = Some(match_state.match_id.clone());
+        let game_id = self.insert_pvp_game(game_state, &players).await;
+        match_state.game_ids.push(game_id);
+    }
+
+    /// Completes a blackjack game whose opening deal was a natural
+    async fn settle_opening_deal(&mut self, game: &mut FullGameState) {
+        // A seeded table is settled once the reveals have dealt it for real
+        if game.awaiting_seed_reveal() {
+            return;
+        }
+        let outcome = match &game.blackjack_game {
+            Some(blackjack) => blackjack.round_outcome(),
+            None => return,
+        };
+
+        match outcome {
+            GameOutcome::Winner(winner) => {
+                game.status = GameStatus::Completed;
+                game.winner = Some(winner);
+                let reason = game.play_end_reason();
+                self.record_game_result(game, winner, reason).await;
+            }
+            GameOutcome::Draw => {
+                game.status = GameStatus::Completed;
+                let reason = game.play_end_reason();
+                self.record_draw_result(game, reason).await;
             }
+            GameOutcome::SeatWinners(_) | GameOutcome::InProgress | GameOutcome::Rejected(_) => {}
         }
     }
 
-    async fn execute_message(&mut self, _message: Self::Message) {
-        panic!("Messages not supported");
+    /// Allocates a game id from the chain's own counter, so games created in the same block never share one
+    fn new_game_id(&mut self, timestamp: u64) -> String {
+        let game_number = *self.state.next_game_id.get();
+        self.state.next_game_id.set(game_number + 1);
+        format!("{:x}_{:x}", timestamp, game_number)
     }
 
-    async fn store(mut self) {
-        let _ = self.state.save().await;
+    /// Returns false if the owner's last state-creating operation was too recent
+    async fn check_rate_limit(&self, owner: AccountOwner, timestamp: u64) -> bool {
+        match self.state.last_action_at.get(&owner).await {
+            Ok(Some(last)) => timestamp.saturating_sub(last) >= MIN_ACTION_INTERVAL_MICROS,
+            _ => true,
+        }
     }
-}
 
-impl GamePlatformContract {
-    async fn record_game_result(&mut self, game: &FullGameState, winner: Player) {
-        let winner_idx = winner.index();
-        let loser_idx = winner.other().index();
+    /// Records a state-creating operation once it has passed validation, so a rejected attempt costs nothing
+    fn stamp_action(&mut self, owner: AccountOwner, timestamp: u64) {
+        let _ = self.state.last_action_at.insert(&owner, timestamp);
+    }
+
+    async fn count_new_game(&mut self, game_type: GameType) {
+        let total = *self.state.total_games_played.get();
+        self.state.total_games_played.set(total + 1);
+
+        let by_type = self.state.games_by_type.get(&game_type).await.unwrap_or(None).unwrap_or(0);
+        let _ = self.state.games_by_type.insert(&game_type, by_type + 1);
+    }
+
+    async fn record_game_result(&mut self, game: &mut FullGameState, winner: Player, reason: GameEndReason) {
+        game.result_reason = Some(reason);
+        self.emit_game_completed(game, Some(winner), vec![winner.index()], reason);
+        self.settle_wager(game, &[winner.index()]);
+
+        if game.game_mode == GameMode::VsBot {
+            self.record_bot_chess_result(game, Some(winner)).await;
+            return;
+        }
+
+        self.record_poker_chips(game, Some(winner)).await;
+        self.record_seat_win(game, winner.index()).await;
+        self.record_seat_loss(game, winner.other().index()).await;
+
+        if game.game_type == GameType::Chess && game.game_mode == GameMode::VsFriend {
+            self.update_chess_elo(game, Some(winner)).await;
+        }
+
+        self.update_head_to_head(game, Some(winner)).await;
+        self.advance_tournament(game, Some(winner)).await;
+        self.advance_match(game, Some(winner)).await;
+
+        self.update_leaderboard().await;
+    }
+
+    /// Books a multi-way result: every seat in `winners` wins and every other seat loses
+    async fn record_seat_results(&mut self, game: &mut FullGameState, winners: &[usize], reason: GameEndReason) {
+        game.result_reason = Some(reason);
+        self.emit_game_completed(game, None, winners.to_vec(), reason);
+        self.settle_wager(game, winners);
 
         if game.game_mode == GameMode::VsBot {
             return;
         }
 
-        if let Some(winner_owner_str) = game.players.get(winner_idx) {
+        self.record_poker_chips(game, None).await;
+        for seat in 0..game.players.len() {
+            if winners.contains(&seat) {
+                self.record_seat_win(game, seat).await;
+            } else {
+                self.record_seat_loss(game, seat).await;
+            }
+        }
+
+        self.update_leaderboard().await;
+    }
+
+    /// Books a chess game the contract played against its bot for the player in seat 0, adding it to the bot record
+    /// a rating can be seeded from; `None` is a draw
+    async fn record_bot_chess_result(&mut self, game: &FullGameState, winner: Option<Player>) {
+        if game.game_type != GameType::Chess {
+            return;
+        }
+        let owner = match game.players.first().and_then(|p| self.parse_owner_from_debug_str(p)) {
+            Some(owner) => owner,
+            None => return,
+        };
+
+        match winner {
+            Some(Player::One) => self.record_seat_win(game, 0).await,
+            Some(Player::Two) => self.record_seat_loss(game, 0).await,
+            None => {
+                if let Ok(Some(mut stats)) = self.state.stats.get(&owner).await {
+                    stats.record_draw(game.game_type);
+                    let _ = self.state.stats.insert(&owner, stats);
+                }
+            }
+        }
+
+        if let Ok(Some(mut profile)) = self.state.user_profiles.get(&owner).await {
+            match winner {
+                Some(Player::One) => profile.bot_chess_wins += 1,
+                Some(Player::Two) => profile.bot_chess_losses += 1,
+                None => {
+                    profile.chess_draws += 1;
+                    profile.bot_chess_draws += 1;
+                    profile.total_games += 1;
+                    profile.current_streak = 0;
+                }
+            }
+            self.add_or_update_leaderboard_entry(&profile).await;
+            let _ = self.state.user_profiles.insert(&owner, profile);
+        }
+    }
+
+    /// Adds a win to the stats, profile and achievements of whoever holds `seat`
+    async fn record_seat_win(&mut self, game: &FullGameState, seat: usize) {
+        if let Some(winner_owner_str) = game.players.get(seat) {
             if let Some(winner_owner) = self.parse_owner_from_debug_str(winner_owner_str) {
                 if let Ok(Some(mut stats)) = self.state.stats.get(&winner_owner).await {
                     stats.record_win(game.game_type);
@@ -980,12 +3292,31 @@ impl GamePlatformContract {
                     if profile.current_streak > profile.best_streak as i32 {
                         profile.best_streak = profile.current_streak as u32;
                     }
+                    let best_streak = profile.best_streak;
                     let _ = self.state.user_profiles.insert(&winner_owner, profile);
+
+                    let mut earned = vec![AchievementKind::FirstWin];
+                    if best_streak >= STREAK_ACHIEVEMENT_LENGTH {
+                        earned.push(AchievementKind::TenGameStreak);
+                    }
+                    if game.chess_board.as_ref().is_some_and(is_quick_checkmate) {
+                        earned.push(AchievementKind::QuickCheckmate);
+                    }
+                    let royal_flush = game.poker_game
+                        .as_ref()
+                        .is_some_and(|p| p.stage == PokerStage::Showdown && p.has_royal_flush(seat));
+                    if royal_flush {
+                        earned.push(AchievementKind::RoyalFlush);
+                    }
+                    self.unlock_achievements(winner_owner, earned, Some(game.game_id.clone())).await;
                 }
             }
         }
+    }
 
-        if let Some(loser_owner_str) = game.players.get(loser_idx) {
+    /// Adds a loss to the stats and profile of whoever holds `seat`
+    async fn record_seat_loss(&mut self, game: &FullGameState, seat: usize) {
+        if let Some(loser_owner_str) = game.players.get(seat) {
             if let Some(loser_owner) = self.parse_owner_from_debug_str(loser_owner_str) {
                 if let Ok(Some(mut stats)) = self.state.stats.get(&loser_owner).await {
                     stats.record_loss(game.game_type);
@@ -1007,15 +3338,347 @@ impl GamePlatformContract {
                 }
             }
         }
+    }
 
-        self.update_leaderboard().await;
+    /// Stakes are native tokens between human players of a head-to-head game
+    fn wager_allowed(game_type: GameType, game_mode: GameMode, wager: Option<Amount>) -> bool {
+        match wager {
+            None => true,
+            Some(stake) => stake > Amount::ZERO && game_mode != GameMode::VsBot && game_type != GameType::Blackjack,
+        }
+    }
+
+    fn escrow_account(&mut self) -> Account {
+        Account {
+            chain_id: self.runtime.chain_id(),
+            owner: AccountOwner::from(self.runtime.application_id().forget_abi()),
+        }
+    }
+
+    /// Moves a stake from the player into the application's escrow; false if they can't cover it
+    fn escrow_stake(&mut self, owner: AccountOwner, stake: Amount) -> bool {
+        if self.runtime.owner_balance(owner) < stake {
+            return false;
+        }
+        let escrow = self.escrow_account();
+        self.runtime.transfer(owner, escrow, stake);
+        true
+    }
+
+    fn pay_from_escrow(&mut self, player_str: &str, amount: Amount) {
+        let owner = match self.parse_owner_from_debug_str(player_str) {
+            Some(o) => o,
+            None => return,
+        };
+        let escrow = self.escrow_account();
+        let recipient = Account { chain_id: escrow.chain_id, owner };
+        self.runtime.transfer(escrow.owner, recipient, amount);
     }
 
-    async fn record_draw_result(&mut self, game: &FullGameState) {
+    /// Adds each seat's net chips, the hand played and any pot won from a finished poker hand to their stats and profile
+    async fn record_poker_chips(&mut self, game: &FullGameState, winner: Option<Player>) {
+        let poker = match &game.poker_game {
+            Some(poker) => poker,
+            None => return,
+        };
+
+        let deltas = poker.chip_deltas(winner);
+        for (seat, (player_str, delta)) in game.players.iter().zip(deltas).enumerate() {
+            let owner = match self.parse_owner_from_debug_str(player_str) {
+                Some(o) => o,
+                None => continue,
+            };
+            if let Ok(Some(mut stats)) = self.state.stats.get(&owner).await {
+                stats.poker_chips_won += delta;
+                // A player sitting in several seats still played the hand once
+                if !game.players[..seat].contains(player_str) {
+                    stats.poker_hands_played += 1;
+                }
+                if poker.winners.contains(&seat) {
+                    stats.poker_biggest_pot_won = stats.poker_biggest_pot_won.max(poker.awarded_pot);
+                }
+                let _ = self.state.stats.insert(&owner, stats);
+            }
+            if let Ok(Some(mut profile)) = self.state.user_profiles.get(&owner).await {
+                profile.poker_chips_won += delta;
+                let _ = self.state.user_profiles.insert(&owner, profile);
+            }
+        }
+    }
+
+    /// Splits the whole pot between the winning seats, the first taking any remainder, or hands every stake back
+    /// on a draw
+    fn settle_wager(&mut self, game: &FullGameState, winners: &[usize]) {
+        let stake = match game.wager {
+            Some(w) => w,
+            None => return,
+        };
+        if winners.is_empty() {
+            self.refund_game(game);
+            return;
+        }
+        let funded = game.wager_funded.iter().filter(|&&f| f).count() as u128;
+        let pot = u128::from(stake.saturating_mul(funded));
+        let share = pot / winners.len() as u128;
+        for (i, seat) in winners.iter().enumerate() {
+            let amount = if i == 0 { share + pot % winners.len() as u128 } else { share };
+            if let Some(winner_str) = game.players.get(*seat) {
+                self.pay_from_escrow(winner_str, Amount::from_attos(amount));
+            }
+        }
+    }
+
+    /// Returns each funded seat's stake
+    fn refund_game(&mut self, game: &FullGameState) {
+        let stake = match game.wager {
+            Some(w) => w,
+            None => return,
+        };
+        for (player_str, &funded) in game.players.iter().zip(&game.wager_funded) {
+            if funded {
+                self.pay_from_escrow(player_str, stake);
+            }
+        }
+    }
+
+    /// Returns the stakes of everyone seated in a lobby that never started
+    fn refund_lobby(&mut self, lobby: &GameLobby) {
+        let stake = match lobby.wager {
+            Some(w) => w,
+            None => return,
+        };
+        for player_str in &lobby.players {
+            self.pay_from_escrow(player_str, stake);
+        }
+    }
+
+    /// Takes a player out of open lobbies, matchmaking queues and tournament registrations, refunding their lobby
+    /// stakes. A lobby they created is cancelled and everyone seated there refunded
+    async fn withdraw_player(&mut self, owner: AccountOwner) {
+        let owner_str = format!("{:?}", owner);
+
+        let mut lobby_ids = self.state.active_lobby_ids.get().clone();
+        let mut cancelled = Vec::new();
+        for lobby_id in &lobby_ids {
+            let mut lobby = match self.state.lobbies.get(lobby_id).await.ok().flatten() {
+                Some(l) if l.status == LobbyStatus::Open && l.players.contains(&owner_str) => l,
+                _ => continue,
+            };
+            if lobby.players.first() == Some(&owner_str) {
+                lobby.status = LobbyStatus::Cancelled;
+                self.refund_lobby(&lobby);
+                cancelled.push(lobby_id.clone());
+            } else {
+                let (players, player_names): (Vec<String>, Vec<String>) = lobby
+                    .players
+                    .iter()
+                    .zip(&lobby.player_names)
+                    .filter(|(player, _)| **player != owner_str)
+                    .map(|(player, name)| (player.clone(), name.clone()))
+                    .unzip();
+                if let Some(stake) = lobby.wager {
+                    for _ in players.len()..lobby.players.len() {
+                        self.pay_from_escrow(&owner_str, stake);
+                    }
+                }
+                lobby.players = players;
+                lobby.player_names = player_names;
+            }
+            let _ = self.state.lobbies.insert(lobby_id, lobby);
+        }
+        lobby_ids.retain(|id| !cancelled.contains(id));
+        self.state.active_lobby_ids.set(lobby_ids);
+
+        for game_type in [GameType::Chess, GameType::Poker, GameType::Blackjack] {
+            let mut queue = self.state
+                .matchmaking_queue
+                .get(&game_type)
+                .await
+                .unwrap_or(None)
+                .unwrap_or_default();
+            if queue.contains(&owner) {
+                queue.retain(|queued| queued != &owner);
+                let _ = self.state.matchmaking_queue.insert(&game_type, queue);
+            }
+        }
+
+        for tournament_id in self.state.active_tournament_ids.get().clone() {
+            if let Ok(Some(mut tournament)) = self.state.tournaments.get(&tournament_id).await {
+                if tournament.status == TournamentStatus::Registering && tournament.participants.contains(&owner_str) {
+                    tournament.participants.retain(|participant| participant != &owner_str);
+                    let _ = self.state.tournaments.insert(&tournament_id, tournament);
+                }
+            }
+        }
+    }
+
+    /// True while `owner` sits in an open lobby, a matchmaking queue, or an unfinished tournament or match
+    async fn has_open_commitments(&self, owner: AccountOwner) -> bool {
+        let owner_str = format!("{:?}", owner);
+
+        for lobby_id in self.state.active_lobby_ids.get() {
+            if let Ok(Some(lobby)) = self.state.lobbies.get(lobby_id).await {
+                if lobby.status == LobbyStatus::Open && lobby.players.contains(&owner_str) {
+                    return true;
+                }
+            }
+        }
+
+        for game_type in [GameType::Chess, GameType::Poker, GameType::Blackjack] {
+            if let Ok(Some(queue)) = self.state.matchmaking_queue.get(&game_type).await {
+                if queue.contains(&owner) {
+                    return true;
+                }
+            }
+        }
+
+        for tournament_id in self.state.active_tournament_ids.get() {
+            if let Ok(Some(tournament)) = self.state.tournaments.get(tournament_id).await {
+                if tournament.status != TournamentStatus::Completed && tournament.participants.contains(&owner_str) {
+                    return true;
+                }
+            }
+        }
+
+        for match_id in self.state.matches.indices().await.unwrap_or_default() {
+            if let Ok(Some(series)) = self.state.matches.get(&match_id).await {
+                if series.status == MatchStatus::InProgress && series.players.contains(&owner_str) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Moves achievements over from a merged account, keeping the earlier unlock of any both accounts hold
+    async fn merge_achievements(&mut self, keep_owner: AccountOwner, merge_owner: AccountOwner) {
+        let merged = self.state.achievements.get(&merge_owner).await.unwrap_or(None).unwrap_or_default();
+        let _ = self.state.achievements.remove(&merge_owner);
+        if merged.is_empty() {
+            return;
+        }
+
+        let mut kept = self.state.achievements.get(&keep_owner).await.unwrap_or(None).unwrap_or_default();
+        for achievement in merged {
+            match kept.iter_mut().find(|a| a.kind == achievement.kind) {
+                Some(held) if held.unlocked_at > achievement.unlocked_at => *held = achievement,
+                Some(_) => {}
+                None => kept.push(achievement),
+            }
+        }
+        let _ = self.state.achievements.insert(&keep_owner, kept);
+    }
+
+    /// Folds a merged account's block list into the kept one and points other players' blocks of it at the kept account
+    async fn merge_blocks(&mut self, keep_owner: AccountOwner, merge_owner: AccountOwner) {
+        let mut kept = self.blocked_players(keep_owner).await;
+        for blocked in self.blocked_players(merge_owner).await {
+            if blocked != keep_owner && !kept.contains(&blocked) && kept.len() < MAX_BLOCKED_PLAYERS {
+                kept.push(blocked);
+            }
+        }
+        kept.retain(|blocked| *blocked != merge_owner);
+        let _ = self.state.blocks.remove(&merge_owner);
+
+        for blocker in self.state.blocks.indices().await.unwrap_or_default() {
+            if blocker == keep_owner {
+                continue;
+            }
+            let mut blocks = self.blocked_players(blocker).await;
+            if blocks.contains(&merge_owner) {
+                blocks.retain(|blocked| *blocked != merge_owner);
+                if !blocks.contains(&keep_owner) {
+                    blocks.push(keep_owner);
+                }
+                let _ = self.state.blocks.insert(&blocker, blocks);
+            }
+        }
+
+        if kept.is_empty() {
+            let _ = self.state.blocks.remove(&keep_owner);
+        } else {
+            let _ = self.state.blocks.insert(&keep_owner, kept);
+        }
+    }
+
+    /// Adds a merged account's records against each opponent to the kept account's; games between the two are dropped
+    async fn merge_head_to_head(&mut self, keep_owner: AccountOwner, merge_owner: AccountOwner) {
+        for pair in self.state.head_to_head.indices().await.unwrap_or_default() {
+            if pair.0 != merge_owner && pair.1 != merge_owner {
+                continue;
+            }
+            let record = self.state.head_to_head.get(&pair).await.unwrap_or(None).unwrap_or_default();
+            let _ = self.state.head_to_head.remove(&pair);
+
+            // Turn the record round to the merged account's side
+            let (opponent, (wins, losses, draws)) = if pair.0 == merge_owner {
+                (pair.1, record)
+            } else {
+                (pair.0, (record.1, record.0, record.2))
+            };
+            if opponent == keep_owner {
+                continue;
+            }
+
+            let (key, swapped) = head_to_head_key(keep_owner, opponent);
+            let mut kept = self.state.head_to_head.get(&key).await.unwrap_or(None).unwrap_or_default();
+            let (first_wins, first_losses) = if swapped { (losses, wins) } else { (wins, losses) };
+            kept.0 += first_wins;
+            kept.1 += first_losses;
+            kept.2 += draws;
+            let _ = self.state.head_to_head.insert(&key, kept);
+        }
+    }
+
+    /// Adds any of `earned` the player doesn't have yet
+    async fn unlock_achievements(&mut self, owner: AccountOwner, earned: Vec<AchievementKind>, game_id: Option<String>) {
+        let timestamp = self.runtime.system_time().micros();
+        let mut achievements = self.state.achievements.get(&owner).await.unwrap_or(None).unwrap_or_default();
+        let mut changed = false;
+        for kind in earned {
+            changed |= unlock_achievement(&mut achievements, kind, game_id.clone(), timestamp);
+        }
+        if changed {
+            let _ = self.state.achievements.insert(&owner, achievements);
+        }
+    }
+
+    fn emit_game_completed(
+        &mut self,
+        game: &FullGameState,
+        winner: Option<Player>,
+        winning_seats: Vec<usize>,
+        reason: GameEndReason,
+    ) {
+        let event = GameCompletedEvent {
+            game_id: game.game_id.clone(),
+            game_type: game.game_type,
+            winner,
+            winning_seats,
+            reason,
+        };
+        self.runtime.emit(GAME_COMPLETED_STREAM.into(), &event);
+
+        // The inviter's chain only learns how a hosted game went from here
+        if let Some(origin) = game.invite_origin {
+            let message = Message::InviteResult { game_id: game.game_id.clone(), winner, reason };
+            self.runtime.prepare_message(message).with_authentication().send_to(origin);
+        }
+    }
+
+    async fn record_draw_result(&mut self, game: &mut FullGameState, reason: GameEndReason) {
+        game.result_reason = Some(reason);
+        self.emit_game_completed(game, None, vec![], reason);
+        self.settle_wager(game, &[]);
+
         if game.game_mode == GameMode::VsBot {
+            self.record_bot_chess_result(game, None).await;
             return;
         }
 
+        self.record_poker_chips(game, None).await;
+
         for player_str in &game.players {
             if let Some(owner) = self.parse_owner_from_debug_str(player_str) {
                 if let Ok(Some(mut stats)) = self.state.stats.get(&owner).await {
@@ -1035,9 +3698,85 @@ impl GamePlatformContract {
             }
         }
 
+        if game.game_type == GameType::Chess && game.game_mode == GameMode::VsFriend {
+            self.update_chess_elo(game, None).await;
+        }
+
+        self.update_head_to_head(game, None).await;
+        self.advance_tournament(game, None).await;
+        self.advance_match(game, None).await;
+
         self.update_leaderboard().await;
     }
 
+    /// Applies an Elo update to both players of a rated chess game; `None` is a draw
+    async fn update_chess_elo(&mut self, game: &FullGameState, winner: Option<Player>) {
+        let owners: Vec<AccountOwner> = game.players
+            .iter()
+            .filter_map(|p| self.parse_owner_from_debug_str(p))
+            .collect();
+        if owners.len() != 2 {
+            return;
+        }
+
+        let mut ratings = [0u32; 2];
+        for (i, owner) in owners.iter().enumerate() {
+            ratings[i] = match self.state.stats.get(owner).await {
+                Ok(Some(stats)) => stats.chess_elo,
+                _ => return,
+            };
+        }
+
+        for (i, owner) in owners.iter().enumerate() {
+            let score = match winner {
+                Some(w) if w.index() == i => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            let delta = elo_delta(ratings[i], ratings[1 - i], score);
+
+            if let Ok(Some(mut stats)) = self.state.stats.get(owner).await {
+                stats.update_elo(delta, self.state.game_defaults.get());
+                let new_elo = stats.chess_elo;
+                let _ = self.state.stats.insert(owner, stats);
+
+                if let Ok(Some(mut profile)) = self.state.user_profiles.get(owner).await {
+                    profile.chess_elo = new_elo;
+                    let _ = self.state.user_profiles.insert(owner, profile);
+                }
+            }
+        }
+    }
+
+    /// Adds a two-player result to the pair's head-to-head record; `None` is a draw
+    async fn update_head_to_head(&mut self, game: &FullGameState, winner: Option<Player>) {
+        let owners: Vec<AccountOwner> = game.players
+            .iter()
+            .filter_map(|p| self.parse_owner_from_debug_str(p))
+            .collect();
+        if owners.len() != 2 || owners[0] == owners[1] {
+            return;
+        }
+
+        let (key, swapped) = head_to_head_key(owners[0], owners[1]);
+        let mut record = self.state.head_to_head.get(&key).await.unwrap_or(None).unwrap_or_default();
+        // Player One sits first in the key unless the pair was swapped
+        let first_player_won = winner.map(|w| (w == Player::One) != swapped);
+        record_head_to_head(&mut record, first_player_won);
+        let _ = self.state.head_to_head.insert(&key, record);
+    }
+
+    async fn blocked_players(&self, owner: AccountOwner) -> Vec<AccountOwner> {
+        self.state.blocks.get(&owner).await.ok().flatten().unwrap_or_default()
+    }
+
+    /// True if either account has blocked the other
+    async fn is_blocked_pair(&self, a: AccountOwner, b: AccountOwner) -> bool {
+        let a_blocks = self.blocked_players(a).await;
+        let b_blocks = self.blocked_players(b).await;
+        blocks_either_way(&a, &a_blocks, &b, &b_blocks)
+    }
+
     fn parse_owner_from_debug_str(&self, s: &str) -> Option<AccountOwner> {
         if s == "BOT" {
             return None;
@@ -1095,22 +3834,10 @@ impl GamePlatformContract {
 
     async fn update_leaderboard(&mut self) {
         let mut current = self.state.leaderboard.get().clone();
-
-        current.sort_by(|a, b| {
-            let a_rate = if a.total_games > 0 { a.wins as f64 / a.total_games as f64 } else { 0.0 };
-            let b_rate = if b.total_games > 0 { b.wins as f64 / b.total_games as f64 } else { 0.0 };
-            match b_rate.partial_cmp(&a_rate) {
-                Some(std::cmp::Ordering::Equal) => b.total_games.cmp(&a.total_games),
-                Some(ord) => ord,
-                None => std::cmp::Ordering::Equal,
-            }
-        });
-
-        for (i, entry) in current.iter_mut().enumerate() {
-            entry.rank = (i + 1) as u32;
-        }
-
+        let timestamp = self.runtime.system_time().micros();
+        let period = rank_leaderboard_for_period(&mut current, *self.state.leaderboard_period.get(), timestamp);
         self.state.leaderboard.set(current);
+        self.state.leaderboard_period.set(period);
     }
 
     async fn add_or_update_leaderboard_entry(&mut self, profile: &UserProfile) {
@@ -1127,8 +3854,9 @@ impl GamePlatformContract {
 
         let existing_idx = entries.iter().position(|e| e.eth_address == profile.eth_address);
 
+        // Keep the current rank and the period baseline so the next re-rank can report movement
         let entry = LeaderboardEntry {
-            rank: 0,
+            rank: existing_idx.map(|idx| entries[idx].rank).unwrap_or(0),
             username: profile.username.clone(),
             eth_address: profile.eth_address.clone(),
             wins: total_wins,
@@ -1136,6 +3864,8 @@ impl GamePlatformContract {
             win_rate,
             elo: profile.chess_elo,
             total_games,
+            previous_rank: existing_idx.map(|idx| entries[idx].previous_rank).unwrap_or(0),
+            rank_delta: 0,
         };
 
         if let Some(idx) = existing_idx {
@@ -1149,6 +3879,44 @@ impl GamePlatformContract {
     }
 }
 
+/// The game played by operations an inviter can send to the chain hosting their game
+fn relayed_game_id(operation: &Operation) -> Option<&str> {
+    match operation {
+        Operation::ChessMove { game_id, .. }
+        | Operation::PokerAction { game_id, .. }
+        | Operation::ResignGame { game_id, .. }
+        | Operation::OfferDraw { game_id }
+        | Operation::AcceptDraw { game_id }
+        | Operation::DeclineDraw { game_id }
+        | Operation::ClaimFiftyMoveRule { game_id }
+        | Operation::RequestTakeback { game_id }
+        | Operation::AcceptTakeback { game_id }
+        | Operation::ClaimTimeout { game_id }
+        | Operation::AbortGame { game_id }
+        | Operation::SendChatMessage { game_id, .. }
+        | Operation::CommitSeed { game_id, .. }
+        | Operation::RevealSeed { game_id, .. } => Some(game_id),
+        _ => None,
+    }
+}
+
+/// The game acted on by operations that need the mover's clock still running
+fn timed_game_id(operation: &Operation) -> Option<&str> {
+    match operation {
+        Operation::ChessMove { game_id, .. }
+        | Operation::PokerAction { game_id, .. }
+        | Operation::BlackjackAction { game_id, .. }
+        | Operation::ResignGame { game_id, .. }
+        | Operation::OfferDraw { game_id }
+        | Operation::AcceptDraw { game_id }
+        | Operation::DeclineDraw { game_id }
+        | Operation::ClaimFiftyMoveRule { game_id }
+        | Operation::RequestTakeback { game_id }
+        | Operation::AcceptTakeback { game_id } => Some(game_id),
+        _ => None,
+    }
+}
+
 // Helper function to parse ETH address as owner
 fn parse_account_owner_from_eth(eth_addr: &str) -> Option<AccountOwner> {
     let cleaned = if eth_addr.starts_with("0x") {
@@ -1,12 +1,13 @@
 // Multi-Game Platform ABI - Chess, Poker, Blackjack
 // Fully decentralized gaming on Linera blockchain
 
-use async_graphql::{Enum, InputObject, Request, Response, SimpleObject};
+use async_graphql::{ComplexObject, Enum, InputObject, Request, Response, SimpleObject};
 use linera_sdk::{
     graphql::GraphQLMutationRoot,
-    linera_base_types::{AccountOwner, ContractAbi, ServiceAbi, TimeDelta, Timestamp},
+    linera_base_types::{AccountOwner, Amount, ChainId, ContractAbi, ServiceAbi, TimeDelta, Timestamp},
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 pub struct GamePlatformAbi;
 
@@ -61,20 +62,167 @@ pub struct UserProfile {
     pub total_games: u32,
     pub current_streak: i32,
     pub best_streak: u32,
+    // Chess results against the bot, also counted in the chess totals above; only games the contract played or
+    // the player recorded themselves
+    pub bot_chess_wins: u32,
+    pub bot_chess_losses: u32,
+    pub bot_chess_draws: u32,
+    // Set once the bot record has been turned into a provisional rating
+    pub elo_seeded: bool,
+    // Open to a game without a lobby; `looking_for` narrows it to one game type
+    pub looking_for_game: bool,
+    pub looking_for: Option<GameType>,
+    pub status_message: Option<String>,
+    // Usernames of accounts merged into this one, which still resolve to it
+    pub merged_usernames: Vec<String>,
 }
 
 impl UserProfile {
-    pub fn new(username: String, eth_address: String, avatar_url: String, timestamp: u64) -> Self {
+    pub fn new(username: String, eth_address: String, avatar_url: String, timestamp: u64, starting_elo: u32) -> Self {
         UserProfile {
             username,
             eth_address,
             avatar_url,
             created_at: timestamp,
             last_active: timestamp,
-            chess_elo: 1200, // Starting ELO
+            chess_elo: starting_elo,
             ..Default::default()
         }
     }
+
+    /// Folds another profile's record into this one, summing results and keeping the best streak and ELO
+    pub fn merge_from(&mut self, other: &UserProfile) {
+        self.created_at = self.created_at.min(other.created_at);
+        self.last_active = self.last_active.max(other.last_active);
+        self.chess_wins += other.chess_wins;
+        self.chess_losses += other.chess_losses;
+        self.chess_draws += other.chess_draws;
+        self.chess_elo = self.chess_elo.max(other.chess_elo);
+        self.poker_wins += other.poker_wins;
+        self.poker_losses += other.poker_losses;
+        self.poker_chips_won += other.poker_chips_won;
+        self.blackjack_wins += other.blackjack_wins;
+        self.blackjack_losses += other.blackjack_losses;
+        self.blackjack_pushes += other.blackjack_pushes;
+        self.total_games += other.total_games;
+        self.best_streak = self.best_streak.max(other.best_streak);
+        self.bot_chess_wins += other.bot_chess_wins;
+        self.bot_chess_losses += other.bot_chess_losses;
+        self.bot_chess_draws += other.bot_chess_draws;
+        self.elo_seeded |= other.elo_seeded;
+        self.merged_usernames.push(other.username.clone());
+        self.merged_usernames.extend(other.merged_usernames.iter().cloned());
+    }
+
+    /// True if `username` is this profile's name or one merged into it, ignoring case
+    pub fn answers_to(&self, username: &str) -> bool {
+        let username = username.to_lowercase();
+        self.username.to_lowercase() == username
+            || self.merged_usernames.iter().any(|merged| merged.to_lowercase() == username)
+    }
+
+    /// True once the player has finished a chess game against another player
+    pub fn has_rated_chess_games(&self) -> bool {
+        self.chess_wins + self.chess_losses + self.chess_draws > self.bot_chess_games()
+    }
+
+    /// Chess games played against the bot, whatever their result
+    pub fn bot_chess_games(&self) -> u32 {
+        self.bot_chess_wins + self.bot_chess_losses + self.bot_chess_draws
+    }
+
+    /// True if the player has said they are looking for a game of this type
+    pub fn is_available_for(&self, game_type: GameType) -> bool {
+        self.looking_for_game && self.looking_for.unwrap_or(game_type) == game_type
+    }
+
+    /// Rating for a game type; only chess is rated
+    pub fn rating(&self, game_type: GameType) -> Option<u32> {
+        match game_type {
+            GameType::Chess => Some(self.chess_elo),
+            GameType::Poker | GameType::Blackjack => None,
+        }
+    }
+}
+
+/// Elo-expected score of `a` against `b`, or `None` if the game type is unrated
+pub fn win_probability(a: &UserProfile, b: &UserProfile, game_type: GameType) -> Option<f64> {
+    Some(expected_score(a.rating(game_type)?, b.rating(game_type)?))
+}
+
+pub const STATUS_MESSAGE_MAX_LEN: usize = 140;
+
+// A player stops being listed as looking for a game this long after saying so
+pub const AVAILABILITY_TTL_MICROS: u64 = 1_800_000_000;
+
+/// True once availability announced at `since` has lapsed by `now`
+pub fn availability_expired(since: u64, now: u64) -> bool {
+    now.saturating_sub(since) > AVAILABILITY_TTL_MICROS
+}
+
+pub const USERNAME_MIN_LEN: usize = 3;
+pub const USERNAME_MAX_LEN: usize = 20;
+
+/// Trims a username and checks its length and characters, returning the trimmed name
+pub fn validate_username(username: &str) -> Result<String, String> {
+    let trimmed = username.trim();
+    let len = trimmed.chars().count();
+    if !(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&len) {
+        return Err(format!(
+            "Username must be {}-{} characters",
+            USERNAME_MIN_LEN, USERNAME_MAX_LEN
+        ));
+    }
+    if !trimmed.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("Username may only contain letters, digits, '_' and '-'".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Checks a registration against the existing mappings: an ETH address belongs to one owner, an owner keeps one
+/// ETH address, and a username belongs to one owner. `existing` is the owner's current profile, if any.
+/// Returns the lowercased username the owner is leaving behind, whose mapping must be removed
+pub fn check_registration(
+    owner: &AccountOwner,
+    eth_address: &str,
+    username: &str,
+    eth_owner: Option<&AccountOwner>,
+    username_owner: Option<&AccountOwner>,
+    existing: Option<&UserProfile>,
+) -> Result<Option<String>, String> {
+    if eth_owner.is_some_and(|eth_owner| eth_owner != owner) {
+        return Err("ETH address registered to another account".to_string());
+    }
+    if existing.is_some_and(|profile| !profile.eth_address.eq_ignore_ascii_case(eth_address)) {
+        return Err("Account is registered with a different ETH address".to_string());
+    }
+    if username_owner.is_some_and(|username_owner| username_owner != owner) {
+        return Err("Username already taken".to_string());
+    }
+    Ok(existing
+        .map(|profile| profile.username.to_lowercase())
+        .filter(|old| *old != username.to_lowercase()))
+}
+
+pub const AVATAR_URL_MAX_LEN: usize = 512;
+
+/// Trims an avatar URL and checks it is a plain http(s) link; empty means no avatar
+pub fn validate_avatar_url(url: &str) -> Result<String, String> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    if trimmed.len() > AVATAR_URL_MAX_LEN {
+        return Err(format!("Avatar URL must be at most {} characters", AVATAR_URL_MAX_LEN));
+    }
+    let lower = trimmed.to_ascii_lowercase();
+    if !lower.starts_with("https://") && !lower.starts_with("http://") {
+        return Err("Avatar URL must start with http:// or https://".to_string());
+    }
+    if trimmed.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("Avatar URL may not contain spaces".to_string());
+    }
+    Ok(trimmed.to_string())
 }
 
 // ============ LOBBY SYSTEM ============
@@ -88,12 +236,110 @@ pub struct GameLobby {
     pub game_mode: GameMode,
     pub is_public: bool,
     pub password_hash: Option<String>,
+    pub password_salt: String,
     pub status: LobbyStatus,
     pub time_control: u64,
     pub created_at: u64,
     pub expires_at: u64,
     pub players: Vec<String>,
+    pub player_names: Vec<String>,
+    pub max_players: u32,
     pub game_id: Option<String>,
+    // Native-token stake each seated player has escrowed
+    pub wager: Option<Amount>,
+    pub creator_color: ColorChoice,
+    pub poker_stakes: PokerStakes,
+    // Carried into the game's rules when it starts
+    pub allow_hints: bool,
+    // Accounts the creator invited; they may join without the password
+    pub invited: Vec<AccountOwner>,
+}
+
+/// Which side the lobby creator takes; in card games White and Black are the first and second seats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum ColorChoice {
+    #[default]
+    White,
+    Black,
+    // Drawn from the players' revealed seeds once the game starts
+    Random,
+}
+
+impl GameLobby {
+    /// Seat order for the lobby's game: join order, with the creator moved to second if they play Black.
+    /// A random choice starts in join order and is drawn once the game's seeds are revealed
+    pub fn game_seating(&self) -> (Vec<String>, Vec<String>) {
+        let mut players = self.players.clone();
+        let mut names = self.player_names.clone();
+        if self.creator_color == ColorChoice::Black && players.len() >= 2 {
+            players.swap(0, 1);
+            names.swap(0, 1);
+        }
+        (players, names)
+    }
+
+    /// True if `joiner` and anyone already seated have blocked one another.
+    /// `member_blocks` lists the accounts each seated player has blocked, in seat order
+    pub fn block_conflict(&self, joiner: &AccountOwner, joiner_blocks: &[AccountOwner], member_blocks: &[Vec<AccountOwner>]) -> bool {
+        let joiner_blocked_members = self
+            .players
+            .iter()
+            .any(|member| joiner_blocks.iter().any(|blocked| format!("{:?}", blocked) == *member));
+        joiner_blocked_members || member_blocks.iter().any(|blocks| blocks.contains(joiner))
+    }
+
+    /// True if the lobby is open and either public or has invited `owner`
+    pub fn is_joinable_by(&self, owner: &AccountOwner, now: u64) -> bool {
+        self.status == LobbyStatus::Open
+            && self.expires_at >= now
+            && (self.is_public || self.invited.contains(owner))
+    }
+}
+
+/// Heads-up seating coin flip from a game seed: true when the creator takes the second seat
+pub fn seat_coin_flip(seed: u64) -> bool {
+    Sha256::digest(seed.to_le_bytes())[0] & 1 == 1
+}
+
+/// A seating coin flip kept on the game so anyone can recompute it from the seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct SeatingDraw {
+    pub seed: u64,
+    pub creator_second: bool,
+}
+
+impl SeatingDraw {
+    pub fn from_seed(seed: u64) -> Self {
+        SeatingDraw { seed, creator_second: seat_coin_flip(seed) }
+    }
+
+    /// True if the recorded result matches the flip its seed produces
+    pub fn verify(&self) -> bool {
+        seat_coin_flip(self.seed) == self.creator_second
+    }
+}
+
+// Most accounts one user can block
+pub const MAX_BLOCKED_PLAYERS: usize = 200;
+
+/// True if either player has blocked the other
+pub fn blocks_either_way(a: &AccountOwner, a_blocks: &[AccountOwner], b: &AccountOwner, b_blocks: &[AccountOwner]) -> bool {
+    a_blocks.contains(b) || b_blocks.contains(a)
+}
+
+// Open lobbies expire this long after creation
+pub const LOBBY_TTL_MICROS: u64 = 900_000_000;
+
+pub const MIN_LOBBY_PLAYERS: u32 = 2;
+pub const MAX_LOBBY_PLAYERS: u32 = 6;
+pub const MAX_LOBBY_INVITES: usize = 20;
+
+/// Seat counts a lobby can be opened with: chess and blackjack are heads-up, poker fills up to a full table
+pub fn lobby_seats_supported(game_type: GameType, players: u32) -> bool {
+    match game_type {
+        GameType::Chess | GameType::Blackjack => players == MIN_LOBBY_PLAYERS,
+        GameType::Poker => (MIN_LOBBY_PLAYERS..=MAX_LOBBY_PLAYERS).contains(&players),
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
@@ -105,6 +351,14 @@ pub enum LobbyStatus {
     Expired,
 }
 
+/// Hex-encoded SHA-256 of a lobby password with the lobby's salt
+pub fn hash_lobby_password(password: &str, salt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(password.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
 // ============ CHESS ============
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
@@ -159,6 +413,19 @@ impl Default for CastlingRights {
     }
 }
 
+impl CastlingRights {
+    /// Drops the right tied to the rook starting on `square`, if it is a corner
+    fn clear_corner(&mut self, square: u8) {
+        match square {
+            0 => self.white_queenside = false,
+            7 => self.white_kingside = false,
+            56 => self.black_queenside = false,
+            63 => self.black_kingside = false,
+            _ => {}
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
 pub struct ChessMoveRecord {
     pub from_square: u8,
@@ -170,6 +437,50 @@ pub struct ChessMoveRecord {
     pub is_en_passant: bool,
     pub notation: String,
     pub timestamp: u64,
+    // Signer who submitted the move; None for bot replies
+    pub by_owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ChessCapturedValue {
+    pub white: u32,
+    pub black: u32,
+}
+
+// Pieces each side has lost, and White's material on the board minus Black's
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ChessMaterial {
+    pub captured_white: Vec<PieceType>,
+    pub captured_black: Vec<PieceType>,
+    pub balance: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum DrawCondition {
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    InsufficientMaterial,
+}
+
+// Halfmoves without a capture or pawn move before the 50- and 75-move rules apply
+pub const FIFTY_MOVE_HALFMOVES: u16 = 100;
+pub const SEVENTY_FIVE_MOVE_HALFMOVES: u16 = 150;
+// Occurrences of one position that end the game as a draw without a claim
+pub const FIVEFOLD_REPETITIONS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct DrawCountdown {
+    pub fifty_move: u16,
+    pub seventy_five_move: u16,
+}
+
+/// Draw conditions that currently apply to a game; always empty for card games
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct GameDrawStatus {
+    pub conditions: Vec<DrawCondition>,
+    // How often the current position has occurred, counting now
+    pub repetition_count: u32,
+    pub halfmove_clock: u16,
 }
 
 impl ChessBoard {
@@ -234,24 +545,48 @@ impl ChessBoard {
     }
 
     pub fn make_move(&mut self, from: u8, to: u8, promotion: Option<PieceType>, timestamp: u64) -> Result<GameOutcome, String> {
+        self.play_move(from, to, promotion, timestamp)?;
+
+        if self.legal_moves(self.active_player).is_empty() {
+            Ok(self.no_moves_outcome())
+        } else if self.is_insufficient_material() || self.halfmove_clock >= SEVENTY_FIVE_MOVE_HALFMOVES {
+            // The fifty-move rule has to be claimed; only the seventy-five-move rule ends the game by itself
+            Ok(GameOutcome::Draw)
+        } else if self.is_fivefold_repetition() {
+            Ok(GameOutcome::Draw)
+        } else {
+            Ok(GameOutcome::InProgress)
+        }
+    }
+
+    // Validates and applies a move without judging the position it leaves
+    fn play_move(&mut self, from: u8, to: u8, promotion: Option<PieceType>, timestamp: u64) -> Result<(), String> {
         if from >= 64 || to >= 64 {
             return Err("Invalid square".to_string());
         }
 
+        if self.move_history.last().is_some_and(|last| timestamp < last.timestamp) {
+            return Err("Move timestamp earlier than previous move".to_string());
+        }
+
         let piece = self.squares[from as usize].ok_or("No piece at source")?;
 
         if piece.owner != self.active_player {
             return Err("Not your piece".to_string());
         }
 
-        // Validate move (basic validation - full validation in frontend)
+        // A pawn reaching the last rank becomes a queen unless told otherwise
+        let legal = self.legal_moves(self.active_player).into_iter().any(|(f, t, promotes_to)| {
+            f == from && t == to && (promotes_to.is_none() || promotes_to == Some(promotion.unwrap_or(PieceType::Queen)))
+        });
+        if !legal {
+            return Err("Illegal move".to_string());
+        }
+
         let captured = self.squares[to as usize];
 
         // Handle captures
         if let Some(cap) = captured {
-            if cap.owner == piece.owner {
-                return Err("Cannot capture own piece".to_string());
-            }
             match self.active_player {
                 Player::One => self.captured_black.push(cap.piece_type),
                 Player::Two => self.captured_white.push(cap.piece_type),
@@ -355,14 +690,10 @@ impl ChessBoard {
             }
         }
         if piece.piece_type == PieceType::Rook {
-            match from {
-                0 => self.castling_rights.white_queenside = false,
-                7 => self.castling_rights.white_kingside = false,
-                56 => self.castling_rights.black_queenside = false,
-                63 => self.castling_rights.black_kingside = false,
-                _ => {}
-            }
+            self.castling_rights.clear_corner(from);
         }
+        // A rook taken on its home square loses its side's right, or a rook landing there later could castle
+        self.castling_rights.clear_corner(to);
 
         // Generate notation
         let notation = self.generate_notation(from, to, &piece, captured.map(|c| c.piece_type), promotion, is_castle, is_en_passant);
@@ -378,6 +709,7 @@ impl ChessBoard {
             is_en_passant,
             notation,
             timestamp,
+            by_owner: None,
         });
 
         // Update counters
@@ -394,16 +726,8 @@ impl ChessBoard {
         // Switch player
         self.active_player = self.active_player.other();
 
-        // Check for checkmate/stalemate (basic check - full detection in frontend)
         self.update_game_status();
-
-        if self.is_checkmate {
-            Ok(GameOutcome::Winner(self.active_player.other()))
-        } else if self.is_stalemate || self.halfmove_clock >= 100 {
-            Ok(GameOutcome::Draw)
-        } else {
-            Ok(GameOutcome::InProgress)
-        }
+        Ok(())
     }
 
     fn generate_notation(&self, from: u8, to: u8, piece: &ChessPiece, captured: Option<PieceType>, promotion: Option<PieceType>, is_castle: bool, _is_en_passant: bool) -> String {
@@ -446,13 +770,11 @@ impl ChessBoard {
         format!("{}{}{}{}{}", piece_char, capture_str, to_file, to_rank, promo_str)
     }
 
+    /// Flags whether the side to move is in check; mate and stalemate are left to `no_moves_outcome`
     fn update_game_status(&mut self) {
-        // Find king position
-        let king_sq = self.find_king(self.active_player);
-        if let Some(king_pos) = king_sq {
-            self.is_check = self.is_square_attacked(king_pos, self.active_player.other());
-        }
-        // Full checkmate/stalemate detection done in frontend for performance
+        self.is_check = self
+            .find_king(self.active_player)
+            .is_some_and(|king| self.is_attacked(king, self.active_player.other()));
     }
 
     fn find_king(&self, player: Player) -> Option<u8> {
@@ -466,49 +788,421 @@ impl ChessBoard {
         None
     }
 
-    fn is_square_attacked(&self, square: u8, by_player: Player) -> bool {
-        // Simplified attack detection
-        for (i, sq) in self.squares.iter().enumerate() {
-            if let Some(piece) = sq {
-                if piece.owner == by_player {
-                    if self.can_piece_attack(i as u8, square, piece) {
-                        return true;
-                    }
-                }
+    /// Destination squares for the piece on `square`, if it belongs to the side to move
+    pub fn valid_moves_from(&self, square: u8) -> Vec<u8> {
+        if square >= 64 {
+            return vec![];
+        }
+        let mut targets: Vec<u8> = self
+            .legal_moves(self.active_player)
+            .into_iter()
+            .filter(|&(from, _, _)| from == square)
+            .map(|(_, to, _)| to)
+            .collect();
+        targets.dedup();
+        targets
+    }
+
+    /// Records which signer submitted the latest move
+    pub fn sign_last_move(&mut self, owner: String) {
+        if let Some(record) = self.move_history.last_mut() {
+            record.by_owner = Some(owner);
+        }
+    }
+
+    /// The position after the first `move_number` moves, replayed from the start with the original records
+    pub fn position_at(&self, move_number: usize) -> Option<ChessBoard> {
+        let records = self.move_history.get(..move_number)?;
+        let mut replay = ChessBoard::new();
+        for record in records {
+            replay.make_move(record.from_square, record.to_square, record.promotion, record.timestamp).ok()?;
+        }
+        replay.move_history = records.to_vec();
+        Some(replay)
+    }
+
+    /// Reverts the last move by replaying the rest of the history
+    pub fn undo_last_move(&mut self) -> Option<ChessMoveRecord> {
+        let undone = self.move_history.last()?.clone();
+        *self = self.position_at(self.move_history.len() - 1)?;
+        Some(undone)
+    }
+
+    /// Point value of the pieces each side has taken
+    pub fn captured_value(&self) -> ChessCapturedValue {
+        ChessCapturedValue {
+            white: self.captured_black.iter().map(|&p| piece_value(p)).sum(),
+            black: self.captured_white.iter().map(|&p| piece_value(p)).sum(),
+        }
+    }
+
+    /// Captured pieces with the material balance, counted from the board so promotions are included
+    pub fn material(&self) -> ChessMaterial {
+        let balance = self
+            .squares
+            .iter()
+            .flatten()
+            .map(|piece| match piece.owner {
+                Player::One => piece_value(piece.piece_type) as i32,
+                Player::Two => -(piece_value(piece.piece_type) as i32),
+            })
+            .sum();
+        ChessMaterial {
+            captured_white: self.captured_white.clone(),
+            captured_black: self.captured_black.clone(),
+            balance,
+        }
+    }
+
+    /// How many times the current position has occurred, found by replaying the move history
+    pub fn repetition_count(&self) -> u32 {
+        let current = self.position_key();
+        let mut replay = ChessBoard::new();
+        let mut count = u32::from(replay.position_key() == current);
+        for record in &self.move_history {
+            if replay.play_move(record.from_square, record.to_square, record.promotion, record.timestamp).is_err() {
+                break;
+            }
+            if replay.position_key() == current {
+                count += 1;
             }
         }
-        false
+        count
     }
 
-    fn can_piece_attack(&self, from: u8, to: u8, piece: &ChessPiece) -> bool {
-        let from_row = from / 8;
-        let from_col = from % 8;
-        let to_row = to / 8;
-        let to_col = to % 8;
-        let row_diff = (to_row as i8 - from_row as i8).abs();
-        let col_diff = (to_col as i8 - from_col as i8).abs();
+    /// True once the current position has occurred five times, which ends the game without a claim
+    pub fn is_fivefold_repetition(&self) -> bool {
+        // Every repeat needs at least four reversible halfmoves, so a fresh clock rules it out cheaply
+        self.halfmove_clock >= 4 * (FIVEFOLD_REPETITIONS as u16 - 1) && self.repetition_count() >= FIVEFOLD_REPETITIONS
+    }
 
-        match piece.piece_type {
-            PieceType::Pawn => {
-                let direction: i8 = if piece.owner == Player::One { 1 } else { -1 };
-                col_diff == 1 && (to_row as i8 - from_row as i8) == direction
+    /// True when neither side has enough material left to deliver mate
+    pub fn is_insufficient_material(&self) -> bool {
+        let mut minors = [0u32; 2];
+        for piece in self.squares.iter().flatten() {
+            match piece.piece_type {
+                PieceType::King => {}
+                PieceType::Knight | PieceType::Bishop => minors[piece.owner.index()] += 1,
+                _ => return false,
+            }
+        }
+        minors[0] + minors[1] <= 1
+    }
+
+    /// Automatic or claimable draw conditions in the current position
+    pub fn draw_status(&self) -> GameDrawStatus {
+        let repetition_count = self.repetition_count();
+        let mut conditions = vec![];
+        if repetition_count >= 3 {
+            conditions.push(DrawCondition::ThreefoldRepetition);
+        }
+        if self.halfmove_clock >= FIFTY_MOVE_HALFMOVES {
+            conditions.push(DrawCondition::FiftyMoveRule);
+        }
+        if self.is_insufficient_material() {
+            conditions.push(DrawCondition::InsufficientMaterial);
+        }
+        GameDrawStatus {
+            conditions,
+            repetition_count,
+            halfmove_clock: self.halfmove_clock,
+        }
+    }
+
+    /// True once a player may claim a draw under the fifty-move rule
+    pub fn can_claim_fifty_move_draw(&self) -> bool {
+        self.halfmove_clock >= FIFTY_MOVE_HALFMOVES
+    }
+
+    /// Halfmoves left before the 50- and 75-move draw thresholds
+    pub fn draw_countdown(&self) -> DrawCountdown {
+        DrawCountdown {
+            fifty_move: FIFTY_MOVE_HALFMOVES.saturating_sub(self.halfmove_clock),
+            seventy_five_move: SEVENTY_FIVE_MOVE_HALFMOVES.saturating_sub(self.halfmove_clock),
+        }
+    }
+
+    // Piece placement, side to move, castling rights and en passant square
+    fn position_key(&self) -> String {
+        let mut key: String = self
+            .squares
+            .iter()
+            .map(|square| match square {
+                None => '.',
+                Some(p) => {
+                    let c = match p.piece_type {
+                        PieceType::Pawn => 'p',
+                        PieceType::Knight => 'n',
+                        PieceType::Bishop => 'b',
+                        PieceType::Rook => 'r',
+                        PieceType::Queen => 'q',
+                        PieceType::King => 'k',
+                    };
+                    if p.owner == Player::One { c.to_ascii_uppercase() } else { c }
+                }
+            })
+            .collect();
+        let rights = &self.castling_rights;
+        key.push_str(&format!(
+            "{:?}{}{}{}{}{:?}",
+            self.active_player,
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+            self.en_passant_square,
+        ));
+        key
+    }
+
+    /// Picks a reply for the side to move: the most valuable capture, otherwise a seeded random move
+    pub fn bot_move(&self, seed: u64) -> Option<(u8, u8)> {
+        // Promotions are always to a queen, so the other choices are dropped
+        let moves: Vec<(u8, u8)> = self
+            .legal_moves(self.active_player)
+            .into_iter()
+            .filter(|&(_, _, promotion)| promotion.is_none() || promotion == Some(PieceType::Queen))
+            .map(|(from, to, _)| (from, to))
+            .collect();
+        if moves.is_empty() {
+            return None;
+        }
+
+        let best_capture = moves
+            .iter()
+            .filter_map(|&(from, to)| self.squares[to as usize].map(|victim| (piece_value(victim.piece_type), from, to)))
+            .max_by_key(|&(value, _, _)| value);
+        if let Some((_, from, to)) = best_capture {
+            return Some((from, to));
+        }
+
+        let mixed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        Some(moves[(mixed >> 33) as usize % moves.len()])
+    }
+
+    /// Plays the bot's reply and reports mate or stalemate for either side
+    pub fn make_bot_move(&mut self, seed: u64, timestamp: u64) -> Result<GameOutcome, String> {
+        let (from, to) = match self.bot_move(seed) {
+            Some(m) => m,
+            None => return Ok(self.no_moves_outcome()),
+        };
+
+        self.make_move(from, to, None, timestamp)
+    }
+
+    fn no_moves_outcome(&mut self) -> GameOutcome {
+        let in_check = self
+            .find_king(self.active_player)
+            .is_some_and(|king| self.is_attacked(king, self.active_player.other()));
+        if in_check {
+            self.is_checkmate = true;
+            GameOutcome::Winner(self.active_player.other())
+        } else {
+            self.is_stalemate = true;
+            GameOutcome::Draw
+        }
+    }
+
+    /// Every legal move for `player` as (from, to, promotion), including castling and each promotion choice
+    pub fn legal_moves(&self, player: Player) -> Vec<(u8, u8, Option<PieceType>)> {
+        let mut pseudo = Vec::new();
+        for from in 0..64u8 {
+            if self.squares[from as usize].is_some_and(|p| p.owner == player) {
+                pseudo.extend(self.piece_targets(from, false).into_iter().map(|to| (from, to)));
+            }
+        }
+        pseudo.extend(self.castling_targets(player));
+
+        let mut moves = Vec::new();
+        for (from, to) in pseudo {
+            let mut after = self.clone();
+            after.apply_raw_move(from, to);
+            let safe = after
+                .find_king(player)
+                .is_some_and(|king| !after.is_attacked(king, player.other()));
+            if !safe {
+                continue;
+            }
+            let promotes = self.squares[from as usize].is_some_and(|p| p.piece_type == PieceType::Pawn)
+                && (to / 8 == 0 || to / 8 == 7);
+            if promotes {
+                for piece in [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight] {
+                    moves.push((from, to, Some(piece)));
+                }
+            } else {
+                moves.push((from, to, None));
             }
-            PieceType::Knight => {
-                (row_diff == 2 && col_diff == 1) || (row_diff == 1 && col_diff == 2)
+        }
+        moves
+    }
+
+    /// Leaf count of the legal-move tree `depth` plies deep, for checking the move generator against known totals
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves(self.active_player);
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        let timestamp = self.move_history.last().map_or(0, |record| record.timestamp);
+        moves
+            .into_iter()
+            .map(|(from, to, promotion)| {
+                let mut after = self.clone();
+                match after.make_move(from, to, promotion, timestamp) {
+                    Ok(_) => after.perft(depth - 1),
+                    Err(_) => 0,
+                }
+            })
+            .sum()
+    }
+
+    /// King destinations for castling: rights intact, path empty, and the king not in or passing through check
+    fn castling_targets(&self, player: Player) -> Vec<(u8, u8)> {
+        let rights = &self.castling_rights;
+        let (king_sq, kingside, queenside) = match player {
+            Player::One => (4u8, rights.white_kingside, rights.white_queenside),
+            Player::Two => (60u8, rights.black_kingside, rights.black_queenside),
+        };
+        let king_home = self.squares[king_sq as usize]
+            .is_some_and(|p| p.piece_type == PieceType::King && p.owner == player);
+        if !king_home || self.is_attacked(king_sq, player.other()) {
+            return vec![];
+        }
+
+        let rook_at = |sq: u8| {
+            self.squares[sq as usize].is_some_and(|p| p.piece_type == PieceType::Rook && p.owner == player)
+        };
+        let empty = |sq: u8| self.squares[sq as usize].is_none();
+        let mut targets = Vec::new();
+        if kingside && rook_at(king_sq + 3) && empty(king_sq + 1) && empty(king_sq + 2)
+            && !self.is_attacked(king_sq + 1, player.other())
+        {
+            targets.push((king_sq, king_sq + 2));
+        }
+        if queenside && rook_at(king_sq - 4) && empty(king_sq - 1) && empty(king_sq - 2) && empty(king_sq - 3)
+            && !self.is_attacked(king_sq - 1, player.other())
+        {
+            targets.push((king_sq, king_sq - 2));
+        }
+        targets
+    }
+
+    /// Moves a piece without bookkeeping, removing an en passant victim or shifting a castling rook if needed
+    fn apply_raw_move(&mut self, from: u8, to: u8) {
+        if let Some(piece) = self.squares[from as usize] {
+            if piece.piece_type == PieceType::Pawn
+                && self.en_passant_square == Some(to)
+                && from % 8 != to % 8
+                && self.squares[to as usize].is_none()
+            {
+                let victim = if piece.owner == Player::One { to - 8 } else { to + 8 };
+                self.squares[victim as usize] = None;
             }
-            PieceType::Bishop => {
-                row_diff == col_diff && row_diff > 0
+            if piece.piece_type == PieceType::King && from.abs_diff(to) == 2 {
+                let (rook_from, rook_to) = if to > from { (from + 3, from + 1) } else { (from - 4, from - 1) };
+                self.squares[rook_to as usize] = self.squares[rook_from as usize].take();
             }
-            PieceType::Rook => {
-                (row_diff == 0 || col_diff == 0) && (row_diff > 0 || col_diff > 0)
+        }
+        self.squares[to as usize] = self.squares[from as usize].take();
+    }
+
+    /// True if any piece of `by_player` attacks `square`, respecting blocked lines
+    fn is_attacked(&self, square: u8, by_player: Player) -> bool {
+        (0..64u8).any(|from| {
+            self.squares[from as usize].is_some_and(|p| p.owner == by_player)
+                && self.piece_targets(from, true).contains(&square)
+        })
+    }
+
+    /// Squares the piece on `from` can move to; `attacks_only` gives pawn capture squares instead of pushes
+    fn piece_targets(&self, from: u8, attacks_only: bool) -> Vec<u8> {
+        let piece = match self.squares[from as usize] {
+            Some(p) => p,
+            None => return vec![],
+        };
+        let row = (from / 8) as i8;
+        let col = (from % 8) as i8;
+        let square_at = |r: i8, c: i8| -> Option<u8> {
+            ((0..8).contains(&r) && (0..8).contains(&c)).then(|| (r * 8 + c) as u8)
+        };
+        let own = |sq: u8| self.squares[sq as usize].is_some_and(|p| p.owner == piece.owner);
+        let mut targets = Vec::new();
+
+        match piece.piece_type {
+            PieceType::Pawn => {
+                let dir: i8 = if piece.owner == Player::One { 1 } else { -1 };
+                for dc in [-1, 1] {
+                    if let Some(sq) = square_at(row + dir, col + dc) {
+                        let enemy = self.squares[sq as usize].is_some_and(|p| p.owner != piece.owner);
+                        if attacks_only || enemy || self.en_passant_square == Some(sq) {
+                            targets.push(sq);
+                        }
+                    }
+                }
+                if !attacks_only {
+                    if let Some(one) = square_at(row + dir, col).filter(|&sq| self.squares[sq as usize].is_none()) {
+                        targets.push(one);
+                        let start_row = if piece.owner == Player::One { 1 } else { 6 };
+                        if row == start_row {
+                            if let Some(two) = square_at(row + 2 * dir, col).filter(|&sq| self.squares[sq as usize].is_none()) {
+                                targets.push(two);
+                            }
+                        }
+                    }
+                }
             }
-            PieceType::Queen => {
-                (row_diff == col_diff || row_diff == 0 || col_diff == 0) && (row_diff > 0 || col_diff > 0)
+            PieceType::Knight | PieceType::King => {
+                let steps: &[(i8, i8)] = if piece.piece_type == PieceType::Knight {
+                    &[(2, 1), (2, -1), (-2, 1), (-2, -1), (1, 2), (1, -2), (-1, 2), (-1, -2)]
+                } else {
+                    &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)]
+                };
+                for &(dr, dc) in steps {
+                    if let Some(sq) = square_at(row + dr, col + dc).filter(|&sq| !own(sq)) {
+                        targets.push(sq);
+                    }
+                }
             }
-            PieceType::King => {
-                row_diff <= 1 && col_diff <= 1 && (row_diff > 0 || col_diff > 0)
+            PieceType::Bishop | PieceType::Rook | PieceType::Queen => {
+                let diagonal = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+                let straight = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+                let directions: Vec<(i8, i8)> = match piece.piece_type {
+                    PieceType::Bishop => diagonal.to_vec(),
+                    PieceType::Rook => straight.to_vec(),
+                    _ => diagonal.iter().chain(straight.iter()).copied().collect(),
+                };
+                for (dr, dc) in directions {
+                    let (mut r, mut c) = (row + dr, col + dc);
+                    while let Some(sq) = square_at(r, c) {
+                        if own(sq) {
+                            break;
+                        }
+                        targets.push(sq);
+                        if self.squares[sq as usize].is_some() {
+                            break;
+                        }
+                        r += dr;
+                        c += dc;
+                    }
+                }
             }
         }
+
+        targets
+    }
+}
+
+/// Conventional material value of a piece; the king is never traded
+pub fn piece_value(piece: PieceType) -> u32 {
+    match piece {
+        PieceType::Pawn => 1,
+        PieceType::Knight | PieceType::Bishop => 3,
+        PieceType::Rook => 5,
+        PieceType::Queen => 9,
+        PieceType::King => 0,
     }
 }
 
@@ -537,6 +1231,40 @@ pub enum PokerStage {
     Showdown,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum BettingMode {
+    #[default]
+    NoLimit,
+    // A raise may be at most the pot after calling
+    PotLimit,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum PokerVariant {
+    // Two hole cards, any of which may play
+    #[default]
+    Holdem,
+    // Four hole cards, of which a hand uses exactly two
+    Omaha,
+}
+
+impl PokerVariant {
+    pub fn hole_cards(self) -> u8 {
+        match self {
+            PokerVariant::Holdem => 2,
+            PokerVariant::Omaha => 4,
+        }
+    }
+
+    /// Hole cards the variant forces into the final hand, or None when any number may play
+    pub fn required_hole_cards(self) -> Option<u8> {
+        match self {
+            PokerVariant::Holdem => None,
+            PokerVariant::Omaha => Some(2),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
 pub enum PokerAction {
     Fold,
@@ -555,55 +1283,304 @@ pub struct PokerGame {
     pub current_bet: u64,
     pub player_bets: Vec<u64>,
     pub player_chips: Vec<u64>,
-    pub active_player: Player,
+    // Seat indices; seats run clockwise from 0
+    pub active_player: usize,
     pub stage: PokerStage,
-    pub dealer: Player,
+    pub dealer: usize,
     pub folded: Vec<bool>,
     pub all_in: Vec<bool>,
-    pub last_raiser: Option<Player>,
+    pub last_raiser: Option<usize>,
     pub action_history: Vec<PokerActionRecord>,
     pub round_complete: bool,
     pub small_blind: u64,
     pub big_blind: u64,
+    pub variant: PokerVariant,
+    // Hole cards dealt per player, and how many of them a hand must use (None = any), as the variant sets them
+    pub hole_cards: u8,
+    pub required_hole_cards: Option<u8>,
+    pub pot_by_street: Vec<StreetPot>,
+    // Size of the last full raise this street; short all-ins don't change it
+    pub min_raise: u64,
+    // Who has acted since the last full raise; they may not raise again
+    pub acted: Vec<bool>,
+    pub betting_mode: BettingMode,
+    // Seats that took the pot, filled in once the hand is over
+    pub winners: Vec<usize>,
+    // Whether every contested pot was split between all the live seats, which alone makes the hand a draw
+    pub pot_split: bool,
+    // Size of the contested pot paid out at the end of the last hand
+    pub awarded_pot: u64,
+    // Chips each seat has put in over the whole hand, which decides the side pots
+    pub contributed: Vec<u64>,
+    // Each seat's stack as the hand was dealt, before the blinds; a rebuy lands before this is taken
+    pub hand_start_chips: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct StreetPot {
+    pub stage: PokerStage,
+    pub pot: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct BoardCard {
+    pub card: Card,
+    pub stage: PokerStage,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
 pub struct PokerActionRecord {
-    pub player: Player,
+    pub player: usize,
     pub action: PokerAction,
     pub amount: u64,
     pub stage: PokerStage,
     pub timestamp: u64,
+    pub by_owner: Option<String>,
 }
 
-impl PokerGame {
-    pub fn new(starting_chips: u64, small_blind: u64, big_blind: u64, seed: u64) -> Self {
-        let mut deck = Self::create_shuffled_deck(seed);
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerContribution {
+    pub player: usize,
+    pub amount: u64,
+}
 
-        // Deal 2 cards to each player
-        let p1_hand = vec![deck.pop().unwrap(), deck.pop().unwrap()];
-        let p2_hand = vec![deck.pop().unwrap(), deck.pop().unwrap()];
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PokerStreetContributions {
+    pub stage: PokerStage,
+    pub current_bet: u64,
+    pub contributions: Vec<PlayerContribution>,
+}
 
-        PokerGame {
-            player_hands: vec![p1_hand, p2_hand],
-            community_cards: vec![],
-            deck,
-            pot: small_blind + big_blind,
-            current_bet: big_blind,
-            player_bets: vec![small_blind, big_blind],
-            player_chips: vec![starting_chips - small_blind, starting_chips - big_blind],
-            active_player: Player::One, // Small blind acts first pre-flop
-            stage: PokerStage::PreFlop,
-            dealer: Player::One,
-            folded: vec![false, false],
-            all_in: vec![false, false],
-            last_raiser: Some(Player::Two), // Big blind is initial "raiser"
-            action_history: vec![],
-            round_complete: false,
-            small_blind,
-            big_blind,
-        }
-    }
+/// Error for a bet whose chip counts would wrap around
+fn chip_overflow() -> String {
+    "Chip count overflow".to_string()
+}
+
+pub const MIN_POKER_SEATS: usize = 2;
+pub const MAX_POKER_SEATS: usize = 6;
+
+/// Stack each seat sits down with and the blinds it posts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "PokerStakesInput")]
+pub struct PokerStakes {
+    pub starting_chips: u64,
+    pub small_blind: u64,
+    pub big_blind: u64,
+}
+
+impl PokerStakes {
+    /// Blinds must be ordered and non-zero, a stack must be able to post the big blind, and a full table's chips
+    /// must still fit the signed deltas results are reported in
+    pub fn is_valid(&self) -> bool {
+        let table_fits = self
+            .starting_chips
+            .checked_mul(MAX_POKER_SEATS as u64)
+            .is_some_and(|total| total <= i64::MAX as u64);
+        0 < self.small_blind && self.small_blind < self.big_blind && self.big_blind <= self.starting_chips && table_fits
+    }
+}
+
+impl Default for PokerStakes {
+    fn default() -> PokerStakes {
+        PokerStakes { starting_chips: 1000, small_blind: 10, big_blind: 20 }
+    }
+}
+
+impl PokerGame {
+    /// A heads-up table
+    pub fn new(starting_chips: u64, small_blind: u64, big_blind: u64, variant: PokerVariant, seed: u64) -> Self {
+        Self::with_seats(2, starting_chips, small_blind, big_blind, variant, seed)
+    }
+
+    /// A table of `seats` players with seat 0 on the button; `seats` is clamped to the supported range
+    pub fn with_seats(seats: usize, starting_chips: u64, small_blind: u64, big_blind: u64, variant: PokerVariant, seed: u64) -> Self {
+        let seats = seats.clamp(MIN_POKER_SEATS, MAX_POKER_SEATS);
+        let mut game = PokerGame {
+            player_hands: vec![],
+            community_cards: vec![],
+            deck: vec![],
+            pot: 0,
+            current_bet: 0,
+            player_bets: vec![],
+            player_chips: vec![starting_chips; seats],
+            active_player: 0,
+            stage: PokerStage::PreFlop,
+            dealer: 0,
+            folded: vec![],
+            all_in: vec![],
+            last_raiser: None,
+            action_history: vec![],
+            round_complete: false,
+            small_blind,
+            big_blind,
+            variant,
+            hole_cards: variant.hole_cards(),
+            required_hole_cards: variant.required_hole_cards(),
+            pot_by_street: vec![],
+            min_raise: big_blind,
+            acted: vec![],
+            betting_mode: BettingMode::NoLimit,
+            winners: vec![],
+            pot_split: false,
+            awarded_pot: 0,
+            contributed: vec![],
+            hand_start_chips: vec![],
+        };
+        game.deal_hand(0, seed);
+        game
+    }
+
+    /// Resets the table for a hand with the button on `dealer`, posting the blinds from the current stacks
+    fn deal_hand(&mut self, dealer: usize, seed: u64) {
+        let seats = self.player_chips.len();
+        let mut deck = Self::create_shuffled_deck(seed);
+
+        // Deal the hole cards to each player
+        self.player_hands = (0..seats).map(|_| Self::deal_hole_cards(&mut deck, self.hole_cards)).collect();
+        self.deck = deck;
+        self.community_cards.clear();
+
+        // Heads-up the button posts the small blind; otherwise the blinds sit to its left
+        let small_blind_seat = if seats == 2 { dealer } else { (dealer + 1) % seats };
+        let big_blind_seat = (small_blind_seat + 1) % seats;
+
+        self.hand_start_chips = self.player_chips.clone();
+        self.action_history.clear();
+        self.player_bets = vec![0; seats];
+        self.player_bets[small_blind_seat] = self.small_blind;
+        self.player_bets[big_blind_seat] = self.big_blind;
+        for (chips, bet) in self.player_chips.iter_mut().zip(&self.player_bets) {
+            *chips -= bet;
+        }
+
+        self.pot = self.small_blind + self.big_blind;
+        self.current_bet = self.big_blind;
+        // First to act pre-flop sits left of the big blind, which heads-up is the button
+        self.active_player = (big_blind_seat + 1) % seats;
+        self.stage = PokerStage::PreFlop;
+        self.dealer = dealer;
+        self.folded = vec![false; seats];
+        self.all_in = vec![false; seats];
+        self.last_raiser = Some(big_blind_seat); // Big blind is initial "raiser"
+        self.round_complete = false;
+        self.pot_by_street = vec![];
+        self.min_raise = self.big_blind;
+        self.acted = vec![false; seats];
+        self.winners = vec![];
+        self.pot_split = false;
+        self.awarded_pot = 0;
+        self.contributed = self.player_bets.clone();
+    }
+
+    /// Pays a finished hand's pot as a main pot plus one side pot per all-in level. Each pot goes to the best live
+    /// hands among the seats that paid into it, any odd chip to the first of them, and a bet nobody matched goes back
+    /// to whoever made it. `winners` ends up as every seat paid from a contested pot, and `pot_split` records whether
+    /// each of those pots was shared by every live seat
+    fn award_pot(&mut self) {
+        let live: Vec<usize> = (0..self.num_players()).filter(|&i| !self.folded[i]).collect();
+        let mut levels: Vec<u64> = self.contributed.iter().copied().filter(|&c| c > 0).collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut winners: Vec<usize> = vec![];
+        let mut pot_split = true;
+        let mut floor = 0;
+        // Chips from a level only folded seats paid into go to the winners of the level below
+        let mut orphaned = 0;
+        let mut last_winners: Vec<usize> = vec![];
+        let mut refunded = 0;
+        for level in levels {
+            let payers: Vec<usize> = (0..self.num_players()).filter(|&i| self.contributed[i] >= level).collect();
+            let amount = (level - floor) * payers.len() as u64;
+            floor = level;
+
+            let eligible: Vec<usize> = payers.iter().copied().filter(|i| live.contains(i)).collect();
+            if eligible.is_empty() {
+                orphaned += amount;
+                continue;
+            }
+            if payers.len() == 1 {
+                refunded += amount;
+                self.pay_out(amount, &eligible);
+                continue;
+            }
+
+            let level_winners = self.best_hands(&eligible);
+            pot_split &= level_winners == live;
+            self.pay_out(amount, &level_winners);
+            for &seat in &level_winners {
+                if !winners.contains(&seat) {
+                    winners.push(seat);
+                }
+            }
+            last_winners = level_winners;
+        }
+        if orphaned > 0 {
+            let takers = if last_winners.is_empty() { live.clone() } else { last_winners };
+            self.pay_out(orphaned, &takers);
+        }
+
+        winners.sort_unstable();
+        self.winners = if winners.is_empty() { live } else { winners };
+        self.pot_split = pot_split;
+        self.awarded_pot = self.pot - refunded;
+        self.pot = 0;
+    }
+
+    /// Splits `amount` evenly between `seats`, the first of them taking any odd chip
+    fn pay_out(&mut self, amount: u64, seats: &[usize]) {
+        if seats.is_empty() {
+            return;
+        }
+        let share = amount / seats.len() as u64;
+        let odd_chip = amount % seats.len() as u64;
+        for (i, &seat) in seats.iter().enumerate() {
+            self.player_chips[seat] += if i == 0 { share + odd_chip } else { share };
+        }
+    }
+
+    /// Settles the finished hand and deals the next one with the button moved one seat
+    pub fn start_next_hand(&mut self, seed: u64) -> Result<(), String> {
+        if !self.is_hand_over() {
+            return Err("Hand still in play".to_string());
+        }
+        if self.player_chips.iter().any(|&chips| chips < self.big_blind) {
+            return Err("A stack cannot cover the blinds".to_string());
+        }
+        let dealer = (self.dealer + 1) % self.num_players();
+        self.deal_hand(dealer, seed);
+        Ok(())
+    }
+
+    /// Tops a busted seat back up to `amount`, at most `max_buy_in`, and deals the next hand
+    pub fn rebuy(&mut self, seat: usize, amount: u64, max_buy_in: u64, seed: u64) -> Result<(), String> {
+        if !self.is_hand_over() {
+            return Err("Cannot rebuy mid-hand".to_string());
+        }
+        if seat >= self.num_players() {
+            return Err("No such seat".to_string());
+        }
+        if self.player_chips[seat] > 0 {
+            return Err("Only a busted stack can rebuy".to_string());
+        }
+        if amount < self.big_blind || amount > max_buy_in {
+            return Err("Rebuy outside the buy-in range".to_string());
+        }
+        let table_chips = self.player_chips.iter().try_fold(amount, |total, &chips| total.checked_add(chips));
+        if table_chips.is_none_or(|total| total > i64::MAX as u64) {
+            return Err("Rebuy would take the table past its chip limit".to_string());
+        }
+        self.player_chips[seat] = amount;
+        self.start_next_hand(seed)
+    }
+
+    pub fn num_players(&self) -> usize {
+        self.player_hands.len()
+    }
+
+    pub fn is_hand_over(&self) -> bool {
+        !self.winners.is_empty()
+    }
 
     fn create_shuffled_deck(seed: u64) -> Vec<Card> {
         let mut deck = Vec::with_capacity(52);
@@ -624,70 +1601,124 @@ impl PokerGame {
         deck
     }
 
-    pub fn make_action(&mut self, action: PokerAction, amount: Option<u64>, timestamp: u64) -> Result<GameOutcome, String> {
-        let player_idx = self.active_player.index();
+    fn deal_hole_cards(deck: &mut Vec<Card>, hole_cards: u8) -> Vec<Card> {
+        (0..hole_cards).filter_map(|_| deck.pop()).collect()
+    }
+
+    /// Applies an action for seat `player_idx`, which must be the active seat. Heads-up the result is reported as a
+    /// two-player outcome; a finished multi-way hand names its winning seats
+    pub fn make_action(
+        &mut self,
+        player_idx: usize,
+        action: PokerAction,
+        amount: Option<u64>,
+        timestamp: u64,
+    ) -> Result<GameOutcome, String> {
+        if self.is_hand_over() {
+            return Err("Hand is over".to_string());
+        }
+
+        if self.action_history.last().is_some_and(|last| timestamp < last.timestamp) {
+            return Err("Action timestamp earlier than previous action".to_string());
+        }
 
+        if player_idx >= self.num_players() {
+            return Err("No such seat".to_string());
+        }
         if self.folded[player_idx] {
             return Err("Player has folded".to_string());
         }
+        if self.all_in[player_idx] {
+            return Err("Player is all in".to_string());
+        }
+        if player_idx != self.active_player {
+            return Err("Not your turn".to_string());
+        }
 
         match action {
             PokerAction::Fold => {
                 self.folded[player_idx] = true;
-                self.action_history.push(PokerActionRecord {
-                    player: self.active_player,
-                    action,
-                    amount: 0,
-                    stage: self.stage,
-                    timestamp,
-                });
-                return Ok(GameOutcome::Winner(self.active_player.other()));
+                // Once a single seat is left it takes the pot uncontested, whatever the street
+                if self.folded.iter().filter(|&&folded| !folded).count() == 1 {
+                    self.action_history.push(PokerActionRecord {
+                        player: self.active_player,
+                        action,
+                        amount: 0,
+                        stage: self.stage,
+                        timestamp,
+                        by_owner: None,
+                    });
+                    self.award_pot();
+                    return Ok(self.hand_outcome());
+                }
             }
             PokerAction::Check => {
                 if self.player_bets[player_idx] < self.current_bet {
                     return Err("Cannot check, must call or raise".to_string());
                 }
+                self.acted[player_idx] = true;
             }
             PokerAction::Call => {
-                let to_call = self.current_bet - self.player_bets[player_idx];
-                if to_call > self.player_chips[player_idx] {
-                    // All-in
-                    let chips = self.player_chips[player_idx];
-                    self.pot += chips;
-                    self.player_bets[player_idx] += chips;
-                    self.player_chips[player_idx] = 0;
+                let to_call = self.current_bet.checked_sub(self.player_bets[player_idx]).ok_or_else(chip_overflow)?;
+                // Short of the full call the player goes all in
+                let paid = to_call.min(self.player_chips[player_idx]);
+                self.commit_chips(player_idx, paid)?;
+                if to_call > paid {
                     self.all_in[player_idx] = true;
-                } else {
-                    self.pot += to_call;
-                    self.player_bets[player_idx] = self.current_bet;
-                    self.player_chips[player_idx] -= to_call;
                 }
+                self.acted[player_idx] = true;
             }
             PokerAction::Raise => {
+                if self.player_chips[player_idx] == 0 {
+                    return Err("No chips left to raise".to_string());
+                }
+                if self.acted[player_idx] {
+                    return Err("Betting has not been reopened".to_string());
+                }
+
                 let raise_amount = amount.unwrap_or(self.big_blind);
-                let to_call = self.current_bet - self.player_bets[player_idx];
-                let total = to_call + raise_amount;
+                if raise_amount < self.min_raise {
+                    return Err("Raise below minimum".to_string());
+                }
+
+                let to_call = self.current_bet.checked_sub(self.player_bets[player_idx]).ok_or_else(chip_overflow)?;
+                if raise_amount > self.max_raise(to_call) {
+                    return Err("Raise above pot limit".to_string());
+                }
+                let total = to_call.checked_add(raise_amount).ok_or_else(chip_overflow)?;
 
                 if total > self.player_chips[player_idx] {
                     return Err("Insufficient chips".to_string());
                 }
 
-                self.pot += total;
-                self.player_chips[player_idx] -= total;
-                self.player_bets[player_idx] = self.current_bet + raise_amount;
+                self.commit_chips(player_idx, total)?;
                 self.current_bet = self.player_bets[player_idx];
-                self.last_raiser = Some(self.active_player);
+                self.register_full_raise(player_idx, raise_amount);
             }
             PokerAction::AllIn => {
                 let chips = self.player_chips[player_idx];
-                self.pot += chips;
-                self.player_bets[player_idx] += chips;
-                self.player_chips[player_idx] = 0;
+                let raise_amount = self.player_bets[player_idx]
+                    .checked_add(chips)
+                    .ok_or_else(chip_overflow)?
+                    .saturating_sub(self.current_bet);
+                if raise_amount > 0 && self.acted[player_idx] {
+                    return Err("Betting has not been reopened".to_string());
+                }
+                let to_call = self.current_bet.saturating_sub(self.player_bets[player_idx]);
+                if raise_amount > self.max_raise(to_call) {
+                    return Err("Raise above pot limit".to_string());
+                }
+
+                self.commit_chips(player_idx, chips)?;
                 self.all_in[player_idx] = true;
+                self.acted[player_idx] = true;
 
-                if self.player_bets[player_idx] > self.current_bet {
+                if raise_amount > 0 {
                     self.current_bet = self.player_bets[player_idx];
-                    self.last_raiser = Some(self.active_player);
+                    // Only a full raise reopens the betting for players who already acted
+                    if raise_amount >= self.min_raise {
+                        self.register_full_raise(player_idx, raise_amount);
+                    }
                 }
             }
         }
@@ -698,39 +1729,225 @@ impl PokerGame {
             amount: amount.unwrap_or(0),
             stage: self.stage,
             timestamp,
+            by_owner: None,
         });
 
         // Check if betting round is complete
         if self.is_round_complete() {
             self.advance_stage();
+            // Run out the board once nobody is left to bet against
+            while self.stage != PokerStage::Showdown && self.players_able_to_act() <= 1 {
+                self.advance_stage();
+            }
         } else {
-            self.active_player = self.active_player.other();
+            self.active_player = self.next_to_act(self.active_player);
         }
 
         if self.stage == PokerStage::Showdown {
-            return self.determine_winner();
+            // A tie splits each pot between the tied seats
+            self.award_pot();
+            return Ok(self.hand_outcome());
         }
 
         Ok(GameOutcome::InProgress)
     }
 
-    fn is_round_complete(&self) -> bool {
-        // Both players have acted and bets are equal (or one is all-in)
-        let p1_bet = self.player_bets[0];
-        let p2_bet = self.player_bets[1];
+    /// The next seat after `seat` still able to bet, or `seat` itself if nobody else can
+    fn next_to_act(&self, seat: usize) -> usize {
+        let seats = self.num_players();
+        (1..=seats)
+            .map(|step| (seat + step) % seats)
+            .find(|&i| !self.folded[i] && !self.all_in[i])
+            .unwrap_or(seat)
+    }
 
-        if self.all_in[0] || self.all_in[1] {
-            return true;
+    /// Heads-up a lone winner beats the other seat; otherwise the winning seats are named. Only a hand whose every
+    /// pot was shared by all the seats is a draw
+    fn hand_outcome(&self) -> GameOutcome {
+        if self.pot_split && self.winners.len() == self.num_players() {
+            return GameOutcome::Draw;
+        }
+        match self.winners.as_slice() {
+            [0] if self.num_players() == 2 => GameOutcome::Winner(Player::One),
+            [1] if self.num_players() == 2 => GameOutcome::Winner(Player::Two),
+            _ => GameOutcome::SeatWinners(self.winners.clone()),
+        }
+    }
+
+    /// Largest raise on top of `to_call` the betting mode allows
+    fn max_raise(&self, to_call: u64) -> u64 {
+        match self.betting_mode {
+            BettingMode::NoLimit => u64::MAX,
+            BettingMode::PotLimit => self.pot.saturating_add(to_call),
+        }
+    }
+
+    /// Moves `amount` from a seat's stack into its bet and the pot, leaving the table untouched if any count would overflow
+    fn commit_chips(&mut self, player_idx: usize, amount: u64) -> Result<(), String> {
+        let chips = self.player_chips[player_idx].checked_sub(amount).ok_or_else(chip_overflow)?;
+        let bet = self.player_bets[player_idx].checked_add(amount).ok_or_else(chip_overflow)?;
+        let pot = self.pot.checked_add(amount).ok_or_else(chip_overflow)?;
+        let contributed = self.contributed[player_idx].checked_add(amount).ok_or_else(chip_overflow)?;
+        self.player_chips[player_idx] = chips;
+        self.player_bets[player_idx] = bet;
+        self.pot = pot;
+        self.contributed[player_idx] = contributed;
+        Ok(())
+    }
+
+    /// Re-deals the hole cards from a new seed before any action has been taken
+    pub fn reseed(&mut self, seed: u64) -> Result<(), String> {
+        if !self.action_history.is_empty() {
+            return Err("Hand already in play".to_string());
+        }
+
+        let mut deck = Self::create_shuffled_deck(seed);
+        for hand in self.player_hands.iter_mut() {
+            *hand = Self::deal_hole_cards(&mut deck, self.hole_cards);
         }
+        self.deck = deck;
+        Ok(())
+    }
+
+    /// Net chips each seat won or lost on the hand against the stack it was dealt in with, so chips bought back in
+    /// never count as winnings; a draw splits the pot. A finished hand has already paid its `winners`, otherwise
+    /// the recorded result decides who takes the pot
+    pub fn chip_deltas(&self, winner: Option<Player>) -> Vec<i64> {
+        let contributed: Vec<i64> = self
+            .hand_start_chips
+            .iter()
+            .zip(&self.player_chips)
+            .map(|(&start, &chips)| start as i64 - chips as i64)
+            .collect();
+        let sharers: Vec<usize> = match winner {
+            _ if self.is_hand_over() => self.winners.clone(),
+            Some(w) => vec![w.index()],
+            None => (0..contributed.len()).filter(|&i| !self.folded[i]).collect(),
+        };
+        let share = self.pot as i64 / sharers.len().max(1) as i64;
+        contributed
+            .iter()
+            .enumerate()
+            .map(|(i, &paid)| if sharers.contains(&i) { share - paid } else { -paid })
+            .collect()
+    }
+
+    /// Records which signer submitted the latest action
+    pub fn sign_last_action(&mut self, owner: String) {
+        if let Some(record) = self.action_history.last_mut() {
+            record.by_owner = Some(owner);
+        }
+    }
+
+    /// Table parameters, read before any action has moved chips
+    pub fn rules(&self) -> PokerRules {
+        PokerRules {
+            small_blind: self.small_blind,
+            big_blind: self.big_blind,
+            buy_in: self.player_chips[0] + self.player_bets[0],
+            variant: self.variant,
+            hole_cards: self.hole_cards,
+            betting_mode: self.betting_mode,
+        }
+    }
+
+    /// Copy safe to show a spectator: no deck, and hole cards only at showdown
+    pub fn spectator_view(&self) -> PokerGame {
+        self.view_for(&[])
+    }
+
+    /// Spectator view that still shows the hole cards of `seats`, the ones the viewer holds
+    pub fn view_for(&self, seats: &[usize]) -> PokerGame {
+        let mut view = self.clone();
+        view.deck.clear();
+        if self.stage != PokerStage::Showdown {
+            for (seat, hand) in view.player_hands.iter_mut().enumerate() {
+                if !seats.contains(&seat) {
+                    hand.clear();
+                }
+            }
+        }
+        view
+    }
+
+    /// True if the player's hole cards and the board hold ten to ace of one suit
+    pub fn has_royal_flush(&self, player_idx: usize) -> bool {
+        let hand = match self.player_hands.get(player_idx) {
+            Some(h) => h,
+            None => return false,
+        };
+        [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades].iter().any(|&suit| {
+            (10..=14).all(|rank| {
+                hand.iter()
+                    .chain(&self.community_cards)
+                    .any(|c| c.suit == suit && c.rank == rank)
+            })
+        })
+    }
+
+    /// Community cards in the order dealt, tagged with the street that revealed them
+    pub fn board_timeline(&self) -> Vec<BoardCard> {
+        self.community_cards
+            .iter()
+            .enumerate()
+            .map(|(i, &card)| BoardCard {
+                card,
+                stage: match i {
+                    0..=2 => PokerStage::Flop,
+                    3 => PokerStage::Turn,
+                    _ => PokerStage::River,
+                },
+            })
+            .collect()
+    }
+
+    /// Chips each player has committed on the current street
+    pub fn street_contributions(&self) -> PokerStreetContributions {
+        PokerStreetContributions {
+            stage: self.stage,
+            current_bet: self.current_bet,
+            contributions: self
+                .player_bets
+                .iter()
+                .enumerate()
+                .map(|(player, &amount)| PlayerContribution { player, amount })
+                .collect(),
+        }
+    }
+
+    fn register_full_raise(&mut self, player_idx: usize, raise_amount: u64) {
+        self.min_raise = raise_amount;
+        self.last_raiser = Some(self.active_player);
+        self.acted = vec![false; self.acted.len()];
+        self.acted[player_idx] = true;
+    }
 
-        p1_bet == p2_bet && self.action_history.iter().filter(|a| a.stage == self.stage).count() >= 2
+    fn players_able_to_act(&self) -> usize {
+        (0..self.folded.len())
+            .filter(|&i| !self.folded[i] && !self.all_in[i])
+            .count()
+    }
+
+    fn is_round_complete(&self) -> bool {
+        // Everyone still betting has acted and matched the current bet
+        (0..self.folded.len()).all(|i| {
+            self.folded[i] || self.all_in[i] || (self.acted[i] && self.player_bets[i] == self.current_bet)
+        })
     }
 
     fn advance_stage(&mut self) {
+        // Snapshot the pot as the street closes
+        self.pot_by_street.push(StreetPot {
+            stage: self.stage,
+            pot: self.pot,
+        });
+
         // Reset for new round
-        self.player_bets = vec![0, 0];
+        self.player_bets = vec![0; self.num_players()];
         self.current_bet = 0;
         self.last_raiser = None;
+        self.min_raise = self.big_blind;
+        self.acted = vec![false; self.acted.len()];
 
         match self.stage {
             PokerStage::PreFlop => {
@@ -741,21 +1958,21 @@ impl PokerGame {
                         self.community_cards.push(card);
                     }
                 }
-                self.active_player = self.dealer.other();
+                self.active_player = self.next_to_act(self.dealer);
             }
             PokerStage::Flop => {
                 self.stage = PokerStage::Turn;
                 if let Some(card) = self.deck.pop() {
                     self.community_cards.push(card);
                 }
-                self.active_player = self.dealer.other();
+                self.active_player = self.next_to_act(self.dealer);
             }
             PokerStage::Turn => {
                 self.stage = PokerStage::River;
                 if let Some(card) = self.deck.pop() {
                     self.community_cards.push(card);
                 }
-                self.active_player = self.dealer.other();
+                self.active_player = self.next_to_act(self.dealer);
             }
             PokerStage::River => {
                 self.stage = PokerStage::Showdown;
@@ -764,90 +1981,116 @@ impl PokerGame {
         }
     }
 
-    fn determine_winner(&self) -> Result<GameOutcome, String> {
-        // Evaluate hands and determine winner
-        let p1_score = self.evaluate_hand(0);
-        let p2_score = self.evaluate_hand(1);
-
-        if p1_score > p2_score {
-            Ok(GameOutcome::Winner(Player::One))
-        } else if p2_score > p1_score {
-            Ok(GameOutcome::Winner(Player::Two))
-        } else {
-            Ok(GameOutcome::Draw)
+    /// The seats among `seats` holding the best score; a lone seat wins without its hand being looked at
+    fn best_hands(&self, seats: &[usize]) -> Vec<usize> {
+        if seats.len() == 1 {
+            return seats.to_vec();
         }
+        let scores: Vec<(usize, HandScore)> = seats.iter().map(|&i| (i, self.evaluate_hand(i))).collect();
+        let best = scores.iter().map(|&(_, score)| score).max().unwrap_or_default();
+        scores.into_iter().filter(|&(_, score)| score == best).map(|(i, _)| i).collect()
     }
 
-    fn evaluate_hand(&self, player_idx: usize) -> u32 {
+    fn evaluate_hand(&self, player_idx: usize) -> HandScore {
+        let hole = &self.player_hands[player_idx];
+
+        if let Some(required) = self.required_hole_cards {
+            let required = required as usize;
+            let from_board = 5usize.saturating_sub(required);
+            if hole.len() >= required && self.community_cards.len() >= from_board {
+                // Best five-card hand using exactly `required` hole cards
+                let mut best = HandScore::default();
+                for hole_pick in index_combinations(hole.len(), required) {
+                    for board_pick in index_combinations(self.community_cards.len(), from_board) {
+                        let cards: Vec<Card> = hole_pick
+                            .iter()
+                            .map(|&i| hole[i])
+                            .chain(board_pick.iter().map(|&i| self.community_cards[i]))
+                            .collect();
+                        best = best.max(self.score_cards(&cards));
+                    }
+                }
+                return best;
+            }
+        }
+
         // Combine player's hole cards with community cards
-        let mut all_cards = self.player_hands[player_idx].clone();
+        let mut all_cards = hole.clone();
         all_cards.extend(self.community_cards.iter().cloned());
+        self.score_cards(&all_cards)
+    }
 
-        // Simple hand ranking (higher = better)
-        // This is simplified - full poker hand evaluation in frontend
-        let score: u32;
-
-        // Count ranks
-        let mut rank_counts = [0u8; 15];
-        let mut suit_counts = [0u8; 4];
-
-        for card in &all_cards {
-            rank_counts[card.rank as usize] += 1;
-            suit_counts[card.suit as usize] += 1;
-        }
-
-        // Check for flush
-        let is_flush = suit_counts.iter().any(|&c| c >= 5);
-
-        // Check for straight
-        let is_straight = self.check_straight(&rank_counts);
-
-        // Count pairs, trips, quads
-        let pairs: Vec<usize> = rank_counts.iter().enumerate().filter(|(_, &c)| c == 2).map(|(i, _)| i).collect();
-        let trips: Vec<usize> = rank_counts.iter().enumerate().filter(|(_, &c)| c == 3).map(|(i, _)| i).collect();
-        let quads: Vec<usize> = rank_counts.iter().enumerate().filter(|(_, &c)| c == 4).map(|(i, _)| i).collect();
-
-        if is_straight && is_flush {
-            score = 800 + rank_counts.iter().enumerate().filter(|(_, &c)| c > 0).map(|(i, _)| i).max().unwrap_or(0) as u32;
-        } else if !quads.is_empty() {
-            score = 700 + quads[0] as u32;
-        } else if !trips.is_empty() && !pairs.is_empty() {
-            score = 600 + trips[0] as u32;
-        } else if is_flush {
-            score = 500;
-        } else if is_straight {
-            score = 400;
-        } else if !trips.is_empty() {
-            score = 300 + trips[0] as u32;
-        } else if pairs.len() >= 2 {
-            score = 200 + *pairs.iter().max().unwrap_or(&0) as u32;
-        } else if pairs.len() == 1 {
-            score = 100 + pairs[0] as u32;
-        } else {
-            score = rank_counts.iter().enumerate().filter(|(_, &c)| c > 0).map(|(i, _)| i).max().unwrap_or(0) as u32;
-        }
+    /// Best five-card hand that can be made from `all_cards`
+    fn score_cards(&self, all_cards: &[Card]) -> HandScore {
+        index_combinations(all_cards.len(), all_cards.len().min(5))
+            .into_iter()
+            .map(|pick| score_five(&pick.iter().map(|&i| all_cards[i]).collect::<Vec<_>>()))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// A hand's category (high card 0 up to straight flush 8), then the ranks that break ties within it, highest first
+type HandScore = (u8, [u8; 5]);
 
-        score
+/// Scores a hand of at most five cards, so equal scores are true ties
+fn score_five(cards: &[Card]) -> HandScore {
+    let mut counts = [0u8; 15];
+    for card in cards {
+        counts[card.rank as usize] += 1;
+    }
+    // Ranks by how many of each are held, then by rank: quads before trips before pairs before kickers
+    let mut groups: Vec<(u8, u8)> = (2..=14u8)
+        .filter(|&rank| counts[rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let mut ranks = [0u8; 5];
+    let ordered = groups.iter().flat_map(|&(count, rank)| std::iter::repeat_n(rank, count as usize));
+    for (slot, rank) in ranks.iter_mut().zip(ordered) {
+        *slot = rank;
     }
 
-    fn check_straight(&self, rank_counts: &[u8; 15]) -> bool {
-        let mut consecutive = 0;
-        for i in (2..=14).rev() {
-            if rank_counts[i] > 0 {
-                consecutive += 1;
-                if consecutive >= 5 {
-                    return true;
-                }
-            } else {
-                consecutive = 0;
-            }
-        }
-        // Check wheel (A-2-3-4-5)
-        if rank_counts[14] > 0 && rank_counts[2] > 0 && rank_counts[3] > 0 && rank_counts[4] > 0 && rank_counts[5] > 0 {
-            return true;
+    let is_flush = cards.len() == 5 && cards.iter().all(|c| c.suit == cards[0].suit);
+    let straight_high = match ranks {
+        _ if groups.len() < 5 => None,
+        [14, 5, 4, 3, 2] => Some(5),
+        [high, .., low] if high - low == 4 => Some(high),
+        _ => None,
+    };
+    if let Some(high) = straight_high {
+        return (if is_flush { 8 } else { 4 }, [high, 0, 0, 0, 0]);
+    }
+
+    let category = match (groups.first().map_or(0, |g| g.0), groups.get(1).map_or(0, |g| g.0)) {
+        (4, _) => 7,
+        (3, 2) => 6,
+        _ if is_flush => 5,
+        (3, _) => 3,
+        (2, 2) => 2,
+        (2, _) => 1,
+        _ => 0,
+    };
+    (category, ranks)
+}
+
+/// All ways to pick `k` indices out of `0..n`, in ascending order
+fn index_combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if k > n {
+        return vec![];
+    }
+    let mut combos = Vec::new();
+    for first in 0..=(n - k) {
+        for rest in index_combinations(n - first - 1, k - 1) {
+            let mut combo = vec![first];
+            combo.extend(rest.into_iter().map(|i| i + first + 1));
+            combos.push(combo);
         }
-        false
     }
+    combos
 }
 
 // ============ BLACKJACK ============
@@ -859,9 +2102,37 @@ pub enum BlackjackAction {
     Double,
     Split,
     Insurance,
+    StandAll,
+    // Takes a 1:1 payout on a dealt blackjack when the dealer shows an ace
+    EvenMoney,
+}
+
+// Shoe size and the level at which it is rebuilt before a new round
+pub const BLACKJACK_DECKS: usize = 6;
+pub const BLACKJACK_RESHUFFLE_THRESHOLD: usize = 52;
+
+/// Opening bet and the bankroll it is drawn from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "BlackjackStakesInput")]
+pub struct BlackjackStakes {
+    pub bet: u64,
+    pub bankroll: u64,
+}
+
+impl BlackjackStakes {
+    pub fn is_valid(&self) -> bool {
+        0 < self.bet && self.bet <= self.bankroll
+    }
+}
+
+impl Default for BlackjackStakes {
+    fn default() -> BlackjackStakes {
+        BlackjackStakes { bet: 100, bankroll: 1000 }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, SimpleObject)]
+#[graphql(complex)]
 pub struct BlackjackGame {
     pub player_hands: Vec<Vec<Card>>,
     pub dealer_hand: Vec<Card>,
@@ -873,6 +2144,33 @@ pub struct BlackjackGame {
     pub is_game_over: bool,
     pub insurance_bet: Option<u64>,
     pub results: Vec<BlackjackResult>,
+    // One flag per hand, set when the hand came from splitting aces and is held to one card
+    pub split_aces: Vec<bool>,
+    pub max_splits: u32,
+    pub dealer_hits_soft_17: bool,
+    // Liberal rule: split aces may be hit like any other hand
+    pub hit_split_aces: bool,
+    pub seed: u64,
+    pub reshuffles: u32,
+    // Bankroll the table opened with, before the first bet
+    pub starting_chips: u64,
+    pub action_history: Vec<BlackjackActionRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct BlackjackActionRecord {
+    pub action: BlackjackAction,
+    pub hand_index: usize,
+    // Value of the acted-on hand once the action was applied
+    pub hand_value: u32,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct BlackjackHandValue {
+    pub value: u32,
+    pub is_soft: bool,
+    pub is_bust: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
@@ -892,7 +2190,7 @@ impl BlackjackGame {
         let player_hand = vec![deck.pop().unwrap(), deck.pop().unwrap()];
         let dealer_hand = vec![deck.pop().unwrap(), deck.pop().unwrap()];
 
-        BlackjackGame {
+        let mut game = BlackjackGame {
             player_hands: vec![player_hand],
             dealer_hand,
             deck,
@@ -903,13 +2201,103 @@ impl BlackjackGame {
             is_game_over: false,
             insurance_bet: None,
             results: vec![],
+            split_aces: vec![false],
+            max_splits: 3,
+            dealer_hits_soft_17: false,
+            hit_split_aces: false,
+            seed,
+            reshuffles: 0,
+            starting_chips: player_chips,
+            action_history: vec![],
+        };
+        game.settle_natural();
+        game
+    }
+
+    /// Re-deals the opening cards from a new seed before the player has acted
+    pub fn reseed(&mut self, seed: u64) -> Result<(), String> {
+        if !self.is_player_turn || self.player_hands.len() != 1 || self.player_hands[0].len() != 2 || self.insurance_bet.is_some() {
+            return Err("Hand already in play".to_string());
+        }
+
+        self.seed = seed;
+        self.deck = Self::create_shuffled_deck(seed);
+        self.player_hands = vec![vec![self.deck.pop().unwrap(), self.deck.pop().unwrap()]];
+        self.dealer_hand = vec![self.deck.pop().unwrap(), self.deck.pop().unwrap()];
+        self.settle_natural();
+        Ok(())
+    }
+
+    /// Deals a fresh round once the previous one is over, rebuilding the shoe if it runs low
+    pub fn start_new_round(&mut self, bet: u64) -> Result<(), String> {
+        if !self.is_game_over {
+            return Err("Round still in progress".to_string());
+        }
+        if bet == 0 || bet > self.player_chips {
+            return Err("Invalid bet".to_string());
+        }
+
+        if self.deck.len() < BLACKJACK_RESHUFFLE_THRESHOLD {
+            self.reshuffle();
+        }
+
+        self.player_chips -= bet;
+        let player_hand = vec![self.deck.pop().unwrap(), self.deck.pop().unwrap()];
+        let dealer_hand = vec![self.deck.pop().unwrap(), self.deck.pop().unwrap()];
+
+        self.player_hands = vec![player_hand];
+        self.dealer_hand = dealer_hand;
+        self.current_hand = 0;
+        self.bets = vec![bet];
+        self.is_player_turn = true;
+        self.is_game_over = false;
+        self.insurance_bet = None;
+        self.results = vec![];
+        self.split_aces = vec![false];
+        self.settle_natural();
+        Ok(())
+    }
+
+    /// A natural on the deal needs no decisions: after the dealer peeks it pays 3:2 or pushes.
+    /// A dealer ten is peeked under at once; against a dealer ace the player is left to choose
+    /// between insurance or even money and playing on
+    fn settle_natural(&mut self) {
+        if self.dealer_hand[0].rank != 14 && self.peek_dealer_blackjack() {
+            return;
+        }
+        let hand = &self.player_hands[0];
+        if hand.len() != 2 || self.calculate_hand_value(hand) != 21 || self.dealer_hand[0].rank == 14 {
+            return;
+        }
+        self.current_hand = self.player_hands.len();
+        self.is_player_turn = false;
+        self.resolve_game();
+    }
+
+    /// Dealer checks the hole card under an ace or ten before the first decision, ending the
+    /// round straight away on a blackjack
+    fn peek_dealer_blackjack(&mut self) -> bool {
+        let first_decision = self.player_hands.len() == 1 && self.player_hands[0].len() == 2;
+        let dealer_blackjack = self.dealer_hand.len() == 2 && self.calculate_hand_value(&self.dealer_hand) == 21;
+        if self.dealer_hand[0].rank < 10 || !first_decision || !dealer_blackjack {
+            return false;
         }
+        self.current_hand = self.player_hands.len();
+        self.is_player_turn = false;
+        self.resolve_game();
+        true
+    }
+
+    fn reshuffle(&mut self) {
+        // Derive each new shoe from the game seed so replays stay deterministic
+        self.reshuffles += 1;
+        let seed = self.seed.wrapping_add((self.reshuffles as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        self.deck = Self::create_shuffled_deck(seed);
     }
 
     fn create_shuffled_deck(seed: u64) -> Vec<Card> {
-        // Use 6 decks for blackjack
-        let mut deck = Vec::with_capacity(312);
-        for _ in 0..6 {
+        let mut deck = Vec::with_capacity(52 * BLACKJACK_DECKS);
+        for _ in 0..BLACKJACK_DECKS {
             for suit in [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades] {
                 for rank in 2..=14 {
                     deck.push(Card { rank, suit });
@@ -927,10 +2315,15 @@ impl BlackjackGame {
         deck
     }
 
-    pub fn make_action(&mut self, action: BlackjackAction) -> Result<GameOutcome, String> {
+    pub fn make_action(&mut self, action: BlackjackAction, timestamp: u64) -> Result<GameOutcome, String> {
         if !self.is_player_turn || self.is_game_over {
             return Err("Not player's turn".to_string());
         }
+        // Insurance and even money are settled before the peek; anything else waits on it
+        if !matches!(action, BlackjackAction::Insurance | BlackjackAction::EvenMoney) && self.peek_dealer_blackjack() {
+            return Ok(self.round_outcome());
+        }
+        let hand_index = self.current_hand;
 
         match action {
             BlackjackAction::Hit => {
@@ -975,6 +2368,10 @@ impl BlackjackGame {
                 if hand.len() != 2 || hand[0].rank != hand[1].rank {
                     return Err("Cannot split".to_string());
                 }
+                if self.player_hands.len() as u32 > self.max_splits {
+                    return Err("Split limit reached".to_string());
+                }
+                let is_aces = hand[0].rank == 14;
 
                 let bet = self.bets[self.current_hand];
                 if bet > self.player_chips {
@@ -993,57 +2390,130 @@ impl BlackjackGame {
                 if let Some(card) = self.deck.pop() {
                     self.player_hands.last_mut().unwrap().push(card);
                 }
+
+                // Split aces receive exactly one card each and stand, unless the table lets them be hit
+                let one_card = is_aces && !self.hit_split_aces;
+                self.split_aces.push(one_card);
+                if one_card {
+                    self.split_aces[self.current_hand] = true;
+                    self.advance_hand();
+                }
             }
             BlackjackAction::Insurance => {
                 if self.dealer_hand[0].rank != 14 {
                     return Err("Insurance only available when dealer shows Ace".to_string());
                 }
+                if self.insurance_bet.is_some() || self.player_hands.len() != 1 || self.player_hands[0].len() != 2 {
+                    return Err("Insurance only available before acting".to_string());
+                }
                 let insurance = self.bets[0] / 2;
                 if insurance > self.player_chips {
                     return Err("Insufficient chips for insurance".to_string());
                 }
                 self.player_chips -= insurance;
                 self.insurance_bet = Some(insurance);
+
+                // Dealer peeks: with blackjack the insurance pays 2:1 and the round ends
+                if self.calculate_hand_value(&self.dealer_hand) == 21 {
+                    self.player_chips += insurance * 3; // 2:1 payout plus the stake
+                    self.current_hand = self.player_hands.len();
+                    self.is_player_turn = false;
+                }
+            }
+            BlackjackAction::StandAll => {
+                // Stand every remaining hand and hand over to the dealer
+                self.current_hand = self.player_hands.len();
+                self.is_player_turn = false;
+            }
+            BlackjackAction::EvenMoney => {
+                let hand = &self.player_hands[0];
+                let natural = self.player_hands.len() == 1 && hand.len() == 2 && self.calculate_hand_value(hand) == 21;
+                if !natural || self.dealer_hand[0].rank != 14 {
+                    return Err("Even money is only offered on a blackjack against a dealer ace".to_string());
+                }
+                if self.insurance_bet.is_some() {
+                    return Err("Insurance already taken".to_string());
+                }
+                // Paid at once, whatever the dealer's hole card
+                self.player_chips += self.bets[0] * 2;
+                self.results.push(BlackjackResult::Win);
+                self.current_hand = self.player_hands.len();
+                self.is_player_turn = false;
+                self.is_game_over = true;
             }
         }
 
-        if !self.is_player_turn {
+        let hand_value = self.calculate_hand_value(&self.player_hands[hand_index]);
+        self.action_history.push(BlackjackActionRecord { action, hand_index, hand_value, timestamp });
+
+        if !self.is_player_turn && !self.is_game_over {
             self.play_dealer();
             self.resolve_game();
         }
 
-        if self.is_game_over {
-            // Calculate total winnings
-            let mut won = false;
-            for result in &self.results {
-                if matches!(result, BlackjackResult::Win | BlackjackResult::Blackjack) {
-                    won = true;
-                    break;
-                }
-            }
-            if won {
-                Ok(GameOutcome::Winner(Player::One))
-            } else if self.results.iter().all(|r| matches!(r, BlackjackResult::Push)) {
-                Ok(GameOutcome::Draw)
-            } else {
-                Ok(GameOutcome::Winner(Player::Two)) // House wins
+        Ok(self.round_outcome())
+    }
+
+    /// Result of the round so far; `InProgress` until it is over
+    pub fn round_outcome(&self) -> GameOutcome {
+        if !self.is_game_over {
+            return GameOutcome::InProgress;
+        }
+
+        // Calculate total winnings
+        let mut won = false;
+        for result in &self.results {
+            if matches!(result, BlackjackResult::Win | BlackjackResult::Blackjack) {
+                won = true;
+                break;
             }
+        }
+        if won {
+            GameOutcome::Winner(Player::One)
+        } else if self.results.iter().all(|r| matches!(r, BlackjackResult::Push)) {
+            GameOutcome::Draw
         } else {
-            Ok(GameOutcome::InProgress)
+            GameOutcome::Winner(Player::Two) // House wins
+        }
+    }
+
+    /// Why the finished round ended: a natural on either side, every hand busting, the dealer busting, or a
+    /// comparison of totals
+    pub fn end_reason(&self) -> GameEndReason {
+        let dealer_value = self.calculate_hand_value(&self.dealer_hand);
+        let dealer_natural = self.dealer_hand.len() == 2 && dealer_value == 21;
+        if dealer_natural || self.results.contains(&BlackjackResult::Blackjack) {
+            GameEndReason::Blackjack
+        } else if self.results.iter().all(|r| *r == BlackjackResult::Bust) {
+            GameEndReason::Bust
+        } else if dealer_value > 21 {
+            GameEndReason::DealerBust
+        } else {
+            GameEndReason::HandsCompared
         }
     }
 
     fn advance_hand(&mut self) {
         self.current_hand += 1;
+        // Split-ace hands are already complete, skip past them
+        while self.current_hand < self.player_hands.len() && self.split_aces[self.current_hand] {
+            self.current_hand += 1;
+        }
         if self.current_hand >= self.player_hands.len() {
             self.is_player_turn = false;
         }
     }
 
     fn play_dealer(&mut self) {
-        while self.calculate_hand_value(&self.dealer_hand) < 17 {
-            if let Some(card) = self.deck.pop() {
-                self.dealer_hand.push(card);
+        loop {
+            let (value, is_soft) = self.calculate_soft_hand_value(&self.dealer_hand);
+            let hits = value < 17 || (value == 17 && is_soft && self.dealer_hits_soft_17);
+            if !hits {
+                break;
+            }
+            match self.deck.pop() {
+                Some(card) => self.dealer_hand.push(card),
+                None => break,
             }
         }
     }
@@ -1052,13 +2522,8 @@ impl BlackjackGame {
         let dealer_value = self.calculate_hand_value(&self.dealer_hand);
         let dealer_bust = dealer_value > 21;
         let dealer_blackjack = dealer_value == 21 && self.dealer_hand.len() == 2;
-
-        // Handle insurance
-        if let Some(insurance) = self.insurance_bet {
-            if dealer_blackjack {
-                self.player_chips += insurance * 3; // 2:1 payout plus original bet
-            }
-        }
+        // A two-card 21 after a split is an ordinary 21, not a natural
+        let unsplit = self.player_hands.len() == 1;
 
         for (i, hand) in self.player_hands.iter().enumerate() {
             if i < self.results.len() {
@@ -1066,7 +2531,7 @@ impl BlackjackGame {
             }
 
             let player_value = self.calculate_hand_value(hand);
-            let player_blackjack = player_value == 21 && hand.len() == 2;
+            let player_blackjack = unsplit && player_value == 21 && hand.len() == 2;
 
             let result = if player_blackjack && !dealer_blackjack {
                 self.player_chips += (self.bets[i] as f64 * 2.5) as u64; // 3:2 payout
@@ -1093,6 +2558,11 @@ impl BlackjackGame {
     }
 
     fn calculate_hand_value(&self, hand: &[Card]) -> u32 {
+        self.calculate_soft_hand_value(hand).0
+    }
+
+    /// Returns the hand value and whether an ace is still counted as 11
+    fn calculate_soft_hand_value(&self, hand: &[Card]) -> (u32, bool) {
         let mut value = 0u32;
         let mut aces = 0u32;
 
@@ -1115,13 +2585,53 @@ impl BlackjackGame {
             aces -= 1;
         }
 
-        value
+        (value, aces > 0)
     }
 
     pub fn get_player_hand_value(&self, hand_idx: usize) -> u32 {
         self.calculate_hand_value(&self.player_hands[hand_idx])
     }
 
+    /// Table parameters, read before the first round is replaced
+    pub fn rules(&self) -> BlackjackRules {
+        BlackjackRules {
+            decks: BLACKJACK_DECKS as u32,
+            bet: self.bets[0],
+            bankroll: self.starting_chips,
+            blackjack_payout: "3:2".to_string(),
+            dealer_hits_soft_17: self.dealer_hits_soft_17,
+            max_splits: self.max_splits,
+            hit_split_aces: self.hit_split_aces,
+        }
+    }
+
+    /// True once the player has done anything beyond receiving the opening deal
+    pub fn has_player_acted(&self) -> bool {
+        self.player_hands.len() != 1
+            || self.player_hands[0].len() != 2
+            || self.insurance_bet.is_some()
+            || !self.is_player_turn
+            || self.is_game_over
+    }
+
+    pub fn hand_is_soft(&self, hand_idx: usize) -> bool {
+        self.calculate_soft_hand_value(&self.player_hands[hand_idx]).1
+    }
+
+    pub fn hand_values(&self) -> Vec<BlackjackHandValue> {
+        self.player_hands
+            .iter()
+            .map(|hand| {
+                let (value, is_soft) = self.calculate_soft_hand_value(hand);
+                BlackjackHandValue {
+                    value,
+                    is_soft,
+                    is_bust: value > 21,
+                }
+            })
+            .collect()
+    }
+
     pub fn get_dealer_visible_value(&self) -> u32 {
         if self.is_player_turn {
             // Only show first card value
@@ -1137,6 +2647,33 @@ impl BlackjackGame {
     }
 }
 
+#[ComplexObject]
+impl BlackjackGame {
+    /// Cards left in the shoe
+    async fn cards_remaining(&self) -> u32 {
+        self.deck.len() as u32
+    }
+}
+
+// ============ SHUFFLE RANDOMNESS ============
+
+/// Hex-encoded SHA-256 commitment to a shuffle seed
+pub fn seed_commitment(seed: u64) -> String {
+    hex::encode(Sha256::digest(seed.to_le_bytes()))
+}
+
+pub fn verify_seed_reveal(commitment: &str, seed: u64) -> bool {
+    seed_commitment(seed) == commitment.to_lowercase()
+}
+
+/// Combines every player's revealed seed so no single player controls the shuffle
+pub fn combine_seeds(reveals: &[u64]) -> u64 {
+    reveals.iter().fold(0, |acc, seed| acc ^ seed)
+}
+
+// How long a seeded table waits on its players' commitments and reveals before the holdouts can be claimed against
+pub const SEED_REVEAL_WINDOW_MICROS: u64 = 300_000_000;
+
 // ============ GAME STATE ============
 
 #[derive(Clone, Serialize, Deserialize, SimpleObject)]
@@ -1179,69 +2716,414 @@ impl Player {
             Player::Two => 1,
         }
     }
+
+    /// The player in a seat, for the first two seats of a table
+    pub fn from_seat(seat: usize) -> Option<Self> {
+        match seat {
+            0 => Some(Player::One),
+            1 => Some(Player::Two),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameOutcome {
     Winner(Player),
+    // A multi-way hand won by these seats; every other seat lost
+    SeatWinners(Vec<usize>),
     Draw,
     InProgress,
+    // The operation was refused, with the reason why
+    Rejected(String),
 }
 
-// ============ CLOCK ============
+impl GameOutcome {
+    pub fn rejected(reason: &str) -> Self {
+        GameOutcome::Rejected(reason.to_string())
+    }
+}
+
+// ============ EVENTS ============
+
+pub const GAME_COMPLETED_STREAM: &[u8] = b"game_completed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum GameEndReason {
+    Checkmate,
+    Stalemate,
+    FiftyMove,
+    Resignation,
+    Timeout,
+    DrawAgreed,
+    Repetition,
+    InsufficientMaterial,
+    Fold,
+    Showdown,
+    // Blackjack: the player drew a natural, every hand busted, the dealer busted, or the totals were compared
+    Blackjack,
+    Bust,
+    DealerBust,
+    HandsCompared,
+    AdminDecision,
+}
+
+/// Emitted on `GAME_COMPLETED_STREAM` whenever a game reaches a final result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameCompletedEvent {
+    pub game_id: String,
+    pub game_type: GameType,
+    pub winner: Option<Player>,
+    // Every seat that won, which is how a multi-way table reports its result; empty on a draw
+    pub winning_seats: Vec<usize>,
+    pub reason: GameEndReason,
+}
+
+// ============ GAME CONTROL ============
+
+/// Seats for a rematch: swapped so colors and the button alternate, or kept as they were
+pub fn rematch_seating<T: Copy>(seats: [T; 2], swap_colors: bool) -> [T; 2] {
+    if swap_colors { [seats[1], seats[0]] } else { seats }
+}
+
+// How long after creation a game with no moves may still be aborted
+pub const ABORT_WINDOW_MICROS: u64 = 60_000_000;
+
+// Minimum gap between state-creating operations from one account
+pub const MIN_ACTION_INTERVAL_MICROS: u64 = 1_000_000;
+
+// A cross-chain invite lapses if it has not been accepted this long after it was sent
+pub const INVITE_TTL_MICROS: u64 = 86_400_000_000;
+
+// ============ CHAT ============
+
+pub const CHAT_MAX_LEN: usize = 280;
+// Older messages are dropped once a game's chat exceeds this
+pub const CHAT_HISTORY_CAP: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct ChatMessage {
+    pub sender_name: String,
+    pub text: String,
+    pub timestamp: u64,
+}
+
+/// Appends a message, dropping the oldest ones beyond `CHAT_HISTORY_CAP`
+pub fn push_chat_message(chat: &mut Vec<ChatMessage>, message: ChatMessage) {
+    chat.push(message);
+    if chat.len() > CHAT_HISTORY_CAP {
+        let excess = chat.len() - CHAT_HISTORY_CAP;
+        chat.drain(..excess);
+    }
+}
+
+// ============ MODERATION ============
+
+// Longest reason a player report may give
+pub const REPORT_REASON_MAX_LEN: usize = 500;
+
+/// A complaint about a player in a shared game, kept for moderators to review
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerReport {
+    pub report_id: u64,
+    pub reporter: String,
+    pub target: String,
+    pub game_id: String,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+// ============ CLOCK ============
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
 pub struct Clock {
-    pub time_left: [TimeDelta; 2],
-    pub increment: TimeDelta,
+    // One entry per seat. With a time bank this is each seat's reserve, drawn on only once a turn outlasts `per_action`
+    pub time_left: Vec<TimeDelta>,
+    // Per-seat, so handicap games can give only one side an increment or delay
+    pub increment: Vec<TimeDelta>,
     pub current_turn_start: Timestamp,
-    pub block_delay: TimeDelta,
+    // Per-seat grace past a flag fall, covering the time a move spends waiting to be included in a block
+    pub block_delay: Vec<TimeDelta>,
+    pub per_action: Option<TimeDelta>,
 }
 
 impl Clock {
+    /// A clock for a two-seat game
     pub fn new(block_time: Timestamp, timeouts: &Timeouts) -> Self {
+        Self::with_seats(block_time, timeouts, 2)
+    }
+
+    /// A clock for `seats` seats; seats past the second take the second seat's increment and delay
+    pub fn with_seats(block_time: Timestamp, timeouts: &Timeouts, seats: usize) -> Self {
+        let time_left = match &timeouts.time_bank {
+            Some(bank) => bank.reserve,
+            None => timeouts.start_time,
+        };
+        let per_seat = |settings: &[TimeDelta; 2]| -> Vec<TimeDelta> { (0..seats).map(|seat| settings[seat.min(1)]).collect() };
         Self {
-            time_left: [timeouts.start_time, timeouts.start_time],
-            increment: timeouts.increment,
+            time_left: vec![time_left; seats],
+            increment: per_seat(&timeouts.increment),
             current_turn_start: block_time,
-            block_delay: timeouts.block_delay,
+            block_delay: per_seat(&timeouts.block_delay),
+            per_action: timeouts.time_bank.as_ref().map(|bank| bank.per_action),
         }
     }
 
-    pub fn make_move(&mut self, block_time: Timestamp, player: Player) {
-        let duration = block_time.delta_since(self.current_turn_start);
-        let i = player.index();
-        if self.time_left[i] >= duration {
-            self.time_left[i] = self.time_left[i]
-                .saturating_sub(duration)
-                .saturating_add(self.increment);
+    pub fn make_move(&mut self, block_time: Timestamp, seat: usize) {
+        let duration = self.charged(block_time.delta_since(self.current_turn_start));
+        let delay = self.seat_block_delay(seat);
+        if let (Some(left), Some(&increment)) = (self.time_left.get_mut(seat), self.increment.get(seat)) {
+            if left.saturating_add(delay) >= duration {
+                *left = left.saturating_sub(duration).saturating_add(increment);
+            }
         }
         self.current_turn_start = block_time;
     }
 
-    pub fn timed_out(&self, block_time: Timestamp, player: Player) -> bool {
-        self.time_left[player.index()] < block_time.delta_since(self.current_turn_start)
+    pub fn add_time(&mut self, seat: usize, extra: TimeDelta) {
+        if let Some(left) = self.time_left.get_mut(seat) {
+            *left = left.saturating_add(extra);
+        }
+    }
+
+    pub fn timed_out(&self, block_time: Timestamp, seat: usize) -> bool {
+        self.seat_time_left(seat).saturating_add(self.seat_block_delay(seat)) < self.charged(block_time.delta_since(self.current_turn_start))
+    }
+
+    /// The moment `seat` flags if their current turn runs on without a move
+    pub fn turn_deadline(&self, seat: usize) -> Timestamp {
+        let free = self.per_action.unwrap_or(TimeDelta::from_micros(0));
+        self.current_turn_start
+            .saturating_add(free)
+            .saturating_add(self.seat_time_left(seat))
+            .saturating_add(self.seat_block_delay(seat))
+    }
+
+    fn seat_time_left(&self, seat: usize) -> TimeDelta {
+        self.time_left.get(seat).copied().unwrap_or(TimeDelta::from_micros(0))
+    }
+
+    fn seat_block_delay(&self, seat: usize) -> TimeDelta {
+        self.block_delay.get(seat).copied().unwrap_or(TimeDelta::from_micros(0))
+    }
+
+    // Part of a turn's length that comes off `time_left`: all of it, or whatever overran the per-action budget
+    fn charged(&self, duration: TimeDelta) -> TimeDelta {
+        match self.per_action {
+            Some(budget) => duration.saturating_sub(budget),
+            None => duration,
+        }
     }
 }
 
+/// Card-game timing: a fresh budget every action, backed by a reserve that only overruns eat into
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "TimeBankInput")]
+pub struct TimeBank {
+    pub per_action: TimeDelta,
+    pub reserve: TimeDelta,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, InputObject)]
 #[graphql(input_name = "TimeoutsInput")]
 pub struct Timeouts {
     pub start_time: TimeDelta,
-    pub increment: TimeDelta,
-    pub block_delay: TimeDelta,
+    pub increment: [TimeDelta; 2],
+    pub block_delay: [TimeDelta; 2],
+    // Replaces `start_time` as the clock's allowance when set
+    pub time_bank: Option<TimeBank>,
+}
+
+impl Timeouts {
+    /// The same increment and delay for both players
+    pub fn symmetric(start_time: TimeDelta, increment: TimeDelta, block_delay: TimeDelta) -> Timeouts {
+        Timeouts {
+            start_time,
+            increment: [increment; 2],
+            block_delay: [block_delay; 2],
+            time_bank: None,
+        }
+    }
+
+    /// A per-action budget backed by a reserve for each player, with no increment
+    pub fn with_time_bank(per_action: TimeDelta, reserve: TimeDelta, block_delay: TimeDelta) -> Timeouts {
+        Timeouts {
+            start_time: reserve,
+            increment: [TimeDelta::from_micros(0); 2],
+            block_delay: [block_delay; 2],
+            time_bank: Some(TimeBank { per_action, reserve }),
+        }
+    }
 }
 
 impl Default for Timeouts {
     fn default() -> Timeouts {
-        Timeouts {
-            start_time: TimeDelta::from_secs(300),
-            increment: TimeDelta::from_secs(10),
-            block_delay: TimeDelta::from_secs(5),
+        Timeouts::symmetric(TimeDelta::from_secs(300), TimeDelta::from_secs(10), TimeDelta::from_secs(5))
+    }
+}
+
+// ============ PLATFORM DEFAULTS ============
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, InputObject)]
+#[graphql(input_name = "GameDefaultsInput")]
+pub struct GameDefaults {
+    pub starting_elo: u32,
+    // Unanswered draw offers lapse after this long
+    pub draw_offer_expiry_secs: u64,
+    // Card tables left without an action this long can be claimed, whatever the clock says
+    pub inactivity_timeout_secs: u64,
+    // Bounds a chess rating update can't cross
+    pub elo_floor: u32,
+    pub elo_ceiling: u32,
+}
+
+impl Default for GameDefaults {
+    fn default() -> GameDefaults {
+        GameDefaults {
+            starting_elo: 1200,
+            draw_offer_expiry_secs: 120,
+            inactivity_timeout_secs: 60,
+            elo_floor: 100,
+            elo_ceiling: 3000,
         }
     }
 }
 
+impl GameDefaults {
+    /// `rating` moved by `delta`, held within the configured floor and ceiling
+    pub fn apply_elo_delta(&self, rating: u32, delta: i32) -> u32 {
+        let updated = (rating as i64 + delta as i64).clamp(0, u32::MAX as i64) as u32;
+        updated.clamp(self.elo_floor, self.elo_ceiling.max(self.elo_floor))
+    }
+
+    /// True once a draw offer made at `offered_at` has lapsed by `now`
+    pub fn draw_offer_expired(&self, offered_at: u64, now: u64) -> bool {
+        now.saturating_sub(offered_at) > self.draw_offer_expiry_secs.saturating_mul(1_000_000)
+    }
+
+    /// True once a game last played at `last_move_at` has sat idle past the inactivity timeout
+    pub fn inactivity_expired(&self, last_move_at: u64, now: u64) -> bool {
+        now.saturating_sub(last_move_at) > self.inactivity_timeout_secs.saturating_mul(1_000_000)
+    }
+}
+
+// ============ GAME RULES ============
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject)]
+pub struct PokerRules {
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub buy_in: u64,
+    pub variant: PokerVariant,
+    pub hole_cards: u8,
+    pub betting_mode: BettingMode,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject)]
+pub struct BlackjackRules {
+    pub decks: u32,
+    pub bet: u64,
+    pub bankroll: u64,
+    pub blackjack_payout: String,
+    pub dealer_hits_soft_17: bool,
+    pub max_splits: u32,
+    pub hit_split_aces: bool,
+}
+
+/// Parameters a game was created with, kept for reproducibility and disputes
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject)]
+pub struct GameRules {
+    pub time_control: Timeouts,
+    pub rated: bool,
+    // When false, move-hint queries are refused for this game
+    pub allow_hints: bool,
+    pub poker: Option<PokerRules>,
+    pub blackjack: Option<BlackjackRules>,
+}
+
+// ============ ELO ============
+
+/// Expected score of a player rated `rating` against `opponent`
+pub fn expected_score(rating: u32, opponent: u32) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent as f64 - rating as f64) / 400.0))
+}
+
+pub fn k_factor(rating: u32) -> f64 {
+    if rating > 2100 { 16.0 } else { 32.0 }
+}
+
+/// Rating change for a player scoring `score` (1.0 win, 0.5 draw, 0.0 loss)
+pub fn elo_delta(rating: u32, opponent: u32, score: f64) -> i32 {
+    (k_factor(rating) * (score - expected_score(rating, opponent))).round() as i32
+}
+
+// Bounds on the provisional rating shift from a bot record, reached at full volume
+pub const BOT_SEED_MAX_ADJUSTMENT: i32 = 200;
+pub const BOT_SEED_FULL_WEIGHT_GAMES: u32 = 20;
+
+/// Provisional Elo shift from a bot record: the win/loss margin, scaled by volume up to `BOT_SEED_FULL_WEIGHT_GAMES`
+pub fn bot_elo_adjustment(wins: u32, losses: u32) -> i32 {
+    let games = wins + losses;
+    if games == 0 {
+        return 0;
+    }
+    let margin = (wins as f64 - losses as f64) / games as f64;
+    let weight = games.min(BOT_SEED_FULL_WEIGHT_GAMES) as f64 / BOT_SEED_FULL_WEIGHT_GAMES as f64;
+    (margin * weight * BOT_SEED_MAX_ADJUSTMENT as f64).round() as i32
+}
+
+// ============ HEAD TO HEAD ============
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct HeadToHeadRecord {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl HeadToHeadRecord {
+    pub fn from_tuple((wins, losses, draws): (u32, u32, u32)) -> Self {
+        HeadToHeadRecord { wins, losses, draws }
+    }
+
+    /// The same record seen from the other player's side
+    pub fn reversed(self) -> Self {
+        HeadToHeadRecord {
+            wins: self.losses,
+            losses: self.wins,
+            draws: self.draws,
+        }
+    }
+}
+
+/// Orders a pair so both lookups hit one entry; the flag is set when `a` is second in the key
+pub fn head_to_head_key(a: AccountOwner, b: AccountOwner) -> ((AccountOwner, AccountOwner), bool) {
+    if a <= b { ((a, b), false) } else { ((b, a), true) }
+}
+
+/// Adds a result to a `(wins, losses, draws)` record kept from the key's first player's side
+pub fn record_head_to_head(record: &mut (u32, u32, u32), first_player_won: Option<bool>) {
+    match first_player_won {
+        Some(true) => record.0 += 1,
+        Some(false) => record.1 += 1,
+        None => record.2 += 1,
+    }
+}
+
+// ============ MATCHMAKING ============
+
+pub const MATCHMAKING_ELO_WINDOW: u32 = 200;
+
+/// Index of the closest-rated queued player within the matchmaking window
+pub fn find_matchmaking_opponent(rating: u32, queue_ratings: &[u32]) -> Option<usize> {
+    queue_ratings
+        .iter()
+        .enumerate()
+        .filter(|(_, &r)| r.abs_diff(rating) <= MATCHMAKING_ELO_WINDOW)
+        .min_by_key(|(_, &r)| r.abs_diff(rating))
+        .map(|(i, _)| i)
+}
+
 // ============ LEADERBOARD ============
 
 #[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
@@ -1254,11 +3136,444 @@ pub struct LeaderboardEntry {
     pub win_rate: f64,
     pub elo: u32,
     pub total_games: u32,
+    // Rank held when the current period began; 0 for entries new since then
+    pub previous_rank: u32,
+    // Positive when the entry moved up since the start of the period
+    pub rank_delta: i32,
+}
+
+// Rank movement is measured against the board as it stood when the period began
+pub const LEADERBOARD_PERIOD_MICROS: u64 = 86_400_000_000;
+
+/// Re-ranks the board at `timestamp`. When a new period has begun since `snapshot_period`, the current ranks are
+/// first kept as the baseline movement is reported against. Returns the period the baseline now belongs to
+pub fn rank_leaderboard_for_period(entries: &mut [LeaderboardEntry], snapshot_period: u64, timestamp: u64) -> u64 {
+    let period = timestamp / LEADERBOARD_PERIOD_MICROS;
+    if period != snapshot_period {
+        for entry in entries.iter_mut() {
+            entry.previous_rank = entry.rank;
+        }
+    }
+    rank_leaderboard(entries);
+    period
+}
+
+/// Sorts entries by win rate (ties broken by games played) and reassigns ranks,
+/// reporting movement against each entry's period baseline
+pub fn rank_leaderboard(entries: &mut [LeaderboardEntry]) {
+    entries.sort_by(|a, b| {
+        let a_rate = if a.total_games > 0 { a.wins as f64 / a.total_games as f64 } else { 0.0 };
+        let b_rate = if b.total_games > 0 { b.wins as f64 / b.total_games as f64 } else { 0.0 };
+        match b_rate.partial_cmp(&a_rate) {
+            Some(std::cmp::Ordering::Equal) => b.total_games.cmp(&a.total_games),
+            Some(ord) => ord,
+            None => std::cmp::Ordering::Equal,
+        }
+    });
+
+    for (i, entry) in entries.iter_mut().enumerate() {
+        let new_rank = (i + 1) as u32;
+        entry.rank_delta = if entry.previous_rank == 0 { 0 } else { entry.previous_rank as i32 - new_rank as i32 };
+        entry.rank = new_rank;
+    }
+}
+
+// Most players a group leaderboard can be asked for at once
+pub const MAX_GROUP_LEADERBOARD_SIZE: usize = 50;
+
+/// The entries belonging to `eth_addresses`, ranked among themselves the same way as the main board.
+/// Only the first `MAX_GROUP_LEADERBOARD_SIZE` addresses are considered
+pub fn group_leaderboard(entries: &[LeaderboardEntry], eth_addresses: &[String]) -> Vec<LeaderboardEntry> {
+    let group: Vec<String> = eth_addresses
+        .iter()
+        .take(MAX_GROUP_LEADERBOARD_SIZE)
+        .map(|address| address.to_lowercase())
+        .collect();
+    let mut members: Vec<LeaderboardEntry> = entries
+        .iter()
+        .filter(|entry| group.contains(&entry.eth_address.to_lowercase()))
+        .map(|entry| LeaderboardEntry { rank: 0, previous_rank: 0, ..entry.clone() })
+        .collect();
+    rank_leaderboard(&mut members);
+    members
+}
+
+/// Returns the `limit` items starting at `offset`, clamped to the slice bounds
+pub fn page<T: Clone>(items: &[T], offset: Option<i32>, limit: i32) -> Vec<T> {
+    let start = (offset.unwrap_or(0).max(0) as usize).min(items.len());
+    let end = start.saturating_add(limit.max(0) as usize).min(items.len());
+    items[start..end].to_vec()
+}
+
+// ============ STATE AUDIT ============
+
+/// Cross-index inconsistencies found by the `state_audit` query
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct StateAuditReport {
+    // Usernames whose owner has no profile, or a profile under another name
+    pub orphaned_usernames: Vec<String>,
+    // Leaderboard ETH addresses with no registered owner
+    pub orphaned_leaderboard_entries: Vec<String>,
+    // Games with a seated player who has no profile
+    pub games_with_missing_players: Vec<String>,
+    // Active lobby ids whose lobby is missing or no longer open
+    pub stale_lobby_ids: Vec<String>,
+}
+
+impl StateAuditReport {
+    pub fn is_clean(&self) -> bool {
+        self.orphaned_usernames.is_empty()
+            && self.orphaned_leaderboard_entries.is_empty()
+            && self.games_with_missing_players.is_empty()
+            && self.stale_lobby_ids.is_empty()
+    }
+
+    /// Flags a username mapping unless it resolves to a profile carrying that name, or one merged into it
+    pub fn check_username(&mut self, username: &str, profile: Option<&UserProfile>) {
+        let matches = profile.is_some_and(|p| p.answers_to(username));
+        if !matches {
+            self.orphaned_usernames.push(username.to_string());
+        }
+    }
+
+    pub fn check_leaderboard_entry(&mut self, entry: &LeaderboardEntry, registered: bool) {
+        if !registered {
+            self.orphaned_leaderboard_entries.push(entry.eth_address.clone());
+        }
+    }
+
+    pub fn check_game_players(&mut self, game_id: &str, all_registered: bool) {
+        if !all_registered {
+            self.games_with_missing_players.push(game_id.to_string());
+        }
+    }
+
+    pub fn check_active_lobby(&mut self, lobby_id: &str, lobby: Option<&GameLobby>) {
+        if !lobby.is_some_and(|l| l.status == LobbyStatus::Open) {
+            self.stale_lobby_ids.push(lobby_id.to_string());
+        }
+    }
+}
+
+// ============ ACHIEVEMENTS ============
+
+// Winning streak, and the most moves a mating side may take, for the matching achievements
+pub const STREAK_ACHIEVEMENT_LENGTH: u32 = 10;
+pub const QUICK_CHECKMATE_MOVES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum AchievementKind {
+    FirstWin,
+    TenGameStreak,
+    QuickCheckmate,
+    RoyalFlush,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct Achievement {
+    pub kind: AchievementKind,
+    pub unlocked_at: u64,
+    // Game that earned it, when there is one on record
+    pub game_id: Option<String>,
+}
+
+/// Adds `kind` unless it is already unlocked; returns true if it was new
+pub fn unlock_achievement(achievements: &mut Vec<Achievement>, kind: AchievementKind, game_id: Option<String>, timestamp: u64) -> bool {
+    if achievements.iter().any(|a| a.kind == kind) {
+        return false;
+    }
+    achievements.push(Achievement {
+        kind,
+        unlocked_at: timestamp,
+        game_id,
+    });
+    true
+}
+
+/// True when the board ended in mate before the winner made `QUICK_CHECKMATE_MOVES` moves
+pub fn is_quick_checkmate(board: &ChessBoard) -> bool {
+    let winner_moves = board.move_history.len().div_ceil(2);
+    board.is_checkmate && winner_moves < QUICK_CHECKMATE_MOVES
+}
+
+// ============ TOURNAMENTS ============
+
+// Single-elimination fields must be a power of two within these bounds
+pub const MIN_TOURNAMENT_SIZE: u32 = 2;
+pub const MAX_TOURNAMENT_SIZE: u32 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum TournamentStatus {
+    Registering,
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct TournamentPairing {
+    pub game_id: String,
+    pub players: Vec<String>,
+    pub winner: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Tournament {
+    pub tournament_id: String,
+    pub game_type: GameType,
+    pub creator: String,
+    pub max_participants: u32,
+    // In seeding order; adjacent entries meet in the first round
+    pub participants: Vec<String>,
+    pub rounds: Vec<Vec<TournamentPairing>>,
+    pub status: TournamentStatus,
+    pub champion: Option<String>,
+    pub created_at: u64,
+    // Whether bracket games offer move hints
+    pub allow_hints: bool,
+}
+
+/// Lobby-page view of a tournament that has not finished yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct TournamentSummary {
+    pub tournament_id: String,
+    pub game_type: GameType,
+    pub status: TournamentStatus,
+    pub registered_players: u32,
+    pub max_players: u32,
+    // Zero while registration is open
+    pub current_round: u32,
+}
+
+/// True for bracket sizes a single-elimination tournament can seat without byes
+pub fn is_valid_bracket_size(size: u32) -> bool {
+    (MIN_TOURNAMENT_SIZE..=MAX_TOURNAMENT_SIZE).contains(&size) && size.is_power_of_two()
+}
+
+impl Tournament {
+    pub fn new(tournament_id: String, game_type: GameType, creator: String, max_participants: u32, timestamp: u64) -> Self {
+        Tournament {
+            tournament_id,
+            game_type,
+            creator,
+            max_participants,
+            participants: vec![],
+            rounds: vec![],
+            status: TournamentStatus::Registering,
+            champion: None,
+            created_at: timestamp,
+            allow_hints: true,
+        }
+    }
+
+    pub fn join(&mut self, participant: String) -> Result<(), String> {
+        if self.status != TournamentStatus::Registering {
+            return Err("Registration is closed".to_string());
+        }
+        if self.is_full() {
+            return Err("Tournament is full".to_string());
+        }
+        if self.participants.contains(&participant) {
+            return Err("Already registered".to_string());
+        }
+        self.participants.push(participant);
+        Ok(())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.participants.len() as u32 >= self.max_participants
+    }
+
+    pub fn summary(&self) -> TournamentSummary {
+        TournamentSummary {
+            tournament_id: self.tournament_id.clone(),
+            game_type: self.game_type,
+            status: self.status,
+            registered_players: self.participants.len() as u32,
+            max_players: self.max_participants,
+            current_round: self.rounds.len() as u32,
+        }
+    }
+
+    /// Players still in the running: everyone before the first round, then the last round's winners
+    pub fn advancing(&self) -> Vec<String> {
+        match self.rounds.last() {
+            None => self.participants.clone(),
+            Some(round) => round.iter().filter_map(|p| p.winner.clone()).collect(),
+        }
+    }
+
+    /// Pairs for the next round, or empty while a round is still being played or once a champion is known
+    pub fn next_round_pairs(&self) -> Vec<(String, String)> {
+        if self.status == TournamentStatus::Completed || !self.is_full() {
+            return vec![];
+        }
+        if self.rounds.last().is_some_and(|round| round.iter().any(|p| p.winner.is_none())) {
+            return vec![];
+        }
+        let advancing = self.advancing();
+        if advancing.len() < 2 {
+            return vec![];
+        }
+        advancing
+            .chunks(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect()
+    }
+
+    pub fn add_round(&mut self, pairings: Vec<TournamentPairing>) {
+        self.rounds.push(pairings);
+        self.status = TournamentStatus::InProgress;
+    }
+
+    /// Records the winner of a current-round game; returns true if that completed the round
+    pub fn record_result(&mut self, game_id: &str, winner: String) -> bool {
+        let round = match self.rounds.last_mut() {
+            Some(r) => r,
+            None => return false,
+        };
+        let pairing = match round.iter_mut().find(|p| p.game_id == game_id) {
+            Some(p) if p.winner.is_none() && p.players.contains(&winner) => p,
+            _ => return false,
+        };
+        pairing.winner = Some(winner.clone());
+
+        if round.iter().any(|p| p.winner.is_none()) {
+            return false;
+        }
+        if round.len() == 1 {
+            self.champion = Some(winner);
+            self.status = TournamentStatus::Completed;
+        }
+        true
+    }
+}
+
+// ============ MATCHES ============
+
+// Longest "first to N" series a match may be set up as
+pub const MAX_MATCH_BEST_OF: u32 = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum MatchStatus {
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct MatchState {
+    pub match_id: String,
+    pub game_type: GameType,
+    // Seated in this order for the first game, then alternating
+    pub players: Vec<String>,
+    pub best_of: u32,
+    // Games won by each entry of `players`
+    pub scores: Vec<u32>,
+    pub draws: u32,
+    pub game_ids: Vec<String>,
+    pub status: MatchStatus,
+    pub winner: Option<String>,
+    pub created_at: u64,
+}
+
+/// True for an odd series length, so a majority always exists
+pub fn is_valid_best_of(best_of: u32) -> bool {
+    (1..=MAX_MATCH_BEST_OF).contains(&best_of) && best_of % 2 == 1
+}
+
+impl MatchState {
+    pub fn new(match_id: String, game_type: GameType, players: Vec<String>, best_of: u32, timestamp: u64) -> Self {
+        MatchState {
+            match_id,
+            game_type,
+            scores: vec![0; players.len()],
+            players,
+            best_of,
+            draws: 0,
+            game_ids: vec![],
+            status: MatchStatus::InProgress,
+            winner: None,
+            created_at: timestamp,
+        }
+    }
+
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Index into `players` of who takes the first seat in the next game
+    pub fn next_first_seat(&self) -> usize {
+        self.game_ids.len() % 2
+    }
+
+    /// Match players in seat order for the next game
+    pub fn next_seating(&self) -> Vec<String> {
+        let mut seating = self.players.clone();
+        seating.rotate_left(self.next_first_seat());
+        seating
+    }
+
+    /// Scores the match's current game from the winning seat; returns true if that decided the match
+    pub fn record_result(&mut self, game_id: &str, winner: Option<Player>) -> bool {
+        if self.status == MatchStatus::Completed || self.game_ids.last().map(String::as_str) != Some(game_id) {
+            return false;
+        }
+        let winner = match winner {
+            Some(seat) => seat,
+            None => {
+                self.draws += 1;
+                return false;
+            }
+        };
+        // Seats swap every game, so map the winning seat back to the match player
+        let first_seat = (self.game_ids.len() - 1) % 2;
+        let idx = (winner.index() + first_seat) % self.players.len();
+        self.scores[idx] += 1;
+
+        if self.scores[idx] >= self.wins_needed() {
+            self.winner = Some(self.players[idx].clone());
+            self.status = MatchStatus::Completed;
+            return true;
+        }
+        false
+    }
+}
+
+// ============ MESSAGES ============
+
+/// Sent between chains running the platform, always with the sender's authentication
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Message {
+    // Asks the invitee's chain to host a game against the inviter under an id the inviter's chain picked
+    GameInvite {
+        game_id: String,
+        game_type: GameType,
+        timeouts: Timeouts,
+        inviter_name: String,
+        invitee: AccountOwner,
+        expires_at: u64,
+    },
+    // Tells the inviter's chain its invite was accepted, or turned down
+    AcceptInvite {
+        game_id: String,
+    },
+    DeclineInvite {
+        game_id: String,
+    },
+    // An operation the inviter signed on their own chain, for the game the host chain keeps
+    RelayedOperation {
+        operation: Operation,
+    },
+    // Tells the inviter's chain how a hosted game ended
+    InviteResult {
+        game_id: String,
+        winner: Option<Player>,
+        reason: GameEndReason,
+    },
 }
 
 // ============ OPERATIONS ============
 
-#[derive(Debug, Deserialize, Serialize, GraphQLMutationRoot)]
+#[derive(Debug, Clone, Deserialize, Serialize, GraphQLMutationRoot)]
 pub enum Operation {
     // User Management
     RegisterUser {
@@ -1270,6 +3585,12 @@ pub enum Operation {
         username: Option<String>,
         avatar_url: Option<String>,
     },
+    // Flags the player as open to a game, optionally of one type, with a short status line
+    SetAvailability {
+        looking_for_game: bool,
+        game_type: Option<GameType>,
+        message: Option<String>,
+    },
 
     // Lobby Management
     CreateLobby {
@@ -1278,6 +3599,14 @@ pub enum Operation {
         is_public: bool,
         password: Option<String>,
         time_control: u64,
+        max_players: u32,
+        wager: Option<Amount>,
+        // Defaults to the creator playing White
+        creator_color: Option<ColorChoice>,
+        // Poker only; defaults to 1000 chips at 10/20
+        poker_stakes: Option<PokerStakes>,
+        // Defaults to allowing hints
+        allow_hints: Option<bool>,
     },
     JoinLobby {
         lobby_id: String,
@@ -1286,13 +3615,35 @@ pub enum Operation {
     CancelLobby {
         lobby_id: String,
     },
+    // Lets the invitee see the lobby and join it without the password
+    InviteToLobby {
+        lobby_id: String,
+        invitee: AccountOwner,
+    },
+    PruneLobbies,
 
     // Game Operations
     CreateGame {
         game_type: GameType,
         game_mode: GameMode,
         opponent: Option<AccountOwner>,
+        // Seat the creator takes against a real opponent; Random flips a coin from the game seed
+        creator_color: Option<ColorChoice>,
         timeouts: Option<Timeouts>,
+        allow_hints: Option<bool>,
+        // Poker only; defaults to no-limit
+        betting_mode: Option<BettingMode>,
+        // Poker only; defaults to 1000 chips at 10/20
+        poker_stakes: Option<PokerStakes>,
+        // Blackjack only; defaults to the standard one-card rule for split aces
+        hit_split_aces: Option<bool>,
+        // Blackjack only; defaults to a 100 bet from a 1000 bankroll
+        blackjack_stakes: Option<BlackjackStakes>,
+        // Stake escrowed from each player; the opponent funds theirs with `FundWager`
+        wager: Option<Amount>,
+    },
+    FundWager {
+        game_id: String,
     },
 
     // Chess Operations
@@ -1319,6 +3670,8 @@ pub enum Operation {
     // Game Control
     ResignGame {
         game_id: String,
+        // Must be true; an unconfirmed resign is ignored so a misclick can't end the game
+        confirm: bool,
     },
     OfferDraw {
         game_id: String,
@@ -1326,9 +3679,79 @@ pub enum Operation {
     AcceptDraw {
         game_id: String,
     },
+    DeclineDraw {
+        game_id: String,
+    },
+    // Chess only; ends the game as a draw once fifty moves pass without a capture or pawn move
+    ClaimFiftyMoveRule {
+        game_id: String,
+    },
+    // Friendly chess games only; the opponent accepts to undo the last move
+    RequestTakeback {
+        game_id: String,
+    },
+    AcceptTakeback {
+        game_id: String,
+    },
     ClaimTimeout {
         game_id: String,
     },
+    AbortGame {
+        game_id: String,
+    },
+    // The challenger calls off a staked challenge the opponent has not funded
+    WithdrawChallenge {
+        game_id: String,
+    },
+    // Invites a player on another chain; the game is hosted on their chain, and the inviter's moves in it are
+    // relayed there from this one
+    SendGameInvite {
+        target_chain: ChainId,
+        invitee: AccountOwner,
+        game_type: GameType,
+        timeouts: Option<Timeouts>,
+    },
+    // The invitee starts, or turns down, a game created from a cross-chain invite
+    AcceptGameInvite {
+        game_id: String,
+    },
+    DeclineGameInvite {
+        game_id: String,
+    },
+    SpectateGame {
+        game_id: String,
+    },
+    SendChatMessage {
+        game_id: String,
+        text: String,
+    },
+    // Flags a player from a game the reporter also played in, for moderation review
+    ReportPlayer {
+        game_id: String,
+        target: AccountOwner,
+        reason: String,
+    },
+
+    RequestRematch {
+        game_id: String,
+        // False keeps the seating; seats swap unless both players ask to keep them
+        swap_colors: bool,
+    },
+    // Friendly poker: a busted seat buys back in and the table deals on
+    PokerRebuy {
+        game_id: String,
+        amount: u64,
+    },
+
+    // Commit-reveal shuffle for card games
+    CommitSeed {
+        game_id: String,
+        commitment: String,
+    },
+    RevealSeed {
+        game_id: String,
+        seed: u64,
+    },
 
     // Record bot game result
     RecordBotGame {
@@ -1337,6 +3760,69 @@ pub enum Operation {
         moves: u32,
         eth_address: String,
     },
+
+    // Matchmaking
+    JoinMatchmaking {
+        game_type: GameType,
+    },
+    LeaveMatchmaking {
+        game_type: GameType,
+    },
+
+    // Removes the signer's profile; refused while they have a game in progress
+    DeleteAccount,
+    // Blocked accounts can't join the blocker's lobbies, be matched with them, or challenge them
+    BlockPlayer {
+        target: AccountOwner,
+    },
+    UnblockPlayer {
+        target: AccountOwner,
+    },
+    // One-time provisional chess rating from bot results, before any rated game
+    SeedEloFromBots,
+
+    // Tournaments
+    CreateTournament {
+        game_type: GameType,
+        max_participants: u32,
+        // Applies to every bracket game; defaults to allowing hints
+        allow_hints: Option<bool>,
+    },
+    JoinTournament {
+        tournament_id: String,
+    },
+
+    // Matches: a series of games against one opponent, first to a majority of `best_of`
+    CreateMatch {
+        opponent: AccountOwner,
+        game_type: GameType,
+        best_of: u32,
+    },
+
+    // Admin
+    UpdateGameDefaults {
+        defaults: GameDefaults,
+    },
+    // Data repair for duplicate registrations
+    MergeAccounts {
+        keep_owner: AccountOwner,
+        merge_owner: AccountOwner,
+    },
+    // Moderation recovery for stuck games, naming the winning seat; `None` records a draw
+    ForceResolve {
+        game_id: String,
+        winner_seat: Option<u32>,
+    },
+    // Account allowed to adjust clocks besides the admin; `None` removes it
+    SetArbiter {
+        arbiter: Option<AccountOwner>,
+    },
+    // Dispute resolution: add `seconds` to one seat's clock (admin or arbiter)
+    AddTime {
+        game_id: String,
+        seat: u32,
+        seconds: u64,
+    },
 }
 
 impl ContractAbi for GamePlatformAbi {
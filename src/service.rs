@@ -7,17 +7,31 @@ use std::sync::Arc;
 use async_graphql::{EmptySubscription, Object, Request, Response, Schema};
 use linera_sdk::{
     abi::WithServiceAbi,
-    linera_base_types::{AccountOwner, TimeDelta},
+    linera_base_types::{AccountOwner, Amount, ChainId, TimeDelta, Timestamp},
     views::View,
     Service, ServiceRuntime,
 };
 
-use self::state::{FullGameState, GamePlatformState, GameInfo, PlayerStats};
+use self::state::{
+    CompletedGameSummary, FullGameState, GamePlatformState, GameInfo, PlatformStats, PlayerStats, PlayerStatsSummary,
+    RecentPerformance, SentInvite, UserIdentity,
+};
 use game_platform::{
-    BlackjackGame, ChessBoard, Clock, GameLobby, GameMode, GameStatus, GameType,
-    LeaderboardEntry, LobbyStatus, Operation, Player, PokerGame, Timeouts, UserProfile,
+    availability_expired, group_leaderboard, head_to_head_key, page, validate_username,
+    win_probability, Achievement, BettingMode, BlackjackActionRecord, BlackjackGame,
+    BlackjackHandValue, BlackjackStakes, BoardCard, ChatMessage, ChessBoard, ChessCapturedValue,
+    ChessMaterial, Clock, ColorChoice, DrawCountdown, GameDefaults, GameDrawStatus, GameLobby,
+    GameMode, GameRules, GameStatus, GameType, HeadToHeadRecord, LeaderboardEntry, LobbyStatus,
+    MatchState, Operation, Player, PlayerReport, PokerGame, PokerStakes, PokerStreetContributions,
+    StateAuditReport, Timeouts, Tournament, TournamentSummary, UserProfile,
 };
 
+/// Most recent game records scanned by time-windowed queries
+const RECENT_GAMES_SCAN_LIMIT: usize = 200;
+
+/// Game records loaded when totalling platform-wide chip counts
+const PLATFORM_STATS_SCAN_LIMIT: usize = 500;
+
 pub struct GamePlatformService {
     state: Arc<GamePlatformState>,
     runtime: Arc<ServiceRuntime<Self>>,
@@ -46,6 +60,7 @@ impl Service for GamePlatformService {
         let schema = Schema::build(
             QueryRoot {
                 state: self.state.clone(),
+                now: self.runtime.system_time().micros(),
             },
             MutationRoot {
                 runtime: self.runtime.clone(),
@@ -60,6 +75,7 @@ impl Service for GamePlatformService {
 
 struct QueryRoot {
     state: Arc<GamePlatformState>,
+    now: u64,
 }
 
 #[Object]
@@ -92,12 +108,99 @@ impl QueryRoot {
         self.state.user_profiles.get(&owner).await.ok().flatten()
     }
 
+    /// Resolve a username, ETH address or owner to all of the user's identifiers
+    async fn resolve_identity(&self, query: String) -> Option<UserIdentity> {
+        let key = query.trim().to_lowercase();
+        let owner = match self.state.eth_to_owner.get(&key).await.ok().flatten() {
+            Some(o) => o,
+            None => match self.state.username_to_owner.get(&key).await.ok().flatten() {
+                Some(o) => o,
+                None => parse_account_owner(query.trim())?,
+            },
+        };
+
+        let profile = self.state.user_profiles.get(&owner).await.ok().flatten()?;
+        Some(UserIdentity {
+            owner: owner.to_string(),
+            eth_address: profile.eth_address.clone(),
+            username: profile.username.clone(),
+            profile,
+        })
+    }
+
     /// Get player statistics
     async fn player_stats(&self, owner: String) -> Option<PlayerStats> {
         let owner = parse_account_owner(&owner)?;
         self.state.stats.get(&owner).await.ok().flatten()
     }
 
+    /// Get a player's win rates per game type and overall, with their streaks
+    async fn player_stats_summary(&self, owner: String) -> Option<PlayerStatsSummary> {
+        let owner = parse_account_owner(&owner)?;
+        let stats = self.state.stats.get(&owner).await.ok().flatten()?;
+        Some(stats.summary())
+    }
+
+    /// Get the achievements a player has unlocked
+    async fn player_achievements(&self, owner: String) -> Vec<Achievement> {
+        let owner = match parse_account_owner(&owner) {
+            Some(o) => o,
+            None => return vec![],
+        };
+        self.state.achievements.get(&owner).await.unwrap_or(None).unwrap_or_default()
+    }
+
+    /// Get the accounts a player has blocked
+    async fn blocked_players(&self, owner: String) -> Vec<AccountOwner> {
+        let owner = match parse_account_owner(&owner) {
+            Some(o) => o,
+            None => return vec![],
+        };
+        self.state.blocks.get(&owner).await.unwrap_or(None).unwrap_or_default()
+    }
+
+    /// Get the players currently looking for a game of this type; an announcement lapses after a while
+    async fn available_players(&self, game_type: GameType) -> Vec<UserProfile> {
+        let listed = self.state.available_since.index_values().await.unwrap_or_default();
+        let mut players = vec![];
+        for (owner, since) in listed {
+            if availability_expired(since, self.now) {
+                continue;
+            }
+            if let Ok(Some(profile)) = self.state.user_profiles.get(&owner).await {
+                if profile.is_available_for(game_type) {
+                    players.push(profile);
+                }
+            }
+        }
+        players
+    }
+
+    /// Get the Elo-expected score of player A against player B; null for unrated game types
+    async fn win_probability(&self, eth_a: String, eth_b: String, game_type: GameType) -> Option<f64> {
+        let mut profiles = Vec::with_capacity(2);
+        for eth_address in [eth_a, eth_b] {
+            let owner = self.state.eth_to_owner.get(&eth_address.to_lowercase()).await.ok().flatten()?;
+            profiles.push(self.state.user_profiles.get(&owner).await.ok().flatten()?);
+        }
+        win_probability(&profiles[0], &profiles[1], game_type)
+    }
+
+    /// Get the record of player `a` against player `b`
+    async fn head_to_head(&self, a: String, b: String) -> HeadToHeadRecord {
+        let (a, b) = match (parse_account_owner(&a), parse_account_owner(&b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return HeadToHeadRecord::default(),
+        };
+
+        let (key, swapped) = head_to_head_key(a, b);
+        let record = match self.state.head_to_head.get(&key).await {
+            Ok(Some(r)) => HeadToHeadRecord::from_tuple(r),
+            _ => return HeadToHeadRecord::default(),
+        };
+        if swapped { record.reversed() } else { record }
+    }
+
     /// Check if username is available
     async fn is_username_available(&self, username: String) -> bool {
         self.state.username_to_owner
@@ -108,11 +211,68 @@ impl QueryRoot {
             .is_none()
     }
 
+    /// Check if a username meets the format rules
+    async fn is_valid_username(&self, username: String) -> bool {
+        validate_username(&username).is_ok()
+    }
+
     // ============ GAME QUERIES ============
 
-    /// Get game state by ID
-    async fn game(&self, game_id: String) -> Option<FullGameState> {
-        self.state.games.get(&game_id).await.ok().flatten()
+    /// Get game state by ID; hole cards are only shown for the seats `viewer` holds, or at showdown
+    async fn game(&self, game_id: String, viewer: Option<String>) -> Option<FullGameState> {
+        let game = self.state.games.get(&game_id).await.ok().flatten()?;
+        Some(game.view_for(viewer_str(viewer).as_deref()))
+    }
+
+    /// Get the summary kept for a finished game
+    async fn game_summary(&self, game_id: String) -> Option<CompletedGameSummary> {
+        finished_game_summary(&self.state, &game_id).await
+    }
+
+    /// Get the latest chat messages of a game, oldest first
+    async fn game_chat(&self, game_id: String, limit: Option<i32>) -> Vec<ChatMessage> {
+        let chat = match self.state.games.get(&game_id).await {
+            Ok(Some(game)) => game.chat,
+            _ => return vec![],
+        };
+        let limit = limit.unwrap_or(50).max(0) as usize;
+        let skip = chat.len().saturating_sub(limit);
+        chat.into_iter().skip(skip).collect()
+    }
+
+    /// Get a match's score and games by ID
+    async fn match_state(&self, match_id: String) -> Option<MatchState> {
+        self.state.matches.get(&match_id).await.ok().flatten()
+    }
+
+    /// Get a tournament bracket by ID
+    async fn tournament(&self, tournament_id: String) -> Option<Tournament> {
+        self.state.tournaments.get(&tournament_id).await.ok().flatten()
+    }
+
+    /// Tournaments still registering or in progress, oldest first
+    async fn active_tournaments(&self) -> Vec<TournamentSummary> {
+        let mut summaries = vec![];
+        for tournament_id in self.state.active_tournament_ids.get().iter() {
+            if let Ok(Some(tournament)) = self.state.tournaments.get(tournament_id).await {
+                summaries.push(tournament.summary());
+            }
+        }
+        summaries
+    }
+
+    /// Get the accounts watching a game
+    async fn spectators(&self, game_id: String) -> Vec<AccountOwner> {
+        match self.state.games.get(&game_id).await {
+            Ok(Some(game)) => game.spectators,
+            _ => vec![],
+        }
+    }
+
+    /// Get the parameters a game was created with
+    async fn game_rules(&self, game_id: String) -> Option<GameRules> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        Some(game.rules)
     }
 
     /// Get active games for a player
@@ -146,6 +306,7 @@ impl QueryRoot {
                         created_at: game.created_at,
                         updated_at: game.updated_at,
                         winner: game.winner,
+                        result_reason: game.result_reason,
                     });
                 }
             }
@@ -154,6 +315,61 @@ impl QueryRoot {
         games
     }
 
+    /// Get the games other chains created from invites sent from this chain
+    async fn accepted_invites(&self, chain_id: ChainId) -> Vec<String> {
+        self.state.accepted_invites.get(&chain_id).await.unwrap_or(None).unwrap_or_default()
+    }
+
+    /// Get an invite sent from this chain, with how the host chain says it went
+    async fn sent_invite(&self, game_id: String) -> Option<SentInvite> {
+        self.state.sent_invites.get(&game_id).await.ok().flatten()
+    }
+
+    /// Get staked challenges still waiting for the player to fund their seat
+    async fn incoming_challenges(&self, owner: String) -> Vec<GameInfo> {
+        let owner = match parse_account_owner(&owner) {
+            Some(o) => o,
+            None => return vec![],
+        };
+
+        let game_ids = self.state.player_games
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        let mut games = vec![];
+        let owner_str = format!("{:?}", owner);
+
+        for game_id in game_ids {
+            if let Ok(Some(game)) = self.state.games.get(&game_id).await {
+                if game.status != GameStatus::WaitingForOpponent {
+                    continue;
+                }
+                let unfunded_seat = (0..game.players.len()).find(|&i| game.players[i] == owner_str && !game.wager_funded[i]);
+                let challenger_idx = match unfunded_seat {
+                    Some(seat) => if seat == 0 { 1 } else { 0 },
+                    None => continue,
+                };
+                games.push(GameInfo {
+                    game_id: game.game_id,
+                    game_type: game.game_type,
+                    game_mode: game.game_mode,
+                    opponent: game.players.get(challenger_idx).cloned().unwrap_or_default(),
+                    opponent_name: game.player_names.get(challenger_idx).cloned().unwrap_or_default(),
+                    status: game.status,
+                    created_at: game.created_at,
+                    updated_at: game.updated_at,
+                    winner: game.winner,
+                    result_reason: game.result_reason,
+                });
+            }
+        }
+
+        games
+    }
+
     /// Get active games for a player by ETH address
     async fn player_active_games_by_eth(&self, eth_address: String) -> Vec<GameInfo> {
         let owner = match self.state.eth_to_owner.get(&eth_address.to_lowercase()).await {
@@ -185,6 +401,7 @@ impl QueryRoot {
                         created_at: game.created_at,
                         updated_at: game.updated_at,
                         winner: game.winner,
+                        result_reason: game.result_reason,
                     });
                 }
             }
@@ -194,7 +411,7 @@ impl QueryRoot {
     }
 
     /// Get game history for a player
-    async fn player_game_history(&self, owner: String, limit: i32) -> Vec<GameInfo> {
+    async fn player_game_history(&self, owner: String, limit: i32, offset: Option<i32>) -> Vec<GameInfo> {
         let owner = match parse_account_owner(&owner) {
             Some(o) => o,
             None => return vec![],
@@ -210,21 +427,20 @@ impl QueryRoot {
         let mut games = vec![];
         let owner_str = format!("{:?}", owner);
 
-        for game_id in game_ids.iter().rev().take(limit as usize) {
-            if let Ok(Some(game)) = self.state.games.get(game_id).await {
-                if game.status == GameStatus::Completed || game.status == GameStatus::TimedOut {
-                    let opponent_idx = if game.players.get(0) == Some(&owner_str) { 1 } else { 0 };
-                    games.push(GameInfo {
-                        game_id: game.game_id,
-                        game_type: game.game_type,
-                        game_mode: game.game_mode,
-                        opponent: game.players.get(opponent_idx).cloned().unwrap_or_default(),
-                        opponent_name: game.player_names.get(opponent_idx).cloned().unwrap_or_default(),
-                        status: game.status,
-                        created_at: game.created_at,
-                        updated_at: game.updated_at,
-                        winner: game.winner,
-                    });
+        let skip = offset.unwrap_or(0).max(0) as usize;
+        let mut finished = 0;
+
+        for game_id in game_ids.iter().rev() {
+            if games.len() >= limit.max(0) as usize {
+                break;
+            }
+            if let Some(summary) = finished_game_summary(&self.state, game_id).await {
+                if summary.status == GameStatus::Completed || summary.status == GameStatus::TimedOut {
+                    finished += 1;
+                    if finished <= skip {
+                        continue;
+                    }
+                    games.push(summary.info_for(&owner_str));
                 }
             }
         }
@@ -232,6 +448,51 @@ impl QueryRoot {
         games
     }
 
+    /// Get a player's results for games completed after `since_micros`
+    async fn player_recent_performance(&self, eth_address: String, since_micros: u64) -> RecentPerformance {
+        let mut performance = RecentPerformance::default();
+
+        let owner = match self.state.eth_to_owner.get(&eth_address.to_lowercase()).await {
+            Ok(Some(o)) => o,
+            _ => return performance,
+        };
+
+        let game_ids = self.state.player_games
+            .get(&owner)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+
+        let owner_str = format!("{:?}", owner);
+
+        for game_id in game_ids.iter().rev().take(RECENT_GAMES_SCAN_LIMIT) {
+            if let Some(summary) = finished_game_summary(&self.state, game_id).await {
+                if summary.status != GameStatus::Completed && summary.status != GameStatus::TimedOut {
+                    continue;
+                }
+                if summary.updated_at <= since_micros {
+                    continue;
+                }
+
+                let player = match summary.players.iter().position(|p| p == &owner_str) {
+                    Some(0) => Player::One,
+                    Some(_) => Player::Two,
+                    None => continue,
+                };
+
+                match summary.winner {
+                    Some(winner) if winner == player => performance.wins += 1,
+                    Some(_) => performance.losses += 1,
+                    None => performance.draws += 1,
+                }
+                performance.games += 1;
+            }
+        }
+
+        performance
+    }
+
     // ============ CHESS QUERIES ============
 
     /// Get chess board for a game
@@ -240,17 +501,78 @@ impl QueryRoot {
         game.chess_board
     }
 
-    /// Get valid moves for a piece (simplified)
-    async fn chess_valid_moves(&self, _game_id: String, _square: i32) -> Vec<i32> {
-        vec![]
+    /// Get the point value of material each side has captured
+    async fn chess_captured_value(&self, game_id: String) -> Option<ChessCapturedValue> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        game.chess_board.map(|board| board.captured_value())
+    }
+
+    /// Get the captured pieces and White's material lead
+    async fn chess_material(&self, game_id: String) -> Option<ChessMaterial> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        game.chess_board.map(|board| board.material())
+    }
+
+    /// Get valid moves for a piece; empty when the game disables hints
+    async fn chess_valid_moves(&self, game_id: String, square: i32) -> Vec<i32> {
+        let game = match self.state.games.get(&game_id).await {
+            Ok(Some(g)) => g,
+            _ => return vec![],
+        };
+        if !game.rules.allow_hints || !(0..64).contains(&square) {
+            return vec![];
+        }
+        match game.chess_board {
+            Some(board) => board.valid_moves_from(square as u8).into_iter().map(i32::from).collect(),
+            None => vec![],
+        }
+    }
+
+    /// Get the draw conditions that currently apply to a game of any type
+    async fn game_draw_status(&self, game_id: String) -> Option<GameDrawStatus> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        Some(game.chess_board.map(|board| board.draw_status()).unwrap_or_default())
+    }
+
+    /// Board after the first `move_number` moves of a game, for stepping through a replay
+    async fn chess_position_at(&self, game_id: String, move_number: i32) -> Option<ChessBoard> {
+        let move_number = usize::try_from(move_number).ok()?;
+        let game = self.state.games.get(&game_id).await.ok()??;
+        game.chess_board?.position_at(move_number)
+    }
+
+    /// Halfmoves remaining until the automatic move-count draws
+    async fn chess_draw_countdown(&self, game_id: String) -> Option<DrawCountdown> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        game.chess_board.map(|board| board.draw_countdown())
     }
 
     // ============ POKER QUERIES ============
 
-    /// Get poker game state
-    async fn poker_game(&self, game_id: String) -> Option<PokerGame> {
+    /// Get poker game state; hole cards are only shown for the seats `viewer` holds, or at showdown
+    async fn poker_game(&self, game_id: String, viewer: Option<String>) -> Option<PokerGame> {
         let game = self.state.games.get(&game_id).await.ok()??;
-        game.poker_game
+        game.view_for(viewer_str(viewer).as_deref()).poker_game
+    }
+
+    /// Get poker state as a spectator sees it, with hole cards hidden until showdown
+    async fn poker_spectator_view(&self, game_id: String) -> Option<PokerGame> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        game.poker_game.map(|poker| poker.spectator_view())
+    }
+
+    /// Get the community cards with the street each was dealt on
+    async fn poker_board_timeline(&self, game_id: String) -> Vec<BoardCard> {
+        match self.state.games.get(&game_id).await {
+            Ok(Some(game)) => game.poker_game.map(|poker| poker.board_timeline()).unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    /// Get each player's committed chips on the current street
+    async fn poker_street_contributions(&self, game_id: String) -> Option<PokerStreetContributions> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        Some(game.poker_game?.street_contributions())
     }
 
     // ============ BLACKJACK QUERIES ============
@@ -261,6 +583,22 @@ impl QueryRoot {
         game.blackjack_game
     }
 
+    /// Get value, soft flag and bust flag for each blackjack hand
+    async fn blackjack_hand_values(&self, game_id: String) -> Vec<BlackjackHandValue> {
+        match self.state.games.get(&game_id).await.ok().flatten() {
+            Some(game) => game.blackjack_game.map(|bj| bj.hand_values()).unwrap_or_default(),
+            None => vec![],
+        }
+    }
+
+    /// Get the ordered blackjack action log for replaying a hand
+    async fn blackjack_history(&self, game_id: String) -> Vec<BlackjackActionRecord> {
+        match self.state.games.get(&game_id).await.ok().flatten() {
+            Some(game) => game.blackjack_game.map(|bj| bj.action_history).unwrap_or_default(),
+            None => vec![],
+        }
+    }
+
     // ============ LOBBY QUERIES ============
 
     /// Get lobby by ID
@@ -275,7 +613,7 @@ impl QueryRoot {
 
         for lobby_id in lobby_ids {
             if let Ok(Some(lobby)) = self.state.lobbies.get(&lobby_id).await {
-                if lobby.status == LobbyStatus::Open && lobby.is_public {
+                if lobby.status == LobbyStatus::Open && lobby.is_public && lobby.expires_at >= self.now {
                     if let Some(gt) = game_type {
                         if lobby.game_type == gt {
                             lobbies.push(lobby);
@@ -312,12 +650,50 @@ impl QueryRoot {
         lobbies
     }
 
+    /// Get the open lobbies a player can join: public ones and any they were invited to
+    async fn joinable_lobbies(&self, owner: String) -> Vec<GameLobby> {
+        let owner = match parse_account_owner(&owner) {
+            Some(o) => o,
+            None => return vec![],
+        };
+
+        let lobby_ids = self.state.active_lobby_ids.get().clone();
+        let mut lobbies = vec![];
+
+        for lobby_id in lobby_ids {
+            if let Ok(Some(lobby)) = self.state.lobbies.get(&lobby_id).await {
+                if lobby.is_joinable_by(&owner, self.now) {
+                    lobbies.push(lobby);
+                }
+            }
+        }
+
+        lobbies
+    }
+
+    /// Get the players waiting in the matchmaking queue for a game type
+    async fn matchmaking_queue(&self, game_type: GameType) -> Vec<String> {
+        self.state.matchmaking_queue
+            .get(&game_type)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .iter()
+            .map(|owner| owner.to_string())
+            .collect()
+    }
+
     // ============ LEADERBOARD QUERIES ============
 
     /// Get leaderboard
-    async fn leaderboard(&self, _game_type: Option<GameType>, limit: i32) -> Vec<LeaderboardEntry> {
-        let entries = self.state.leaderboard.get().clone();
-        entries.into_iter().take(limit as usize).collect()
+    async fn leaderboard(&self, _game_type: Option<GameType>, limit: i32, offset: Option<i32>) -> Vec<LeaderboardEntry> {
+        page(self.state.leaderboard.get(), offset, limit)
+    }
+
+    /// Rank just the given players against each other, e.g. for a friends board
+    async fn group_leaderboard(&self, eth_addresses: Vec<String>) -> Vec<LeaderboardEntry> {
+        group_leaderboard(self.state.leaderboard.get(), &eth_addresses)
     }
 
     /// Get player rank
@@ -342,17 +718,198 @@ impl QueryRoot {
         self.state.total_games_played.get().clone() as i64
     }
 
+    /// List games of a type and/or status from the type and status index, latest listed first
+    async fn games_by_filter(
+        &self,
+        game_type: Option<GameType>,
+        status: Option<GameStatus>,
+        limit: i32,
+        offset: Option<i32>,
+    ) -> Vec<FullGameState> {
+        let game_types = match game_type {
+            Some(t) => vec![t],
+            None => vec![GameType::Chess, GameType::Poker, GameType::Blackjack],
+        };
+        let statuses = match status {
+            Some(s) => vec![s],
+            None => vec![
+                GameStatus::WaitingForOpponent,
+                GameStatus::InProgress,
+                GameStatus::Completed,
+                GameStatus::Cancelled,
+                GameStatus::TimedOut,
+            ],
+        };
+
+        // Page through the matching index buckets in turn, latest listed first, loading only the games returned
+        let mut skip = offset.unwrap_or(0).max(0) as u64;
+        let mut remaining = limit.max(0) as u64;
+        let mut games = Vec::new();
+        for game_type in game_types {
+            for &status in &statuses {
+                if remaining == 0 {
+                    return games;
+                }
+                let len = self.state.game_index_len.get(&(game_type, status)).await.ok().flatten().unwrap_or(0);
+                if skip >= len {
+                    skip -= len;
+                    continue;
+                }
+                let newest = len - 1 - skip;
+                let taken = remaining.min(newest + 1);
+                for slot in (newest + 1 - taken..=newest).rev() {
+                    let game_id = match self.state.game_index.get(&(game_type, status, slot)).await {
+                        Ok(Some(id)) => id,
+                        _ => continue,
+                    };
+                    if let Ok(Some(game)) = self.state.games.get(&game_id).await {
+                        games.push(game.view_for(None));
+                    }
+                }
+                remaining -= taken;
+                skip = 0;
+            }
+        }
+        games
+    }
+
+    /// Get player reports for moderators, newest first; empty unless `viewer` is the admin
+    async fn reports(&self, viewer: String, limit: i32, offset: Option<i32>) -> Vec<PlayerReport> {
+        let is_admin = parse_account_owner(&viewer).is_some_and(|owner| self.state.admin.get().as_ref() == Some(&owner));
+        if !is_admin {
+            return vec![];
+        }
+        let next_id = *self.state.next_report_id.get();
+        let skip = offset.unwrap_or(0).max(0) as usize;
+        let mut reports = vec![];
+        for report_id in (0..next_id).rev().skip(skip).take(limit.max(0) as usize) {
+            if let Ok(Some(report)) = self.state.reports.get(&report_id).await {
+                reports.push(report);
+            }
+        }
+        reports
+    }
+
+    /// Get aggregate platform numbers for the admin dashboard
+    async fn platform_stats(&self) -> PlatformStats {
+        let mut stats = PlatformStats {
+            total_users: *self.state.total_users.get(),
+            total_games: *self.state.total_games_played.get(),
+            active_lobbies: self.state.active_lobby_ids.get().len() as u64,
+            ..Default::default()
+        };
+
+        for game_type in [GameType::Chess, GameType::Poker, GameType::Blackjack] {
+            let count = self.state.games_by_type.get(&game_type).await.ok().flatten().unwrap_or(0);
+            match game_type {
+                GameType::Chess => stats.chess_games = count,
+                GameType::Poker => stats.poker_games = count,
+                GameType::Blackjack => stats.blackjack_games = count,
+            }
+        }
+
+        let game_ids = self.state.games.indices().await.unwrap_or_default();
+        for game_id in game_ids.iter().take(PLATFORM_STATS_SCAN_LIMIT) {
+            let game = match self.state.games.get(game_id).await {
+                Ok(Some(g)) => g,
+                _ => continue,
+            };
+            stats.games_scanned += 1;
+            let active = game.status == GameStatus::InProgress;
+
+            if let Some(poker) = &game.poker_game {
+                if active {
+                    stats.chips_in_play += poker.player_chips.iter().sum::<u64>() + poker.pot;
+                }
+            }
+
+            if let Some(bj) = &game.blackjack_game {
+                let staked: u64 = if bj.is_game_over { 0 } else { bj.bets.iter().sum() };
+                if active {
+                    stats.chips_in_play += bj.player_chips + staked;
+                }
+                // The house keeps whatever the player no longer holds or has staked
+                if let Some(rules) = &game.rules.blackjack {
+                    stats.house_balance += rules.bankroll as i64 - (bj.player_chips + staked) as i64;
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Read-only consistency check across the profile, leaderboard, game and lobby indexes
+    async fn state_audit(&self) -> StateAuditReport {
+        let mut report = StateAuditReport::default();
+
+        let usernames = self.state.username_to_owner.indices().await.unwrap_or_default();
+        for username in &usernames {
+            let profile = match self.state.username_to_owner.get(username).await {
+                Ok(Some(owner)) => self.state.user_profiles.get(&owner).await.ok().flatten(),
+                _ => None,
+            };
+            report.check_username(username, profile.as_ref());
+        }
+
+        for entry in self.state.leaderboard.get() {
+            let registered = matches!(
+                self.state.eth_to_owner.get(&entry.eth_address.to_lowercase()).await,
+                Ok(Some(_))
+            );
+            report.check_leaderboard_entry(entry, registered);
+        }
+
+        let game_ids = self.state.games.indices().await.unwrap_or_default();
+        for game_id in &game_ids {
+            let game = match self.state.games.get(game_id).await {
+                Ok(Some(g)) => g,
+                _ => continue,
+            };
+            let mut all_registered = true;
+            for player in game.players.iter().filter(|p| p.as_str() != "BOT") {
+                let has_profile = match parse_account_owner(player) {
+                    Some(owner) => matches!(self.state.user_profiles.get(&owner).await, Ok(Some(_))),
+                    None => false,
+                };
+                all_registered &= has_profile;
+            }
+            report.check_game_players(game_id, all_registered);
+        }
+
+        for lobby_id in self.state.active_lobby_ids.get() {
+            let lobby = self.state.lobbies.get(lobby_id).await.ok().flatten();
+            report.check_active_lobby(lobby_id, lobby.as_ref());
+        }
+
+        report
+    }
+
     /// Get total registered users
     async fn total_users(&self) -> i64 {
         self.state.total_users.get().clone() as i64
     }
 
+    /// Get the platform defaults applied to new users and games
+    async fn game_defaults(&self) -> GameDefaults {
+        self.state.game_defaults.get().clone()
+    }
+
     /// Get game clock
     async fn game_clock(&self, game_id: String) -> Option<Clock> {
         let game = self.state.games.get(&game_id).await.ok()??;
         Some(game.clock)
     }
 
+    /// When the side to move runs out of time, if a clocked turn is under way
+    async fn turn_deadline(&self, game_id: String) -> Option<Timestamp> {
+        let game = self.state.games.get(&game_id).await.ok()??;
+        if game.status != GameStatus::InProgress {
+            return None;
+        }
+        let seat = game.seat_to_move()?;
+        Some(game.clock.turn_deadline(seat))
+    }
+
     /// Check if it's player's turn
     async fn is_player_turn(&self, game_id: String, owner: String) -> bool {
         let owner = match parse_account_owner(&owner) {
@@ -371,27 +928,12 @@ impl QueryRoot {
             None => return false,
         };
 
-        match game.game_type {
-            GameType::Chess => {
-                if let Some(board) = game.chess_board {
-                    let active = if board.active_player == Player::One { 0 } else { 1 };
-                    return player_idx == active;
-                }
-            }
-            GameType::Poker => {
-                if let Some(poker) = game.poker_game {
-                    let active = if poker.active_player == Player::One { 0 } else { 1 };
-                    return player_idx == active;
-                }
-            }
-            GameType::Blackjack => {
-                if let Some(bj) = game.blackjack_game {
-                    return bj.is_player_turn && player_idx == 0;
-                }
-            }
+        // A side whose flag has fallen has no turn left, even before the timeout is recorded
+        if game.timed_out_seat(Timestamp::from(self.now)).is_some() {
+            return false;
         }
 
-        false
+        game.seat_to_move() == Some(player_idx)
     }
 
     /// Get time remaining for each player
@@ -401,10 +943,15 @@ impl QueryRoot {
             None => return vec![300, 300],
         };
 
-        vec![
-            game.clock.time_left[0].as_micros() as i64 / 1_000_000,
-            game.clock.time_left[1].as_micros() as i64 / 1_000_000,
-        ]
+        let mut remaining: Vec<i64> = game.clock
+            .time_left
+            .iter()
+            .map(|left| left.as_micros() as i64 / 1_000_000)
+            .collect();
+        if let Some(left) = game.timed_out_seat(Timestamp::from(self.now)).and_then(|seat| remaining.get_mut(seat)) {
+            *left = 0;
+        }
+        remaining
     }
 }
 
@@ -443,9 +990,22 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Mark yourself as looking for a game, optionally of one type, with a status message
+    async fn set_availability(
+        &self,
+        looking_for_game: bool,
+        game_type: Option<GameType>,
+        message: Option<String>,
+    ) -> Vec<u8> {
+        let operation = Operation::SetAvailability { looking_for_game, game_type, message };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
     // ============ LOBBY MUTATIONS ============
 
     /// Create a new game lobby
+    #[allow(clippy::too_many_arguments)]
     async fn create_lobby(
         &self,
         game_type: GameType,
@@ -453,6 +1013,11 @@ impl MutationRoot {
         is_public: bool,
         password: Option<String>,
         time_control: Option<i32>,
+        max_players: Option<i32>,
+        wager: Option<Amount>,
+        creator_color: Option<ColorChoice>,
+        poker_stakes: Option<PokerStakes>,
+        allow_hints: Option<bool>,
     ) -> Vec<u8> {
         let operation = Operation::CreateLobby {
             game_type,
@@ -460,6 +1025,11 @@ impl MutationRoot {
             is_public,
             password,
             time_control: time_control.unwrap_or(300) as u64,
+            max_players: max_players.unwrap_or(2) as u32,
+            wager,
+            creator_color,
+            poker_stakes,
+            allow_hints,
         };
         self.runtime.schedule_operation(&operation);
         vec![]
@@ -476,6 +1046,13 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Expire lobbies that have outlived their TTL
+    async fn prune_lobbies(&self) -> Vec<u8> {
+        let operation = Operation::PruneLobbies;
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
     /// Cancel a lobby
     async fn cancel_lobby(&self, lobby_id: String) -> Vec<u8> {
         let operation = Operation::CancelLobby { lobby_id };
@@ -483,29 +1060,88 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Invite a player to a lobby, letting them join without the password
+    async fn invite_to_lobby(&self, lobby_id: String, invitee: String) -> Vec<u8> {
+        let invitee = match parse_account_owner(&invitee) {
+            Some(i) => i,
+            None => return vec![],
+        };
+        let operation = Operation::InviteToLobby { lobby_id, invitee };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
     // ============ GAME MUTATIONS ============
 
-    /// Create a new game (vs bot or direct)
+    /// Create a new game (vs bot or direct); `time_bank_seconds` gives each action its own allowance with
+    /// `time_seconds` as the reserve behind it
+    #[allow(clippy::too_many_arguments)]
     async fn create_game(
         &self,
         game_type: GameType,
         game_mode: GameMode,
         opponent: Option<String>,
+        creator_color: Option<ColorChoice>,
         time_seconds: Option<i32>,
+        time_bank_seconds: Option<i32>,
+        allow_hints: Option<bool>,
+        betting_mode: Option<BettingMode>,
+        poker_stakes: Option<PokerStakes>,
+        hit_split_aces: Option<bool>,
+        blackjack_stakes: Option<BlackjackStakes>,
+        wager: Option<Amount>,
     ) -> Vec<u8> {
         let opponent_owner = opponent.and_then(|o| parse_account_owner(&o));
-        let time_secs = time_seconds.unwrap_or(300) as u64;
 
         let operation = Operation::CreateGame {
             game_type,
             game_mode,
             opponent: opponent_owner,
-            timeouts: Some(Timeouts {
-                start_time: TimeDelta::from_secs(time_secs),
-                increment: TimeDelta::from_secs(10),
-                block_delay: TimeDelta::from_secs(5),
-            }),
+            creator_color,
+            timeouts: Some(mutation_timeouts(time_seconds, time_bank_seconds)),
+            allow_hints,
+            betting_mode,
+            poker_stakes,
+            hit_split_aces,
+            blackjack_stakes,
+            wager,
+        };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Block a player from joining your lobbies, matching with you or challenging you
+    async fn block_player(&self, target: String) -> Vec<u8> {
+        let target = match parse_account_owner(&target) {
+            Some(t) => t,
+            None => return vec![],
+        };
+        let operation = Operation::BlockPlayer { target };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Lift a block on a player
+    async fn unblock_player(&self, target: String) -> Vec<u8> {
+        let target = match parse_account_owner(&target) {
+            Some(t) => t,
+            None => return vec![],
         };
+        let operation = Operation::UnblockPlayer { target };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Join the matchmaking queue for a game type
+    async fn join_matchmaking(&self, game_type: GameType) -> Vec<u8> {
+        let operation = Operation::JoinMatchmaking { game_type };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Leave the matchmaking queue for a game type
+    async fn leave_matchmaking(&self, game_type: GameType) -> Vec<u8> {
+        let operation = Operation::LeaveMatchmaking { game_type };
         self.runtime.schedule_operation(&operation);
         vec![]
     }
@@ -575,6 +1211,8 @@ impl MutationRoot {
             "double" => game_platform::BlackjackAction::Double,
             "split" => game_platform::BlackjackAction::Split,
             "insurance" => game_platform::BlackjackAction::Insurance,
+            "standall" | "stand_all" | "stand-all" => game_platform::BlackjackAction::StandAll,
+            "evenmoney" | "even_money" | "even-money" => game_platform::BlackjackAction::EvenMoney,
             _ => return vec![],
         };
 
@@ -588,9 +1226,9 @@ impl MutationRoot {
 
     // ============ GAME CONTROL MUTATIONS ============
 
-    /// Resign from a game
-    async fn resign_game(&self, game_id: String) -> Vec<u8> {
-        let operation = Operation::ResignGame { game_id };
+    /// Resign from a game; ignored unless `confirm` is true
+    async fn resign_game(&self, game_id: String, confirm: Option<bool>) -> Vec<u8> {
+        let operation = Operation::ResignGame { game_id, confirm: confirm.unwrap_or(false) };
         self.runtime.schedule_operation(&operation);
         vec![]
     }
@@ -609,6 +1247,34 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Claim a draw under the fifty-move rule
+    async fn claim_fifty_move_rule(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::ClaimFiftyMoveRule { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Decline a draw offer
+    async fn decline_draw(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::DeclineDraw { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Ask to undo your last move in a friendly chess game
+    async fn request_takeback(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::RequestTakeback { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Agree to undo the opponent's last move
+    async fn accept_takeback(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::AcceptTakeback { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
     /// Claim victory on timeout
     async fn claim_timeout(&self, game_id: String) -> Vec<u8> {
         let operation = Operation::ClaimTimeout { game_id };
@@ -616,6 +1282,125 @@ impl MutationRoot {
         vec![]
     }
 
+    /// Escrow your stake for a wagered game
+    async fn fund_wager(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::FundWager { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Invite a player on another chain to a game hosted on their chain; your moves in it are relayed there
+    async fn send_game_invite(
+        &self,
+        target_chain: ChainId,
+        invitee: String,
+        game_type: GameType,
+        time_seconds: Option<i32>,
+        time_bank_seconds: Option<i32>,
+    ) -> Vec<u8> {
+        let invitee = match parse_account_owner(&invitee) {
+            Some(i) => i,
+            None => return vec![],
+        };
+
+        let operation = Operation::SendGameInvite {
+            target_chain,
+            invitee,
+            game_type,
+            timeouts: Some(mutation_timeouts(time_seconds, time_bank_seconds)),
+        };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Accept a cross-chain invite and start the game
+    async fn accept_game_invite(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::AcceptGameInvite { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Turn down a cross-chain invite
+    async fn decline_game_invite(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::DeclineGameInvite { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Call off a staked challenge the opponent has not yet funded
+    async fn withdraw_challenge(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::WithdrawChallenge { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Abort a game before any move has been made
+    async fn abort_game(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::AbortGame { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Watch an ongoing game
+    async fn spectate_game(&self, game_id: String) -> Vec<u8> {
+        let operation = Operation::SpectateGame { game_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Send a chat message to a game's players and spectators
+    async fn send_chat_message(&self, game_id: String, text: String) -> Vec<u8> {
+        let operation = Operation::SendChatMessage { game_id, text };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Report a player from a game you played together
+    async fn report_player(&self, game_id: String, target: String, reason: String) -> Vec<u8> {
+        let target = match parse_account_owner(&target) {
+            Some(t) => t,
+            None => return vec![],
+        };
+        let operation = Operation::ReportPlayer { game_id, target, reason };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Request a rematch of a finished game
+    async fn request_rematch(&self, game_id: String, swap_colors: Option<bool>) -> Vec<u8> {
+        let operation = Operation::RequestRematch {
+            game_id,
+            swap_colors: swap_colors.unwrap_or(true),
+        };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Buy back into a friendly poker game after busting
+    async fn poker_rebuy(&self, game_id: String, amount: u64) -> Vec<u8> {
+        let operation = Operation::PokerRebuy { game_id, amount };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Commit to a shuffle seed by its SHA-256 hash
+    async fn commit_seed(&self, game_id: String, commitment: String) -> Vec<u8> {
+        let operation = Operation::CommitSeed { game_id, commitment };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Reveal a previously committed shuffle seed
+    async fn reveal_seed(&self, game_id: String, seed: String) -> Vec<u8> {
+        let seed = match seed.parse::<u64>() {
+            Ok(s) => s,
+            Err(_) => return vec![],
+        };
+        let operation = Operation::RevealSeed { game_id, seed };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
     /// Record a bot game result
     async fn record_bot_game(
         &self,
@@ -633,6 +1418,128 @@ impl MutationRoot {
         self.runtime.schedule_operation(&operation);
         vec![]
     }
+
+    // ============ ADMIN MUTATIONS ============
+
+    /// Update platform defaults (admin only)
+    async fn update_game_defaults(&self, defaults: GameDefaults) -> Vec<u8> {
+        let operation = Operation::UpdateGameDefaults { defaults };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Merge a duplicate account into another (admin only)
+    async fn merge_accounts(&self, keep_owner: String, merge_owner: String) -> Vec<u8> {
+        let (keep_owner, merge_owner) = match (parse_account_owner(&keep_owner), parse_account_owner(&merge_owner)) {
+            (Some(k), Some(m)) => (k, m),
+            _ => return vec![],
+        };
+        let operation = Operation::MergeAccounts { keep_owner, merge_owner };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Force a stuck game to a final result by naming the winning seat, or no seat for a draw (admin only)
+    async fn force_resolve(&self, game_id: String, winner_seat: Option<i32>) -> Vec<u8> {
+        if winner_seat.is_some_and(|seat| seat < 0) {
+            return vec![];
+        }
+        let operation = Operation::ForceResolve { game_id, winner_seat: winner_seat.map(|seat| seat as u32) };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Turn your bot chess record into a provisional rating; only once, before any rated game
+    async fn seed_elo_from_bots(&self) -> Vec<u8> {
+        let operation = Operation::SeedEloFromBots;
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Delete your profile; refused while you have a game in progress
+    async fn delete_account(&self) -> Vec<u8> {
+        let operation = Operation::DeleteAccount;
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Create a single-elimination tournament; the field size must be a power of two
+    async fn create_tournament(&self, game_type: GameType, max_participants: i32, allow_hints: Option<bool>) -> Vec<u8> {
+        if max_participants <= 0 {
+            return vec![];
+        }
+        let operation = Operation::CreateTournament { game_type, max_participants: max_participants as u32, allow_hints };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Register for a tournament
+    async fn join_tournament(&self, tournament_id: String) -> Vec<u8> {
+        let operation = Operation::JoinTournament { tournament_id };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Start a best-of series against an opponent; `best_of` must be odd
+    async fn create_match(&self, opponent: String, game_type: GameType, best_of: i32) -> Vec<u8> {
+        let opponent = match parse_account_owner(&opponent) {
+            Some(o) => o,
+            None => return vec![],
+        };
+        if best_of <= 0 {
+            return vec![];
+        }
+        let operation = Operation::CreateMatch { opponent, game_type, best_of: best_of as u32 };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Designate the clock arbiter, or clear it (admin only)
+    async fn set_arbiter(&self, arbiter: Option<String>) -> Vec<u8> {
+        let arbiter = match arbiter {
+            Some(a) => match parse_account_owner(&a) {
+                Some(owner) => Some(owner),
+                None => return vec![],
+            },
+            None => None,
+        };
+        let operation = Operation::SetArbiter { arbiter };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+
+    /// Add time to a seat's clock (admin or arbiter)
+    async fn add_time(&self, game_id: String, seat: i32, seconds: i32) -> Vec<u8> {
+        if seat < 0 || seconds <= 0 {
+            return vec![];
+        }
+        let operation = Operation::AddTime { game_id, seat: seat as u32, seconds: seconds as u64 };
+        self.runtime.schedule_operation(&operation);
+        vec![]
+    }
+}
+
+/// The summary filed for a finished game, or one built from the game itself if it finished before summaries were kept
+async fn finished_game_summary(state: &GamePlatformState, game_id: &str) -> Option<CompletedGameSummary> {
+    if let Ok(Some(summary)) = state.game_summaries.get(game_id).await {
+        return Some(summary);
+    }
+    state.games.get(game_id).await.ok().flatten().filter(FullGameState::is_finished).map(|game| game.summary())
+}
+
+/// Clock settings for a game created through a mutation: a sudden-death clock with an increment, or a time bank
+/// when a per-action allowance is given
+fn mutation_timeouts(time_seconds: Option<i32>, time_bank_seconds: Option<i32>) -> Timeouts {
+    let time_secs = TimeDelta::from_secs(time_seconds.unwrap_or(300) as u64);
+    match time_bank_seconds {
+        Some(per_action) => Timeouts::with_time_bank(TimeDelta::from_secs(per_action as u64), time_secs, TimeDelta::from_secs(5)),
+        None => Timeouts::symmetric(time_secs, TimeDelta::from_secs(10), TimeDelta::from_secs(5)),
+    }
+}
+
+/// A query's `viewer` in the form games store their players in
+fn viewer_str(viewer: Option<String>) -> Option<String> {
+    viewer.as_deref().and_then(parse_account_owner).map(|owner| format!("{:?}", owner))
 }
 
 /// Parse an AccountOwner from a string format
@@ -1,15 +1,16 @@
 // Game Platform State - Full blockchain storage
 
-use async_graphql::SimpleObject;
+use async_graphql::{ComplexObject, SimpleObject};
 use linera_sdk::{
-    linera_base_types::AccountOwner,
+    linera_base_types::{AccountOwner, Amount, ChainId, Timestamp},
     views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext},
 };
 use serde::{Deserialize, Serialize};
 
 use game_platform::{
-    BlackjackGame, ChessBoard, Clock, GameLobby, GameMode, GameStatus, GameType,
-    LeaderboardEntry, Player, PokerGame, Timeouts, UserProfile,
+    Achievement, BlackjackGame, BlackjackStakes, ChatMessage, ChessBoard, Clock, GameDefaults, GameEndReason,
+    GameLobby, GameMode, GameRules, GameStatus, GameType, LeaderboardEntry, MatchState, Player,
+    PlayerReport, PokerGame, PokerStakes, PokerVariant, SeatingDraw, Timeouts, Tournament, UserProfile,
 };
 
 // ============ GAME INFO ============
@@ -25,11 +26,118 @@ pub struct GameInfo {
     pub created_at: u64,
     pub updated_at: u64,
     pub winner: Option<Player>,
+    pub result_reason: Option<GameEndReason>,
+}
+
+// An invite sent from this chain to a game hosted on another, kept in step by the host's messages
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct SentInvite {
+    pub game_id: String,
+    pub host_chain: ChainId,
+    pub inviter: AccountOwner,
+    pub invitee: AccountOwner,
+    pub game_type: GameType,
+    pub expires_at: u64,
+    pub status: GameStatus,
+    pub winner: Option<Player>,
+    pub result_reason: Option<GameEndReason>,
+}
+
+// What is kept of a game once it has finished, for history queries
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct CompletedGameSummary {
+    pub game_id: String,
+    pub game_type: GameType,
+    pub game_mode: GameMode,
+    pub status: GameStatus,
+    pub players: Vec<String>,
+    pub player_names: Vec<String>,
+    pub winner: Option<Player>,
+    pub result_reason: Option<GameEndReason>,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub tournament_id: Option<String>,
+    pub match_id: Option<String>,
+}
+
+impl CompletedGameSummary {
+    /// The game as seen from one of its players
+    pub fn info_for(&self, owner_str: &str) -> GameInfo {
+        let opponent_idx = if self.players.first().map(String::as_str) == Some(owner_str) { 1 } else { 0 };
+        GameInfo {
+            game_id: self.game_id.clone(),
+            game_type: self.game_type,
+            game_mode: self.game_mode,
+            opponent: self.players.get(opponent_idx).cloned().unwrap_or_default(),
+            opponent_name: self.player_names.get(opponent_idx).cloned().unwrap_or_default(),
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            winner: self.winner,
+            result_reason: self.result_reason,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, SimpleObject)]
+pub struct RecentPerformance {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub games: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, SimpleObject)]
+pub struct GameTypeSummary {
+    pub game_type: GameType,
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    // Split pots for poker, pushes for blackjack
+    pub draws: u32,
+    // Percentage of games played that were won
+    pub win_rate: f64,
+    pub current_streak: i32,
+    pub best_streak: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerStatsSummary {
+    pub by_game: Vec<GameTypeSummary>,
+    pub overall_win_rate: f64,
+    pub current_streak: i32,
+    pub best_streak: u32,
+    pub games_played: u32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, SimpleObject)]
+pub struct PlatformStats {
+    pub total_users: u64,
+    pub total_games: u64,
+    pub chess_games: u64,
+    pub poker_games: u64,
+    pub blackjack_games: u64,
+    pub active_lobbies: u64,
+    // Totals below come from a bounded scan of stored games
+    pub chips_in_play: u64,
+    pub house_balance: i64,
+    pub games_scanned: u32,
+}
+
+// ============ IDENTITY ============
+
+#[derive(Clone, Serialize, Deserialize, SimpleObject)]
+pub struct UserIdentity {
+    pub owner: String,
+    pub eth_address: String,
+    pub username: String,
+    pub profile: UserProfile,
 }
 
 // ============ FULL GAME STATE ============
 
 #[derive(Clone, Serialize, Deserialize, SimpleObject)]
+#[graphql(complex)]
 pub struct FullGameState {
     pub game_id: String,
     pub game_type: GameType,
@@ -37,19 +145,350 @@ pub struct FullGameState {
     pub status: GameStatus,
     pub players: Vec<String>,
     pub player_names: Vec<String>,
+    // Set when the seats were settled by a coin flip
+    pub seating_draw: Option<SeatingDraw>,
+    // A random seating still waiting on the players' revealed seeds
+    pub seating_pending: bool,
     pub created_at: u64,
     pub updated_at: u64,
+    // Last time a seat played, which inactivity is measured from
+    pub last_move_at: u64,
     pub winner: Option<Player>,
+    // How the game ended, set when it is completed or timed out
+    pub result_reason: Option<GameEndReason>,
     pub clock: Clock,
+    pub timeouts: Timeouts,
     pub draw_offered_by: Option<Player>,
+    pub draw_offered_at: Option<u64>,
+    // Friendly-game takeback handshake, and the clock as it stood before the last move
+    pub takeback_requested_by: Option<Player>,
+    pub clock_before_last_move: Option<Clock>,
+    // Rematch handshake, one slot per player
+    pub rematch_requests: Vec<bool>,
+    // Cleared as soon as any rematch request asks to swap seats
+    pub rematch_keep_seats: bool,
+    // Where play carried on: the agreed rematch, or the table a rebuy continued at
+    pub rematch_game_id: Option<String>,
+    // Commit-reveal shuffle, one slot per player
+    pub seed_commitments: Vec<Option<String>>,
+    pub seed_reveals: Vec<Option<u64>>,
+    pub rules: GameRules,
+    pub spectators: Vec<AccountOwner>,
+    pub chat: Vec<ChatMessage>,
+    // Set on games created for a tournament round
+    pub tournament_id: Option<String>,
+    // Set on games played as part of a match
+    pub match_id: Option<String>,
+    // Chain the invite came from and when it lapses, for games created from a cross-chain invite
+    pub invite_origin: Option<ChainId>,
+    pub invite_expires_at: Option<u64>,
+    // Native-token stake per seat, and which seats have escrowed it
+    pub wager: Option<Amount>,
+    pub wager_funded: Vec<bool>,
     // Game-specific state
     pub chess_board: Option<ChessBoard>,
     pub poker_game: Option<PokerGame>,
     pub blackjack_game: Option<BlackjackGame>,
 }
 
+#[allow(dead_code)]
+impl FullGameState {
+    pub fn new(
+        game_id: String,
+        game_type: GameType,
+        game_mode: GameMode,
+        players: Vec<String>,
+        player_names: Vec<String>,
+        timestamp: u64,
+        timeouts: Timeouts,
+    ) -> Self {
+        let seats = players.len();
+        let chess_board = (game_type == GameType::Chess).then(ChessBoard::new);
+        let stakes = PokerStakes::default();
+        let poker_game = (game_type == GameType::Poker)
+            .then(|| PokerGame::with_seats(seats, stakes.starting_chips, stakes.small_blind, stakes.big_blind, PokerVariant::Holdem, timestamp));
+        let blackjack_stakes = BlackjackStakes::default();
+        let blackjack_game = (game_type == GameType::Blackjack)
+            .then(|| BlackjackGame::new(blackjack_stakes.bet, blackjack_stakes.bankroll, timestamp));
+        let rules = GameRules {
+            time_control: timeouts.clone(),
+            rated: game_mode != GameMode::VsBot,
+            allow_hints: true,
+            poker: poker_game.as_ref().map(PokerGame::rules),
+            blackjack: blackjack_game.as_ref().map(BlackjackGame::rules),
+        };
+        FullGameState {
+            game_id,
+            game_type,
+            game_mode,
+            status: GameStatus::InProgress,
+            players,
+            player_names,
+            seating_draw: None,
+            seating_pending: false,
+            created_at: timestamp,
+            updated_at: timestamp,
+            last_move_at: timestamp,
+            winner: None,
+            result_reason: None,
+            clock: Clock::with_seats(Timestamp::from(timestamp), &timeouts, seats),
+            timeouts,
+            draw_offered_by: None,
+            draw_offered_at: None,
+            takeback_requested_by: None,
+            clock_before_last_move: None,
+            rematch_requests: vec![false; seats],
+            rematch_keep_seats: true,
+            rematch_game_id: None,
+            seed_commitments: vec![None; seats],
+            seed_reveals: vec![None; seats],
+            rules,
+            spectators: vec![],
+            chat: vec![],
+            tournament_id: None,
+            match_id: None,
+            invite_origin: None,
+            invite_expires_at: None,
+            wager: None,
+            wager_funded: vec![false; seats],
+            chess_board,
+            poker_game,
+            blackjack_game,
+        }
+    }
+
+    /// The seat with a draw offer on the table, unless the offer has lapsed
+    pub fn live_draw_offer(&self, now: u64, defaults: &GameDefaults) -> Option<Player> {
+        let offered_at = self.draw_offered_at.unwrap_or(self.created_at);
+        self.draw_offered_by.filter(|_| !defaults.draw_offer_expired(offered_at, now))
+    }
+
+    pub fn clear_draw_offer(&mut self) {
+        self.draw_offered_by = None;
+        self.draw_offered_at = None;
+    }
+
+    /// The seat whose clock is running, if anyone is due to act
+    pub fn seat_to_move(&self) -> Option<usize> {
+        match self.game_type {
+            GameType::Chess => self.chess_board.as_ref().map(|b| b.active_player.index()),
+            GameType::Poker => self.poker_game.as_ref().map(|p| p.active_player),
+            GameType::Blackjack => self
+                .blackjack_game
+                .as_ref()
+                .and_then(|bj| bj.is_player_turn.then_some(0)),
+        }
+    }
+
+    /// Re-deals a poker table at `stakes`, keeping its seats and betting mode; for use before any action
+    pub fn set_poker_stakes(&mut self, stakes: PokerStakes, timestamp: u64) {
+        if let Some(poker) = self.poker_game.as_mut() {
+            let mut table = PokerGame::with_seats(
+                poker.num_players(),
+                stakes.starting_chips,
+                stakes.small_blind,
+                stakes.big_blind,
+                poker.variant,
+                timestamp,
+            );
+            table.betting_mode = poker.betting_mode;
+            *poker = table;
+            self.rules.poker = Some(poker.rules());
+        }
+    }
+
+    /// Re-deals a blackjack table at `stakes` from the same seed, keeping its table rules; for use before any action
+    pub fn set_blackjack_stakes(&mut self, stakes: BlackjackStakes) {
+        if let Some(blackjack) = self.blackjack_game.as_mut() {
+            let mut table = BlackjackGame::new(stakes.bet, stakes.bankroll, blackjack.seed);
+            table.max_splits = blackjack.max_splits;
+            table.dealer_hits_soft_17 = blackjack.dealer_hits_soft_17;
+            table.hit_split_aces = blackjack.hit_split_aces;
+            *blackjack = table;
+            self.rules.blackjack = Some(blackjack.rules());
+        }
+    }
+
+    /// The seat to move, if the game is still running and its clock has run out
+    pub fn timed_out_seat(&self, block_time: Timestamp) -> Option<usize> {
+        if self.status != GameStatus::InProgress || self.awaiting_seed_reveal() {
+            return None;
+        }
+        self.seat_to_move().filter(|&seat| self.clock.timed_out(block_time, seat))
+    }
+
+    /// True once any move or betting action has been made
+    pub fn has_play_started(&self) -> bool {
+        self.chess_board.as_ref().is_some_and(|b| !b.move_history.is_empty())
+            || self.poker_game.as_ref().is_some_and(|p| !p.action_history.is_empty())
+            || self.blackjack_game.as_ref().is_some_and(|bj| bj.has_player_acted())
+    }
+
+    /// Why a game that ended through normal play is over
+    pub fn play_end_reason(&self) -> GameEndReason {
+        if let Some(board) = &self.chess_board {
+            if board.is_checkmate {
+                return GameEndReason::Checkmate;
+            }
+            if board.is_stalemate {
+                return GameEndReason::Stalemate;
+            }
+            if board.is_insufficient_material() {
+                return GameEndReason::InsufficientMaterial;
+            }
+            if board.is_fivefold_repetition() {
+                return GameEndReason::Repetition;
+            }
+            return GameEndReason::FiftyMove;
+        }
+        if let Some(blackjack) = &self.blackjack_game {
+            return blackjack.end_reason();
+        }
+        // A pot taken without a showdown is one everyone else folded out of
+        if self.poker_game.as_ref().is_some_and(|p| p.folded.iter().filter(|&&f| !f).count() == 1) {
+            return GameEndReason::Fold;
+        }
+        GameEndReason::Showdown
+    }
+
+    /// Seats that take part in the commit-reveal shuffle: the first seat of each human at a card table,
+    /// or at a chess board whose colors are still to be drawn
+    pub fn seed_seats(&self) -> Vec<usize> {
+        if self.game_type == GameType::Chess && !self.seating_pending {
+            return vec![];
+        }
+        (0..self.players.len())
+            .filter(|&i| self.players[i] != "BOT" && !self.players[..i].contains(&self.players[i]))
+            .collect()
+    }
+
+    /// A table with more than one human is only dealt from a seed all of them committed to and revealed
+    pub fn uses_seed_shuffle(&self) -> bool {
+        self.seed_seats().len() > 1
+    }
+
+    /// True until every human at a seeded table has revealed their seed; nobody can act before then
+    pub fn awaiting_seed_reveal(&self) -> bool {
+        self.uses_seed_shuffle() && self.seed_seats().iter().any(|&seat| self.seed_reveals[seat].is_none())
+    }
+
+    /// Seats holding up the shuffle: those yet to commit, or once everyone has, those yet to reveal
+    pub fn seed_holdouts(&self) -> Vec<usize> {
+        let seats = self.seed_seats();
+        let committing = seats.iter().any(|&seat| self.seed_commitments[seat].is_none());
+        seats
+            .into_iter()
+            .filter(|&seat| {
+                if committing { self.seed_commitments[seat].is_none() } else { self.seed_reveals[seat].is_none() }
+            })
+            .collect()
+    }
+
+    /// Draws a pending random seating from the revealed seeds, moving the creator to the second seat
+    /// if the flip says so
+    pub fn settle_seating(&mut self, seed: u64) {
+        if !self.seating_pending {
+            return;
+        }
+        let draw = SeatingDraw::from_seed(seed);
+        if draw.creator_second {
+            self.players.swap(0, 1);
+            self.player_names.swap(0, 1);
+            self.seed_commitments.swap(0, 1);
+            self.seed_reveals.swap(0, 1);
+            self.wager_funded.swap(0, 1);
+            self.rematch_requests.swap(0, 1);
+        }
+        self.seating_draw = Some(draw);
+        self.seating_pending = false;
+    }
+
+    /// Hides the cards of a seeded table until the reveals reshuffle them
+    pub fn withhold_deal(&mut self) {
+        if let Some(poker) = self.poker_game.as_mut() {
+            for hand in poker.player_hands.iter_mut() {
+                hand.clear();
+            }
+            poker.deck.clear();
+        }
+    }
+
+    /// The game as `viewer` may see it: the deck and other seats' hole cards stay hidden until showdown
+    pub fn view_for(mut self, viewer: Option<&str>) -> FullGameState {
+        let seats: Vec<usize> = (0..self.players.len()).filter(|&i| Some(self.players[i].as_str()) == viewer).collect();
+        self.poker_game = self.poker_game.map(|poker| poker.view_for(&seats));
+        self
+    }
+
+    /// True once the game can no longer be played
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, GameStatus::Completed | GameStatus::TimedOut | GameStatus::Cancelled)
+    }
+
+    pub fn summary(&self) -> CompletedGameSummary {
+        CompletedGameSummary {
+            game_id: self.game_id.clone(),
+            game_type: self.game_type,
+            game_mode: self.game_mode,
+            status: self.status,
+            players: self.players.clone(),
+            player_names: self.player_names.clone(),
+            winner: self.winner,
+            result_reason: self.result_reason,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            tournament_id: self.tournament_id.clone(),
+            match_id: self.match_id.clone(),
+        }
+    }
+
+    /// Drops what a finished game no longer needs once its summary is filed: the chess board with its move history,
+    /// undealt cards, action histories, rollback state and any open draw offer. A poker table keeps its stacks for a rebuy.
+    pub fn compact(&mut self) {
+        self.clear_draw_offer();
+        self.chess_board = None;
+        if let Some(poker) = self.poker_game.as_mut() {
+            poker.deck = Vec::new();
+            poker.action_history = Vec::new();
+            poker.pot_by_street = Vec::new();
+        }
+        if let Some(blackjack) = self.blackjack_game.as_mut() {
+            blackjack.deck = Vec::new();
+            blackjack.action_history = Vec::new();
+        }
+        self.clock_before_last_move = None;
+    }
+}
+
+#[ComplexObject]
+impl FullGameState {
+    /// True once the game has been completed, timed out or cancelled
+    async fn is_game_over(&self) -> bool {
+        self.is_finished()
+    }
+}
+
 // ============ PLAYER STATS ============
 
+/// Consecutive results within one game type
+#[derive(Clone, Copy, Default, Serialize, Deserialize, SimpleObject)]
+pub struct Streak {
+    // Positive for a run of wins, negative for a run of losses
+    pub current: i32,
+    pub best: u32,
+}
+
+impl Streak {
+    fn record_win(&mut self) {
+        self.current = if self.current >= 0 { self.current + 1 } else { 1 };
+        self.best = self.best.max(self.current as u32);
+    }
+
+    fn record_loss(&mut self) {
+        self.current = if self.current <= 0 { self.current - 1 } else { -1 };
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize, SimpleObject)]
 pub struct PlayerStats {
     // Chess
@@ -61,6 +500,10 @@ pub struct PlayerStats {
     pub poker_wins: u32,
     pub poker_losses: u32,
     pub poker_chips_won: i64,
+    pub poker_hands_played: u32,
+    pub poker_biggest_pot_won: u64,
+    // Hands whose pot every seat shared
+    pub poker_draws: u32,
     // Blackjack
     pub blackjack_wins: u32,
     pub blackjack_losses: u32,
@@ -69,6 +512,10 @@ pub struct PlayerStats {
     pub total_games: u32,
     pub current_streak: i32,
     pub best_streak: u32,
+    // Per game type, alongside the overall streak above
+    pub chess_streak: Streak,
+    pub poker_streak: Streak,
+    pub blackjack_streak: Streak,
 }
 
 #[allow(dead_code)]
@@ -80,6 +527,7 @@ impl PlayerStats {
             GameType::Blackjack => self.blackjack_wins += 1,
         }
         self.total_games += 1;
+        self.streak_mut(game_type).record_win();
 
         if self.current_streak >= 0 {
             self.current_streak += 1;
@@ -99,6 +547,7 @@ impl PlayerStats {
             GameType::Blackjack => self.blackjack_losses += 1,
         }
         self.total_games += 1;
+        self.streak_mut(game_type).record_loss();
 
         if self.current_streak <= 0 {
             self.current_streak -= 1;
@@ -110,15 +559,82 @@ impl PlayerStats {
     pub fn record_draw(&mut self, game_type: GameType) {
         match game_type {
             GameType::Chess => self.chess_draws += 1,
-            GameType::Poker => {}
+            GameType::Poker => self.poker_draws += 1,
             GameType::Blackjack => self.blackjack_pushes += 1,
         }
         self.total_games += 1;
         self.current_streak = 0;
+        self.streak_mut(game_type).current = 0;
     }
 
-    pub fn update_elo(&mut self, delta: i32) {
-        self.chess_elo = ((self.chess_elo as i32) + delta).max(100) as u32;
+    fn streak_mut(&mut self, game_type: GameType) -> &mut Streak {
+        match game_type {
+            GameType::Chess => &mut self.chess_streak,
+            GameType::Poker => &mut self.poker_streak,
+            GameType::Blackjack => &mut self.blackjack_streak,
+        }
+    }
+
+    pub fn merge_from(&mut self, other: &PlayerStats) {
+        self.chess_wins += other.chess_wins;
+        self.chess_losses += other.chess_losses;
+        self.chess_draws += other.chess_draws;
+        self.chess_elo = self.chess_elo.max(other.chess_elo);
+        self.poker_wins += other.poker_wins;
+        self.poker_losses += other.poker_losses;
+        self.poker_chips_won += other.poker_chips_won;
+        self.poker_hands_played += other.poker_hands_played;
+        self.poker_biggest_pot_won = self.poker_biggest_pot_won.max(other.poker_biggest_pot_won);
+        self.poker_draws += other.poker_draws;
+        self.blackjack_wins += other.blackjack_wins;
+        self.blackjack_losses += other.blackjack_losses;
+        self.blackjack_pushes += other.blackjack_pushes;
+        self.total_games += other.total_games;
+        self.best_streak = self.best_streak.max(other.best_streak);
+        for (streak, other_streak) in [
+            (&mut self.chess_streak, other.chess_streak),
+            (&mut self.poker_streak, other.poker_streak),
+            (&mut self.blackjack_streak, other.blackjack_streak),
+        ] {
+            streak.best = streak.best.max(other_streak.best);
+        }
+    }
+
+    pub fn update_elo(&mut self, delta: i32, defaults: &GameDefaults) {
+        self.chess_elo = defaults.apply_elo_delta(self.chess_elo, delta);
+    }
+
+    /// Results per game type alongside the overall rate and streaks
+    pub fn summary(&self) -> PlayerStatsSummary {
+        let records = [
+            (GameType::Chess, self.chess_wins, self.chess_losses, self.chess_draws, self.chess_streak),
+            (GameType::Poker, self.poker_wins, self.poker_losses, self.poker_draws, self.poker_streak),
+            (GameType::Blackjack, self.blackjack_wins, self.blackjack_losses, self.blackjack_pushes, self.blackjack_streak),
+        ];
+        let by_game = records
+            .into_iter()
+            .map(|(game_type, wins, losses, draws, streak)| {
+                let games_played = wins + losses + draws;
+                let win_rate = if games_played == 0 { 0.0 } else { wins as f64 / games_played as f64 * 100.0 };
+                GameTypeSummary {
+                    game_type,
+                    games_played,
+                    wins,
+                    losses,
+                    draws,
+                    win_rate,
+                    current_streak: streak.current,
+                    best_streak: streak.best,
+                }
+            })
+            .collect();
+        PlayerStatsSummary {
+            by_game,
+            overall_win_rate: self.win_rate(),
+            current_streak: self.current_streak,
+            best_streak: self.best_streak,
+            games_played: self.total_games,
+        }
     }
 
     pub fn win_rate(&self) -> f64 {
@@ -150,23 +666,88 @@ pub struct GamePlatformState {
 
     // Active games (GameId -> FullGameState)
     pub games: MapView<String, FullGameState>,
+    pub next_game_id: RegisterView<u64>,
+
+    // Finished games (GameId -> CompletedGameSummary)
+    pub game_summaries: MapView<String, CompletedGameSummary>,
 
     // Games by player (AccountOwner -> Vec<GameId>)
     pub player_games: MapView<AccountOwner, Vec<String>>,
 
     // Game lobbies (LobbyId -> GameLobby)
     pub lobbies: MapView<String, GameLobby>,
+    pub next_lobby_id: RegisterView<u64>,
 
     // Active lobbies list
     pub active_lobby_ids: RegisterView<Vec<String>>,
 
     // Leaderboard entries (cached, updated on game completion)
     pub leaderboard: RegisterView<Vec<LeaderboardEntry>>,
+    // Period the leaderboard's rank baseline was taken in
+    pub leaderboard_period: RegisterView<u64>,
 
     // Global counters
     pub total_games_played: RegisterView<u64>,
     pub total_users: RegisterView<u64>,
 
+    // Results between each pair of players, (wins, losses, draws) for the lower-ordered owner
+    pub head_to_head: MapView<(AccountOwner, AccountOwner), (u32, u32, u32)>,
+
+    // Games created per type
+    pub games_by_type: MapView<GameType, u64>,
+    // Game ids listed by type and status, each bucket a dense run of slots from 0
+    pub game_index: MapView<(GameType, GameStatus, u64), String>,
+    pub game_index_len: MapView<(GameType, GameStatus), u64>,
+    // Where each game is listed, so it can move when its status changes
+    pub game_index_slots: MapView<String, (GameStatus, u64)>,
+
     // Current timeouts setting
     pub default_timeouts: RegisterView<Timeouts>,
+
+    // Players waiting for an automatic pairing, per game type
+    pub matchmaking_queue: MapView<GameType, Vec<AccountOwner>>,
+
+    // Admin-configurable platform defaults
+    pub game_defaults: RegisterView<GameDefaults>,
+
+    // Chain creator, allowed to run admin operations
+    pub admin: RegisterView<Option<AccountOwner>>,
+
+    // Designated by the admin to add time to clocks after disputes
+    pub arbiter: RegisterView<Option<AccountOwner>>,
+
+    // Knockout tournaments by id
+    pub tournaments: MapView<String, Tournament>,
+    pub next_tournament_id: RegisterView<u64>,
+
+    // Tournaments still registering or being played, oldest first
+    pub active_tournament_ids: RegisterView<Vec<String>>,
+
+    // Unlocked achievements per player, in unlock order
+    pub achievements: MapView<AccountOwner, Vec<Achievement>>,
+
+    // Multi-game matches by id
+    pub matches: MapView<String, MatchState>,
+    pub next_match_id: RegisterView<u64>,
+
+    // Time of each account's last state-creating operation
+    pub last_action_at: MapView<AccountOwner, u64>,
+
+    // Accounts each user has blocked
+    pub blocks: MapView<AccountOwner, Vec<AccountOwner>>,
+
+    // Players looking for a game, by when they last said so; entries lapse after AVAILABILITY_TTL_MICROS
+    pub available_since: MapView<AccountOwner, u64>,
+
+    // Games other chains created from invites sent from here, by host chain
+    pub accepted_invites: MapView<ChainId, Vec<String>>,
+    // Invites sent from here by game id, which also routes the inviter's moves to the host chain
+    pub sent_invites: MapView<String, SentInvite>,
+    pub next_invite_id: RegisterView<u64>,
+
+    // Player reports awaiting moderation, by sequential id
+    pub reports: MapView<u64, PlayerReport>,
+    pub next_report_id: RegisterView<u64>,
+    // Report ids by (reporter, target, game), so each complaint is filed once
+    pub reported: MapView<(String, String, String), u64>,
 }
@@ -0,0 +1,35 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for achievement unlocking.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{unlock_achievement, AchievementKind, Card, PokerGame, PokerVariant, Suit};
+
+/// Tests that an achievement is only recorded the first time it is earned
+#[test]
+fn test_achievement_unlocks_once() {
+    let mut achievements = Vec::new();
+
+    assert!(unlock_achievement(&mut achievements, AchievementKind::FirstWin, Some("g1".to_string()), 10));
+    assert!(!unlock_achievement(&mut achievements, AchievementKind::FirstWin, Some("g2".to_string()), 20));
+    assert!(unlock_achievement(&mut achievements, AchievementKind::RoyalFlush, None, 30));
+
+    assert_eq!(achievements.len(), 2);
+    assert_eq!(achievements[0].game_id.as_deref(), Some("g1"));
+    assert_eq!(achievements[0].unlocked_at, 10);
+}
+
+/// Tests that ten to ace of one suit across hole cards and board is a royal flush
+#[test]
+fn test_royal_flush_detection() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 3);
+    let hearts = |rank| Card { rank, suit: Suit::Hearts };
+    game.player_hands[0] = vec![hearts(14), hearts(13)];
+    game.player_hands[1] = vec![hearts(2), Card { rank: 11, suit: Suit::Spades }];
+    game.community_cards = vec![hearts(12), hearts(11), hearts(10), hearts(3), hearts(4)];
+
+    assert!(game.has_royal_flush(0));
+    assert!(!game.has_royal_flush(1));
+}
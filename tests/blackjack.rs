@@ -0,0 +1,312 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rule tests for the blackjack game logic.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    BlackjackAction, BlackjackGame, BlackjackResult, Card, GameEndReason, GameOutcome, Suit, BLACKJACK_DECKS,
+};
+
+fn card(rank: u8) -> Card {
+    Card { rank, suit: Suit::Spades }
+}
+
+/// Builds a game with fixed hands. The deck is dealt from the end.
+fn stacked_game(player: &[u8], dealer: &[u8], deck: &[u8]) -> BlackjackGame {
+    let mut game = BlackjackGame::new(100, 1000, 1);
+    game.player_hands = vec![player.iter().map(|&r| card(r)).collect()];
+    game.dealer_hand = dealer.iter().map(|&r| card(r)).collect();
+    game.deck = deck.iter().map(|&r| card(r)).collect();
+    game
+}
+
+/// Tests that split aces get one card each and stand automatically
+#[test]
+fn test_split_aces_receive_one_card() {
+    let mut game = stacked_game(&[14, 14], &[10, 8], &[5, 9, 13]);
+
+    let outcome = game.make_action(BlackjackAction::Split, 0).unwrap();
+
+    assert_ne!(outcome, GameOutcome::InProgress);
+    assert_eq!(game.player_hands.len(), 2);
+    assert_eq!(game.player_hands[0].len(), 2);
+    assert_eq!(game.player_hands[1].len(), 2);
+    assert!(!game.is_player_turn);
+    assert!(game.make_action(BlackjackAction::Hit, 0).is_err());
+
+    // Ace and king after a split is paid 1:1, not as a natural
+    assert_eq!(game.player_hands[0], vec![card(14), card(13)]);
+    assert_eq!(game.results, vec![BlackjackResult::Win, BlackjackResult::Win]);
+    // 800 left after both bets, then 200 back on each hand
+    assert_eq!(game.player_chips, 1200);
+}
+
+/// Tests that split aces can take more cards when the table allows it
+#[test]
+fn test_hit_split_aces_when_allowed() {
+    let mut game = stacked_game(&[14, 14], &[10, 8], &[2, 3, 5, 9]);
+    game.hit_split_aces = true;
+
+    let outcome = game.make_action(BlackjackAction::Split, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::InProgress);
+    assert!(game.is_player_turn);
+    assert_eq!(game.current_hand, 0);
+
+    game.make_action(BlackjackAction::Hit, 0).unwrap();
+    assert_eq!(game.player_hands[0], vec![card(14), card(9), card(3)]);
+    assert_eq!(game.current_hand, 0);
+}
+
+/// Tests that splitting eights leaves the first hand in play
+#[test]
+fn test_split_eights_continue_play() {
+    let mut game = stacked_game(&[8, 8], &[10, 8], &[5, 9, 3, 2]);
+
+    let outcome = game.make_action(BlackjackAction::Split, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::InProgress);
+    assert!(game.is_player_turn);
+    assert_eq!(game.current_hand, 0);
+
+    game.make_action(BlackjackAction::Hit, 0).unwrap();
+    assert_eq!(game.player_hands[0].len(), 3);
+}
+
+/// Tests that re-splitting stops at the configured limit
+#[test]
+fn test_resplit_limit() {
+    let mut game = stacked_game(&[8, 8], &[10, 8], &[8, 8]);
+    game.max_splits = 1;
+
+    game.make_action(BlackjackAction::Split, 0).unwrap();
+    assert_eq!(game.player_hands[0], vec![card(8), card(8)]);
+    assert!(game.make_action(BlackjackAction::Split, 0).is_err());
+}
+
+/// Tests that the dealer stands on soft 17 by default
+#[test]
+fn test_dealer_stands_on_soft_17() {
+    let mut game = stacked_game(&[10, 8], &[14, 6], &[4]);
+
+    game.make_action(BlackjackAction::Stand, 0).unwrap();
+
+    assert_eq!(game.dealer_hand.len(), 2);
+    assert_eq!(game.results, vec![BlackjackResult::Win]);
+}
+
+/// Tests that the dealer draws on soft 17 when the rule is enabled
+#[test]
+fn test_dealer_hits_soft_17() {
+    let mut game = stacked_game(&[10, 8], &[14, 6], &[4]);
+    game.dealer_hits_soft_17 = true;
+
+    game.make_action(BlackjackAction::Stand, 0).unwrap();
+
+    assert_eq!(game.dealer_hand.len(), 3);
+    assert_eq!(game.results, vec![BlackjackResult::Lose]);
+}
+
+/// Tests that StandAll resolves every open hand in one action
+#[test]
+fn test_stand_all_resolves_every_hand() {
+    let mut game = stacked_game(&[8, 8], &[10, 8], &[3, 2, 5, 8]);
+
+    game.make_action(BlackjackAction::Split, 0).unwrap();
+    game.make_action(BlackjackAction::Split, 0).unwrap();
+    assert_eq!(game.player_hands.len(), 3);
+
+    let outcome = game.make_action(BlackjackAction::StandAll, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::Winner(game_platform::Player::Two));
+    assert!(game.is_game_over);
+    assert_eq!(game.results.len(), 3);
+}
+
+/// Tests that a nearly empty shoe is rebuilt before the next round
+#[test]
+fn test_reshuffle_when_shoe_runs_low() {
+    let mut game = stacked_game(&[10, 8], &[10, 7], &[2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+
+    game.make_action(BlackjackAction::Stand, 0).unwrap();
+    assert!(game.is_game_over);
+
+    game.start_new_round(100).unwrap();
+
+    assert_eq!(game.reshuffles, 1);
+    assert_eq!(game.deck.len(), 52 * BLACKJACK_DECKS - 4);
+    assert!(game.is_player_turn);
+    assert_eq!(game.player_hands[0].len(), 2);
+}
+
+/// Tests that insurance pays 2:1 and ends the round when the dealer has blackjack
+#[test]
+fn test_insurance_wins_against_dealer_blackjack() {
+    let mut game = stacked_game(&[10, 8], &[14, 13], &[5]);
+
+    let outcome = game.make_action(BlackjackAction::Insurance, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::Winner(game_platform::Player::Two));
+    assert!(game.is_game_over);
+    assert_eq!(game.results, vec![BlackjackResult::Lose]);
+    // 900 after the bet, 50 insurance staked and 150 returned
+    assert_eq!(game.player_chips, 1000);
+}
+
+/// Tests that insurance is lost when the dealer does not have blackjack
+#[test]
+fn test_insurance_lost_without_dealer_blackjack() {
+    let mut game = stacked_game(&[10, 8], &[14, 7], &[5]);
+
+    let outcome = game.make_action(BlackjackAction::Insurance, 0).unwrap();
+    assert_eq!(outcome, GameOutcome::InProgress);
+    assert!(game.is_player_turn);
+    assert_eq!(game.player_chips, 850);
+
+    game.make_action(BlackjackAction::Stand, 0).unwrap();
+
+    assert_eq!(game.results, vec![BlackjackResult::Push]);
+    assert_eq!(game.player_chips, 950);
+}
+
+/// Tests that the dealer peeks under a ten and a blackjack ends the round before the player acts
+#[test]
+fn test_dealer_peeks_under_ten_upcard() {
+    let mut game = stacked_game(&[10, 8], &[13, 14], &[5]);
+
+    let outcome = game.make_action(BlackjackAction::Hit, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::Winner(game_platform::Player::Two));
+    assert!(game.is_game_over);
+    assert_eq!(game.player_hands[0].len(), 2);
+    assert_eq!(game.results, vec![BlackjackResult::Lose]);
+    assert_eq!(game.player_chips, 900);
+}
+
+/// Tests that a dealer blackjack under a ten ends the round on the deal
+#[test]
+fn test_dealer_blackjack_under_ten_settles_on_deal() {
+    // Seed 214 deals the player 10-8 against a dealer king-ace
+    let mut game = BlackjackGame::new(100, 1000, 214);
+
+    assert!(game.is_game_over);
+    assert!(!game.is_player_turn);
+    assert_eq!(game.results, vec![BlackjackResult::Lose]);
+    assert_eq!(game.player_chips, 900);
+    assert!(game.make_action(BlackjackAction::Hit, 0).is_err());
+}
+
+/// Tests that the dealer peeks under an ace even when insurance is not taken
+#[test]
+fn test_dealer_peeks_under_ace_without_insurance() {
+    let mut game = stacked_game(&[9, 7], &[14, 12], &[5]);
+
+    game.make_action(BlackjackAction::Double, 0).unwrap();
+
+    assert!(game.is_game_over);
+    assert_eq!(game.player_hands[0].len(), 2);
+    assert_eq!(game.bets, vec![100]);
+    assert_eq!(game.results, vec![BlackjackResult::Lose]);
+    assert_eq!(game.player_chips, 900);
+}
+
+/// Tests soft and hard hand value reporting
+#[test]
+fn test_soft_and_hard_hand_values() {
+    let mut game = stacked_game(&[14, 6], &[10, 8], &[13]);
+
+    assert!(game.hand_is_soft(0));
+    assert_eq!(game.hand_values()[0].value, 17);
+
+    game.make_action(BlackjackAction::Hit, 0).unwrap();
+
+    let values = game.hand_values();
+    assert_eq!(values[0].value, 17);
+    assert!(!values[0].is_soft);
+    assert!(!values[0].is_bust);
+}
+
+/// Tests that a natural on the deal pays 3:2 without any action
+#[test]
+fn test_dealt_natural_pays_immediately() {
+    // Seed 12 deals the player A-J against a dealer 19
+    let game = BlackjackGame::new(100, 1000, 12);
+
+    assert!(game.is_game_over);
+    assert!(!game.is_player_turn);
+    assert_eq!(game.results, vec![BlackjackResult::Blackjack]);
+    assert_eq!(game.player_chips, 1150);
+    assert_eq!(game.round_outcome(), GameOutcome::Winner(game_platform::Player::One));
+}
+
+/// Tests that a dealt natural pushes against a dealer natural once even money is declined
+#[test]
+fn test_dealt_natural_pushes_against_dealer_natural() {
+    // Seed 31 deals both sides ace-ten, the dealer showing the ace
+    let mut game = BlackjackGame::new(100, 1000, 31);
+    assert!(game.is_player_turn);
+
+    game.make_action(BlackjackAction::Stand, 0).unwrap();
+
+    assert!(game.is_game_over);
+    assert_eq!(game.results, vec![BlackjackResult::Push]);
+    assert_eq!(game.player_chips, 1000);
+    assert_eq!(game.round_outcome(), GameOutcome::Draw);
+}
+
+/// Tests that even money pays a blackjack 1:1 against a dealer ace without waiting on the hole card
+#[test]
+fn test_even_money_against_dealer_ace() {
+    let mut game = stacked_game(&[14, 13], &[14, 7], &[5]);
+
+    let outcome = game.make_action(BlackjackAction::EvenMoney, 0).unwrap();
+
+    assert_eq!(outcome, GameOutcome::Winner(game_platform::Player::One));
+    assert!(game.is_game_over);
+    assert_eq!(game.results, vec![BlackjackResult::Win]);
+    assert_eq!(game.player_chips, 1100);
+    assert_eq!(game.dealer_hand.len(), 2);
+
+    let mut no_ace = stacked_game(&[14, 13], &[10, 7], &[5]);
+    assert!(no_ace.make_action(BlackjackAction::EvenMoney, 0).is_err());
+}
+
+/// Tests that hit then stand records two ordered action entries
+#[test]
+fn test_hit_then_stand_records_history() {
+    let mut game = stacked_game(&[5, 6], &[10, 8], &[2, 3]);
+
+    game.make_action(BlackjackAction::Hit, 10).unwrap();
+    game.make_action(BlackjackAction::Stand, 20).unwrap();
+
+    assert_eq!(game.action_history.len(), 2);
+    assert_eq!(game.action_history[0].action, BlackjackAction::Hit);
+    assert_eq!(game.action_history[0].hand_value, 14);
+    assert_eq!(game.action_history[0].timestamp, 10);
+    assert_eq!(game.action_history[1].action, BlackjackAction::Stand);
+    assert_eq!(game.action_history[1].hand_value, 14);
+    assert_eq!(game.action_history[1].timestamp, 20);
+    assert!(game.action_history.iter().all(|record| record.hand_index == 0));
+}
+
+/// Tests that a finished round reports how it was decided
+#[test]
+fn test_round_end_reasons() {
+    let mut compared = stacked_game(&[10, 8], &[10, 7], &[]);
+    compared.make_action(BlackjackAction::Stand, 0).unwrap();
+    assert_eq!(compared.end_reason(), GameEndReason::HandsCompared);
+
+    let mut dealer_bust = stacked_game(&[10, 8], &[10, 6], &[10]);
+    dealer_bust.make_action(BlackjackAction::Stand, 0).unwrap();
+    assert_eq!(dealer_bust.end_reason(), GameEndReason::DealerBust);
+
+    let mut bust = stacked_game(&[10, 8], &[10, 7], &[10]);
+    bust.make_action(BlackjackAction::Hit, 0).unwrap();
+    assert_eq!(bust.results, vec![BlackjackResult::Bust]);
+    assert_eq!(bust.end_reason(), GameEndReason::Bust);
+
+    // Seed 214 deals the dealer a king-ace
+    assert_eq!(BlackjackGame::new(100, 1000, 214).end_reason(), GameEndReason::Blackjack);
+}
@@ -0,0 +1,25 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for in-game chat helpers.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{push_chat_message, ChatMessage, CHAT_HISTORY_CAP};
+
+/// Tests that the chat buffer keeps only the newest messages
+#[test]
+fn test_chat_buffer_drops_oldest() {
+    let mut chat = Vec::new();
+    for i in 0..CHAT_HISTORY_CAP + 5 {
+        push_chat_message(&mut chat, ChatMessage {
+            sender_name: "Alice".to_string(),
+            text: format!("message {i}"),
+            timestamp: i as u64,
+        });
+    }
+
+    assert_eq!(chat.len(), CHAT_HISTORY_CAP);
+    assert_eq!(chat[0].text, "message 5");
+    assert_eq!(chat.last().unwrap().timestamp, (CHAT_HISTORY_CAP + 4) as u64);
+}
@@ -0,0 +1,333 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rule tests for the chess game logic.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{ChessBoard, ChessPiece, DrawCondition, DrawCountdown, GameOutcome, PieceType, Player};
+
+/// Tests that a move stamped earlier than the previous one is rejected
+#[test]
+fn test_out_of_order_timestamp_rejected() {
+    let mut board = ChessBoard::new();
+
+    board.make_move(12, 28, None, 100).unwrap();
+
+    assert!(board.make_move(52, 36, None, 50).is_err());
+    assert!(board.make_move(52, 36, None, 100).is_ok());
+}
+
+/// Tests that the bot prefers capturing a hanging piece
+#[test]
+fn test_bot_takes_free_capture() {
+    let mut board = ChessBoard::new();
+
+    // 1. e4 d5 2. Qh5 — the black pawn on d5 can take e4
+    board.make_move(12, 28, None, 1).unwrap();
+    board.make_move(51, 35, None, 2).unwrap();
+    board.make_move(3, 39, None, 3).unwrap();
+
+    assert_eq!(board.active_player, Player::Two);
+    assert_eq!(board.bot_move(7), Some((35, 28)));
+}
+
+/// Tests that the bot reply hands the turn back with a legal move recorded
+#[test]
+fn test_bot_reply_returns_turn() {
+    let mut board = ChessBoard::new();
+    board.make_move(12, 28, None, 1).unwrap();
+
+    board.make_bot_move(42, 1).unwrap();
+
+    assert_eq!(board.active_player, Player::One);
+    assert_eq!(board.move_history.len(), 2);
+    let reply = &board.move_history[1];
+    assert!(reply.from_square >= 48);
+}
+
+/// Tests that a move outside the legal move list is refused and leaves the board untouched
+#[test]
+fn test_illegal_move_rejected() {
+    let mut board = ChessBoard::new();
+
+    // The a1 rook can't jump its own pawn, and a knight doesn't move straight ahead
+    assert_eq!(board.make_move(0, 16, None, 1), Err("Illegal move".to_string()));
+    assert_eq!(board.make_move(6, 22, None, 1), Err("Illegal move".to_string()));
+    assert!(board.move_history.is_empty());
+    assert_eq!(board.active_player, Player::One);
+}
+
+/// Tests that a mating move ends the game without waiting for the loser to try a move
+#[test]
+fn test_fools_mate_is_checkmate() {
+    let mut board = ChessBoard::new();
+
+    // 1. f3 e5 2. g4 Qh4#
+    let mut outcome = GameOutcome::InProgress;
+    for (ts, (from, to)) in [(13, 21), (52, 36), (14, 30), (59, 31)].into_iter().enumerate() {
+        outcome = board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+
+    assert_eq!(outcome, GameOutcome::Winner(Player::Two));
+    assert!(board.is_check);
+    assert!(board.is_checkmate);
+}
+
+/// Tests that capturing a queen is worth nine points to the capturing side
+#[test]
+fn test_captured_value_after_queen_capture() {
+    let mut board = ChessBoard::new();
+
+    // 1. e4 e5 2. Qh5 Qg5 3. Qxg5
+    board.make_move(12, 28, None, 1).unwrap();
+    board.make_move(52, 36, None, 2).unwrap();
+    board.make_move(3, 39, None, 3).unwrap();
+    board.make_move(59, 38, None, 4).unwrap();
+    board.make_move(39, 38, None, 5).unwrap();
+
+    let value = board.captured_value();
+    assert_eq!(value.white, 9);
+    assert_eq!(value.black, 0);
+}
+
+/// Tests that a queen trade leaves the material balance level
+#[test]
+fn test_material_balanced_after_queen_trade() {
+    let mut board = ChessBoard::new();
+
+    // 1. e4 e5 2. d4 exd4 3. Qxd4 Qf6 4. Qxf6 Nxf6
+    let moves = [(12, 28), (52, 36), (11, 27), (36, 27), (3, 27), (59, 45), (27, 45), (62, 45)];
+    for (ts, (from, to)) in moves.into_iter().enumerate() {
+        board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+
+    let material = board.material();
+    assert_eq!(material.balance, 0);
+    assert_eq!(material.captured_white, vec![PieceType::Pawn, PieceType::Queen]);
+    assert_eq!(material.captured_black, vec![PieceType::Pawn, PieceType::Queen]);
+}
+
+/// Tests that winning a rook puts White five points ahead
+#[test]
+fn test_material_after_winning_rook() {
+    let mut board = ChessBoard::new();
+    // Open the a-file so the rooks face each other
+    board.squares[8] = None;
+    board.squares[48] = None;
+
+    board.make_move(0, 56, None, 1).unwrap();
+
+    let material = board.material();
+    assert_eq!(material.balance, 5);
+    assert_eq!(material.captured_black, vec![PieceType::Rook]);
+    assert!(material.captured_white.is_empty());
+}
+
+/// Tests that a starting pawn can advance one or two squares
+#[test]
+fn test_valid_moves_from_starting_pawn() {
+    let board = ChessBoard::new();
+
+    let mut moves = board.valid_moves_from(12);
+    moves.sort();
+    assert_eq!(moves, vec![20, 28]);
+    assert!(board.valid_moves_from(52).is_empty());
+}
+
+/// Tests that the starting position has the 20 legal moves of perft depth 1
+#[test]
+fn test_legal_moves_from_start() {
+    let board = ChessBoard::new();
+
+    let moves = board.legal_moves(Player::One);
+    assert_eq!(moves.len(), 20);
+    assert!(moves.iter().all(|&(_, _, promotion)| promotion.is_none()));
+    assert_eq!(board.legal_moves(Player::Two).len(), 20);
+}
+
+/// Tests the standard starting-position perft totals
+#[test]
+fn test_perft_from_start() {
+    let board = ChessBoard::new();
+
+    assert_eq!(board.perft(1), 20);
+    assert_eq!(board.perft(2), 400);
+    assert_eq!(board.perft(3), 8902);
+}
+
+/// Builds a position from the piece-placement field of a FEN string, every castling right intact and white to move
+fn board_from_placement(placement: &str) -> ChessBoard {
+    let mut board = ChessBoard::new();
+    board.squares = vec![None; 64];
+    for (i, rank) in placement.split('/').enumerate() {
+        let mut file = 0;
+        for c in rank.chars() {
+            if let Some(skip) = c.to_digit(10) {
+                file += skip as usize;
+                continue;
+            }
+            let owner = if c.is_ascii_uppercase() { Player::One } else { Player::Two };
+            let piece_type = match c.to_ascii_lowercase() {
+                'p' => PieceType::Pawn,
+                'n' => PieceType::Knight,
+                'b' => PieceType::Bishop,
+                'r' => PieceType::Rook,
+                'q' => PieceType::Queen,
+                _ => PieceType::King,
+            };
+            board.squares[(7 - i) * 8 + file] = Some(ChessPiece { piece_type, owner, has_moved: false });
+            file += 1;
+        }
+    }
+    board
+}
+
+/// Tests the Kiwipete perft totals, which exercise castling, en passant and promotions
+#[test]
+fn test_perft_kiwipete() {
+    let board = board_from_placement("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R");
+
+    assert_eq!(board.perft(1), 48);
+    assert_eq!(board.perft(2), 2039);
+    assert_eq!(board.perft(3), 97862);
+}
+
+/// Tests that a rook captured on its home square takes its castling right with it
+#[test]
+fn test_rook_captured_at_home_loses_castling() {
+    let mut board = ChessBoard::new();
+    for (square, piece) in board.squares.iter_mut().enumerate() {
+        if ![0, 4, 15, 56, 60].contains(&square) {
+            *piece = None;
+        }
+    }
+    board.squares[1] = Some(ChessPiece { piece_type: PieceType::Rook, owner: Player::One, has_moved: true });
+
+    // 1. h3 Rxa1 2. Rxa1 Kf8 — white's rook is back on a1, but not the one that had the right
+    for (ts, (from, to)) in [(15, 23), (56, 0), (1, 0), (60, 61)].into_iter().enumerate() {
+        board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+
+    assert!(!board.castling_rights.white_queenside);
+    assert!(!board.legal_moves(Player::One).contains(&(4, 2, None)));
+    assert!(board.make_move(4, 2, None, 5).is_err());
+}
+
+/// Tests that shuffling knights back to the start counts each repetition
+#[test]
+fn test_repetition_count_after_knight_shuffle() {
+    let mut board = ChessBoard::new();
+    assert_eq!(board.repetition_count(), 1);
+
+    for (ts, (from, to)) in [(6, 21), (62, 45), (21, 6), (45, 62)].into_iter().enumerate() {
+        board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+
+    assert_eq!(board.repetition_count(), 2);
+    assert!(board.draw_status().conditions.is_empty());
+}
+
+/// Tests that bare kings are reported as insufficient material
+#[test]
+fn test_bare_kings_insufficient_material() {
+    let mut board = ChessBoard::new();
+    for (square, piece) in board.squares.iter_mut().enumerate() {
+        if square != 4 && square != 60 {
+            *piece = None;
+        }
+    }
+
+    assert!(board.is_insufficient_material());
+    assert_eq!(board.draw_status().conditions, vec![DrawCondition::InsufficientMaterial]);
+    assert!(!ChessBoard::new().is_insufficient_material());
+}
+
+/// Tests that capturing the last piece on the board ends the game as a draw
+#[test]
+fn test_capture_to_bare_kings_ends_game() {
+    let mut board = ChessBoard::new();
+    for (square, piece) in board.squares.iter_mut().enumerate() {
+        if square != 4 && square != 60 {
+            *piece = None;
+        }
+    }
+    board.squares[13] = Some(ChessPiece { piece_type: PieceType::Knight, owner: Player::Two, has_moved: true });
+
+    assert_eq!(board.make_move(4, 13, None, 1).unwrap(), GameOutcome::Draw);
+    assert!(board.is_insufficient_material());
+}
+
+/// Tests that the fifth occurrence of a position ends the game without a claim
+#[test]
+fn test_fivefold_repetition_ends_game() {
+    let mut board = ChessBoard::new();
+    let shuffle = [(6, 21), (62, 45), (21, 6), (45, 62)];
+
+    let mut outcomes = vec![];
+    for (ts, (from, to)) in shuffle.iter().cycle().take(16).enumerate() {
+        outcomes.push(board.make_move(*from, *to, None, ts as u64 + 1).unwrap());
+    }
+
+    assert!(outcomes[..15].iter().all(|o| *o == GameOutcome::InProgress));
+    assert_eq!(outcomes[15], GameOutcome::Draw);
+    assert!(board.is_fivefold_repetition());
+}
+
+/// Tests that quiet moves count down towards the move-count draws and a pawn move resets them
+#[test]
+fn test_draw_countdown_after_quiet_moves() {
+    let mut board = ChessBoard::new();
+    assert_eq!(board.draw_countdown(), DrawCountdown { fifty_move: 100, seventy_five_move: 150 });
+
+    for (ts, (from, to)) in [(6, 21), (62, 45), (21, 6)].into_iter().enumerate() {
+        board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+    assert_eq!(board.draw_countdown(), DrawCountdown { fifty_move: 97, seventy_five_move: 147 });
+
+    board.make_move(52, 36, None, 4).unwrap();
+    assert_eq!(board.draw_countdown().fifty_move, 100);
+}
+
+/// Tests that fifty quiet moves make the draw claimable without ending the game
+#[test]
+fn test_fifty_move_draw_is_claimed_not_automatic() {
+    let mut board = ChessBoard::new();
+    // Knight tours of different lengths, so no position comes round five times before the hundredth halfmove
+    let white_tour = [6, 23, 38, 28, 34, 19, 36, 21];
+    let black_tour = [62, 47, 37, 43, 26, 41, 35, 25, 42, 27, 44, 29, 39, 45];
+
+    for ts in 0..100u64 {
+        let i = ts as usize / 2;
+        let tour: &[u8] = if ts % 2 == 0 { &white_tour } else { &black_tour };
+        let (from, to) = (tour[i % tour.len()], tour[(i + 1) % tour.len()]);
+        assert!(!board.can_claim_fifty_move_draw());
+        let outcome = board.make_move(from, to, None, ts + 1).unwrap();
+        assert_eq!(outcome, GameOutcome::InProgress);
+    }
+
+    assert_eq!(board.halfmove_clock, 100);
+    assert!(board.can_claim_fifty_move_draw());
+    assert!(board.draw_status().conditions.contains(&DrawCondition::FiftyMoveRule));
+}
+
+/// Tests replaying a game to an earlier position
+#[test]
+fn test_position_at_replays_opening() {
+    let mut board = ChessBoard::new();
+    // 1. e4 e5 2. Nf3 Nc6 3. Bc4
+    for (ts, (from, to)) in [(12, 28), (52, 36), (6, 21), (57, 42), (5, 26)].into_iter().enumerate() {
+        board.make_move(from, to, None, ts as u64 + 1).unwrap();
+    }
+
+    let position = board.position_at(4).unwrap();
+    let knight = position.squares[42].unwrap();
+    assert_eq!(knight.piece_type, PieceType::Knight);
+    assert_eq!(knight.owner, Player::Two);
+    assert!(position.squares[26].is_none());
+    assert_eq!(position.active_player, Player::One);
+    assert_eq!(position.move_history.len(), 4);
+
+    assert!(board.position_at(0).unwrap().squares[42].is_none());
+    assert!(board.position_at(6).is_none());
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for the game clock.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{Clock, GameDefaults, Player, Timeouts};
+use linera_sdk::linera_base_types::{TimeDelta, Timestamp};
+
+/// Tests that only the player configured with an increment receives it
+#[test]
+fn test_per_player_increment() {
+    let timeouts = Timeouts {
+        start_time: TimeDelta::from_secs(300),
+        increment: [TimeDelta::from_secs(5), TimeDelta::from_secs(0)],
+        block_delay: [TimeDelta::from_secs(0); 2],
+        time_bank: None,
+    };
+    let mut clock = Clock::new(Timestamp::from(0), &timeouts);
+
+    clock.make_move(Timestamp::from(10_000_000), Player::One.index());
+    clock.make_move(Timestamp::from(20_000_000), Player::Two.index());
+
+    assert_eq!(clock.time_left[0], TimeDelta::from_secs(295));
+    assert_eq!(clock.time_left[1], TimeDelta::from_secs(290));
+}
+
+/// Tests that a fresh turn's deadline is its start plus the mover's remaining time and block delay
+#[test]
+fn test_turn_deadline_for_fresh_turn() {
+    let mut clock = Clock::new(Timestamp::from(1_000_000), &Timeouts::default());
+    assert_eq!(clock.turn_deadline(Player::One.index()), Timestamp::from(306_000_000));
+
+    clock.make_move(Timestamp::from(11_000_000), Player::One.index());
+    // 300s - 10s thinking + 10s increment + 5s delay, counted from the new turn start
+    assert_eq!(clock.turn_deadline(Player::Two.index()), Timestamp::from(316_000_000));
+}
+
+/// Tests that a seat's block delay holds off its flag and still lets a late move through
+#[test]
+fn test_block_delay_grace() {
+    let timeouts = Timeouts {
+        start_time: TimeDelta::from_secs(60),
+        increment: [TimeDelta::from_secs(0); 2],
+        block_delay: [TimeDelta::from_secs(5), TimeDelta::from_secs(0)],
+        time_bank: None,
+    };
+    let mut clock = Clock::new(Timestamp::from(0), &timeouts);
+
+    assert!(!clock.timed_out(Timestamp::from(65_000_000), Player::One.index()));
+    assert!(clock.timed_out(Timestamp::from(65_000_001), Player::One.index()));
+    assert!(clock.timed_out(Timestamp::from(60_000_001), Player::Two.index()));
+
+    // A move inside the grace empties the clock rather than being ignored
+    clock.make_move(Timestamp::from(63_000_000), Player::One.index());
+    assert_eq!(clock.time_left[0], TimeDelta::from_secs(0));
+}
+
+/// Tests that a slow action uses up its per-action budget before drawing on the bank
+#[test]
+fn test_time_bank_covers_slow_action() {
+    let timeouts = Timeouts::with_time_bank(TimeDelta::from_secs(15), TimeDelta::from_secs(60), TimeDelta::from_secs(0));
+    let mut clock = Clock::new(Timestamp::from(0), &timeouts);
+
+    // Within budget: the bank is untouched
+    clock.make_move(Timestamp::from(10_000_000), Player::One.index());
+    assert_eq!(clock.time_left[0], TimeDelta::from_secs(60));
+
+    // 40s taken, the first 15 free
+    clock.make_move(Timestamp::from(50_000_000), Player::Two.index());
+    assert_eq!(clock.time_left[1], TimeDelta::from_secs(35));
+
+    // Player One can now run for budget plus bank before flagging
+    assert_eq!(clock.turn_deadline(Player::One.index()), Timestamp::from(125_000_000));
+    assert!(!clock.timed_out(Timestamp::from(125_000_000), Player::One.index()));
+    assert!(clock.timed_out(Timestamp::from(125_000_001), Player::One.index()));
+}
+
+/// Tests that a third seat gets its own clock, sharing the second seat's increment
+#[test]
+fn test_third_seat_runs_its_own_clock() {
+    let timeouts = Timeouts {
+        start_time: TimeDelta::from_secs(300),
+        increment: [TimeDelta::from_secs(0), TimeDelta::from_secs(5)],
+        block_delay: [TimeDelta::from_secs(0); 2],
+        time_bank: None,
+    };
+    let mut clock = Clock::with_seats(Timestamp::from(0), &timeouts, 3);
+    assert_eq!(clock.time_left.len(), 3);
+
+    clock.make_move(Timestamp::from(10_000_000), 2);
+    assert_eq!(clock.time_left, vec![TimeDelta::from_secs(300), TimeDelta::from_secs(300), TimeDelta::from_secs(295)]);
+    assert!(clock.timed_out(Timestamp::from(305_000_001), 2));
+    assert!(!clock.timed_out(Timestamp::from(305_000_001), 0));
+}
+
+/// Tests that a draw offer can be taken up until its expiry and not after
+#[test]
+fn test_draw_offer_expires() {
+    let defaults = GameDefaults { draw_offer_expiry_secs: 60, ..GameDefaults::default() };
+    let offered_at = 5_000_000;
+
+    assert!(!defaults.draw_offer_expired(offered_at, offered_at));
+    assert!(!defaults.draw_offer_expired(offered_at, offered_at + 60_000_000));
+    assert!(defaults.draw_offer_expired(offered_at, offered_at + 60_000_001));
+}
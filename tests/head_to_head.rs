@@ -0,0 +1,35 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for head-to-head records.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{head_to_head_key, record_head_to_head, HeadToHeadRecord};
+use linera_sdk::linera_base_types::AccountOwner;
+
+/// Tests that two games won by each side leave the pair at 1-1 from either side
+#[test]
+fn test_head_to_head_one_each() {
+    let alice = AccountOwner::Address20([1; 20]);
+    let bob = AccountOwner::Address20([2; 20]);
+
+    let (key, swapped) = head_to_head_key(alice, bob);
+    let (reverse_key, reverse_swapped) = head_to_head_key(bob, alice);
+    assert_eq!(key, reverse_key);
+    assert_ne!(swapped, reverse_swapped);
+
+    let mut record = (0, 0, 0);
+    // Alice wins the first game, Bob the second
+    record_head_to_head(&mut record, Some(key.0 == alice));
+    record_head_to_head(&mut record, Some(key.0 == bob));
+
+    let alice_view = HeadToHeadRecord::from_tuple(record);
+    let alice_view = if swapped { alice_view.reversed() } else { alice_view };
+    assert_eq!(alice_view, HeadToHeadRecord { wins: 1, losses: 1, draws: 0 });
+
+    record_head_to_head(&mut record, None);
+    let bob_view = HeadToHeadRecord::from_tuple(record);
+    let bob_view = if reverse_swapped { bob_view.reversed() } else { bob_view };
+    assert_eq!(bob_view, HeadToHeadRecord { wins: 1, losses: 1, draws: 1 });
+}
@@ -0,0 +1,102 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for leaderboard ranking.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    group_leaderboard, page, rank_leaderboard, rank_leaderboard_for_period, LeaderboardEntry,
+    LEADERBOARD_PERIOD_MICROS,
+};
+
+fn entry(username: &str, wins: u32, losses: u32) -> LeaderboardEntry {
+    LeaderboardEntry {
+        rank: 0,
+        username: username.to_string(),
+        eth_address: format!("0x{username}"),
+        wins,
+        losses,
+        win_rate: 0.0,
+        elo: 1200,
+        total_games: wins + losses,
+        previous_rank: 0,
+        rank_delta: 0,
+    }
+}
+
+/// Tests that movement is reported against the period's starting ranks, however often the board is re-ranked
+#[test]
+fn test_rank_deltas_after_reshuffle() {
+    let mut entries = vec![entry("alice", 3, 1), entry("bob", 2, 2), entry("carol", 1, 3)];
+    let period = rank_leaderboard_for_period(&mut entries, 0, 0);
+    assert_eq!(entries[0].username, "alice");
+    assert!(entries.iter().all(|e| e.rank_delta == 0));
+
+    // Carol wins enough to jump to the top the next day
+    let next_day = LEADERBOARD_PERIOD_MICROS;
+    entries[2].wins = 8;
+    entries[2].total_games = 10;
+    let period = rank_leaderboard_for_period(&mut entries, period, next_day);
+
+    assert_eq!(entries[0].username, "carol");
+    assert_eq!(entries[0].previous_rank, 3);
+    assert_eq!(entries[0].rank_delta, 2);
+    assert_eq!(entries[1].username, "alice");
+    assert_eq!(entries[1].rank_delta, -1);
+    assert_eq!(entries[2].username, "bob");
+    assert_eq!(entries[2].rank_delta, -1);
+
+    // Another result later that day re-ranks without losing the movement since the day began
+    entries[2].losses += 1;
+    entries[2].total_games += 1;
+    let period = rank_leaderboard_for_period(&mut entries, period, next_day + 1);
+    assert_eq!(entries[0].username, "carol");
+    assert_eq!(entries[0].rank_delta, 2);
+    assert_eq!(entries[2].rank_delta, -1);
+
+    // A new day takes a fresh baseline
+    rank_leaderboard_for_period(&mut entries, period, 2 * next_day);
+    assert!(entries.iter().all(|e| e.rank_delta == 0));
+}
+
+/// Tests fetching a middle page of the leaderboard with an offset
+#[test]
+fn test_leaderboard_page_with_offset() {
+    let mut entries: Vec<LeaderboardEntry> = (0..25)
+        .map(|i| entry(&format!("player{i:02}"), 25 - i, i))
+        .collect();
+    rank_leaderboard(&mut entries);
+
+    let second_page = page(&entries, Some(10), 10);
+    assert_eq!(second_page.len(), 10);
+    assert_eq!(second_page[0].rank, 11);
+    assert_eq!(second_page[9].rank, 20);
+    assert_eq!(second_page[0].username, "player10");
+
+    // Windows past the end are clamped
+    assert_eq!(page(&entries, Some(20), 10).len(), 5);
+    assert!(page(&entries, Some(40), 10).is_empty());
+    assert_eq!(page(&entries, None, 3)[0].rank, 1);
+}
+
+/// Tests that a group board ranks only the requested players
+#[test]
+fn test_group_leaderboard_ranks_only_members() {
+    let mut entries = vec![
+        entry("alice", 5, 0),
+        entry("bob", 4, 1),
+        entry("carol", 3, 2),
+        entry("dave", 2, 3),
+        entry("erin", 1, 4),
+    ];
+    rank_leaderboard(&mut entries);
+
+    let group = vec!["0xerin".to_string(), "0xBOB".to_string(), "0xdave".to_string()];
+    let board = group_leaderboard(&entries, &group);
+
+    let names: Vec<&str> = board.iter().map(|e| e.username.as_str()).collect();
+    assert_eq!(names, vec!["bob", "dave", "erin"]);
+    assert_eq!(board.iter().map(|e| e.rank).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(board.iter().all(|e| e.rank_delta == 0));
+}
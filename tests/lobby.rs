@@ -0,0 +1,116 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for lobby helpers.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    hash_lobby_password, lobby_seats_supported, seat_coin_flip, ColorChoice, GameLobby, GameMode,
+    GameType, LobbyStatus, PokerStakes, SeatingDraw,
+};
+use linera_sdk::linera_base_types::AccountOwner;
+
+/// Tests that different passwords and salts produce different digests
+#[test]
+fn test_password_hashes_do_not_collide() {
+    let salt = "lobby1:owner";
+    let first = hash_lobby_password("hunter2", salt);
+
+    assert_eq!(first.len(), 64);
+    assert_eq!(first, hash_lobby_password("hunter2", salt));
+    assert_ne!(first, hash_lobby_password("hunter3", salt));
+    assert_ne!(first, hash_lobby_password("hunter2", "lobby2:owner"));
+    // Strings that collided under the old rolling fold
+    assert_ne!(hash_lobby_password("Aa", salt), hash_lobby_password("BB", salt));
+}
+
+fn two_player_lobby(creator_color: ColorChoice) -> GameLobby {
+    GameLobby {
+        lobby_id: "lobby1".to_string(),
+        creator: "alice".to_string(),
+        creator_name: "Alice".to_string(),
+        game_type: GameType::Chess,
+        game_mode: GameMode::VsFriend,
+        is_public: true,
+        password_hash: None,
+        password_salt: String::new(),
+        status: LobbyStatus::Full,
+        time_control: 300,
+        created_at: 0,
+        expires_at: 0,
+        players: vec!["alice".to_string(), "bob".to_string()],
+        player_names: vec!["Alice".to_string(), "Bob".to_string()],
+        max_players: 2,
+        game_id: None,
+        wager: None,
+        creator_color,
+        poker_stakes: PokerStakes::default(),
+        allow_hints: true,
+        invited: vec![],
+    }
+}
+
+/// Tests that a creator choosing Black is seated second, so the joiner moves first
+#[test]
+fn test_creator_playing_black_is_seated_second() {
+    let (players, names) = two_player_lobby(ColorChoice::Black).game_seating();
+    assert_eq!(players, vec!["bob".to_string(), "alice".to_string()]);
+    assert_eq!(names, vec!["Bob".to_string(), "Alice".to_string()]);
+
+    let (players, _) = two_player_lobby(ColorChoice::White).game_seating();
+    assert_eq!(players[0], "alice");
+
+    // A random choice is left to the game's seed draw, so the lobby seats in join order
+    let (players, _) = two_player_lobby(ColorChoice::Random).game_seating();
+    assert_eq!(players[0], "alice");
+}
+
+/// Tests that a seating coin flip is fixed by its seed and can be checked after the game
+#[test]
+fn test_seating_draw_is_deterministic_and_auditable() {
+    let draw = SeatingDraw::from_seed(7);
+    assert_eq!(draw, SeatingDraw::from_seed(7));
+    assert_eq!(draw.creator_second, seat_coin_flip(7));
+    assert!(draw.verify());
+
+    // A recorded result that disagrees with its seed is caught
+    let tampered = SeatingDraw { creator_second: !draw.creator_second, ..draw };
+    assert!(!tampered.verify());
+
+    // Both sides of the coin come up across seeds
+    assert!((0..32).any(seat_coin_flip));
+    assert!(!(0..32).all(seat_coin_flip));
+}
+
+/// Tests that a player blocked by the lobby creator cannot join, and neither can one who blocked them
+#[test]
+fn test_blocked_player_cannot_join_lobby() {
+    let alice = AccountOwner::Address20([1; 20]);
+    let bob = AccountOwner::Address20([2; 20]);
+    let carol = AccountOwner::Address20([3; 20]);
+
+    let mut lobby = two_player_lobby(ColorChoice::White);
+    lobby.status = LobbyStatus::Open;
+    lobby.players = vec![format!("{:?}", alice)];
+    lobby.player_names = vec!["Alice".to_string()];
+
+    // Alice has blocked Bob
+    let alice_blocks = vec![vec![bob]];
+    assert!(lobby.block_conflict(&bob, &[], &alice_blocks));
+    assert!(!lobby.block_conflict(&carol, &[], &alice_blocks));
+
+    // Carol blocking Alice keeps her out too
+    assert!(lobby.block_conflict(&carol, &[alice], &[vec![]]));
+}
+
+/// Tests that only poker lobbies can seat more than two players
+#[test]
+fn test_lobby_seats_per_game_type() {
+    assert!(lobby_seats_supported(GameType::Chess, 2));
+    assert!(!lobby_seats_supported(GameType::Chess, 3));
+    assert!(!lobby_seats_supported(GameType::Blackjack, 6));
+    assert!(lobby_seats_supported(GameType::Poker, 6));
+    assert!(!lobby_seats_supported(GameType::Poker, 7));
+    assert!(!lobby_seats_supported(GameType::Poker, 1));
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for best-of match scoring.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{is_valid_best_of, rematch_seating, GameType, MatchState, MatchStatus, Player};
+
+/// Tests that seats alternate and wins are credited to the right match player
+#[test]
+fn test_match_alternates_seats() {
+    let players = vec!["alice".to_string(), "bob".to_string()];
+    let mut state = MatchState::new("m".to_string(), GameType::Chess, players, 3, 0);
+
+    state.game_ids.push("g1".to_string());
+    assert_eq!(state.next_seating(), vec!["bob".to_string(), "alice".to_string()]);
+    // Bob holds the second seat in game one
+    assert!(!state.record_result("g1", Some(Player::Two)));
+
+    state.game_ids.push("g2".to_string());
+    assert!(!state.record_result("g2", None));
+    assert_eq!(state.draws, 1);
+
+    // Seats are back to the original order in game three
+    state.game_ids.push("g3".to_string());
+    assert!(state.record_result("g3", Some(Player::Two)));
+    assert_eq!(state.scores, vec![0, 2]);
+    assert_eq!(state.status, MatchStatus::Completed);
+    assert_eq!(state.winner.as_deref(), Some("bob"));
+}
+
+/// Tests that only odd series lengths are accepted
+#[test]
+fn test_best_of_must_be_odd() {
+    assert!(is_valid_best_of(1));
+    assert!(is_valid_best_of(3));
+    assert!(!is_valid_best_of(0));
+    assert!(!is_valid_best_of(4));
+    assert!(!is_valid_best_of(11));
+}
+
+/// Tests that a rematch swaps seats by default and keeps the player order when asked
+#[test]
+fn test_rematch_seating() {
+    assert_eq!(rematch_seating(["alice", "bob"], true), ["bob", "alice"]);
+    assert_eq!(rematch_seating(["alice", "bob"], false), ["alice", "bob"]);
+}
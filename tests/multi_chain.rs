@@ -0,0 +1,415 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for games that span two chains.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{seed_commitment, GameOutcome, GameType, Operation, PokerAction};
+use linera_sdk::{
+    linera_base_types::{AccountOwner, ApplicationId, TimeDelta},
+    test::{ActiveChain, QueryOutcome, TestValidator},
+};
+
+/// Registers the inviter and sends an invite to the invitee's chain, returning the game id the invitee sees
+async fn send_invite(
+    inviter_chain: &ActiveChain,
+    invitee_chain: &ActiveChain,
+    application_id: ApplicationId<game_platform::GamePlatformAbi>,
+    game_type: GameType,
+) -> String {
+    let invitee = AccountOwner::from(invitee_chain.public_key());
+    inviter_chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Inviter".to_string(),
+                    eth_address: "0x4747474747474747474747474747474747474747".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::SendGameInvite {
+                    target_chain: invitee_chain.id(),
+                    invitee,
+                    game_type,
+                    timeouts: None,
+                });
+        })
+        .await;
+    invitee_chain.handle_received_messages().await;
+
+    let query = format!(r#"query {{ playerActiveGames(owner: "{invitee}") {{ gameId }} }}"#);
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, query).await;
+    response["playerActiveGames"][0]["gameId"].as_str().unwrap().to_string()
+}
+
+/// Adds a block holding a single operation
+async fn submit(
+    chain: &ActiveChain,
+    application_id: ApplicationId<game_platform::GamePlatformAbi>,
+    operation: Operation,
+) {
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, operation);
+        })
+        .await;
+}
+
+/// Reads the status of an invite as the inviter's chain last heard it
+async fn sent_invite_status(
+    inviter_chain: &ActiveChain,
+    application_id: ApplicationId<game_platform::GamePlatformAbi>,
+    game_id: &str,
+) -> serde_json::Value {
+    let query = format!(r#"query {{ sentInvite(gameId: "{game_id}") {{ status winner resultReason }} }}"#);
+    let QueryOutcome { response, .. } = inviter_chain.graphql_query(application_id, query).await;
+    response["sentInvite"].clone()
+}
+
+/// Tests that an invite sent to another chain creates the game there and its acceptance is reported back
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_invite() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let mut invitee_chain = validator.new_chain().await;
+    let invitee = AccountOwner::from(invitee_chain.public_key());
+
+    let application_id = inviter_chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    inviter_chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Inviter".to_string(),
+                    eth_address: "0x3333333333333333333333333333333333333333".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::SendGameInvite {
+                    target_chain: invitee_chain.id(),
+                    invitee,
+                    game_type: GameType::Chess,
+                    timeouts: None,
+                });
+        })
+        .await;
+
+    invitee_chain.handle_received_messages().await;
+
+    let query = format!(r#"query {{ playerActiveGames(owner: "{invitee}") {{ gameId opponentName status }} }}"#);
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, query.clone()).await;
+    let game = &response["playerActiveGames"][0];
+    assert_eq!(game["opponentName"].as_str().unwrap(), "Inviter");
+    assert_eq!(game["status"].as_str().unwrap(), "WAITING_FOR_OPPONENT");
+    let game_id = game["gameId"].as_str().unwrap().to_string();
+
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AcceptGameInvite { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, query).await;
+    assert_eq!(response["playerActiveGames"][0]["status"].as_str().unwrap(), "IN_PROGRESS");
+
+    inviter_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = inviter_chain
+        .graphql_query(
+            application_id,
+            format!(r#"query {{ acceptedInvites(chainId: "{}") }}"#, invitee_chain.id()),
+        )
+        .await;
+    assert_eq!(response["acceptedInvites"][0].as_str().unwrap(), game_id);
+    assert!(game_id.starts_with(&format!("invite_{}_", inviter_chain.id())));
+}
+
+/// Tests that the inviter's moves reach the hosting chain and the result comes back to the inviter's chain
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_invite_relays_moves_and_result() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+
+    let game_id = send_invite(&inviter_chain, &invitee_chain, application_id, GameType::Chess).await;
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AcceptGameInvite { game_id: game_id.clone() });
+        })
+        .await;
+    inviter_chain.handle_received_messages().await;
+    assert_eq!(sent_invite_status(&inviter_chain, application_id, &game_id).await["status"], "IN_PROGRESS");
+
+    // The inviter plays White from their own chain
+    inviter_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ChessMove {
+                game_id: game_id.clone(),
+                from_square: 12,
+                to_square: 28,
+                promotion: None,
+            });
+        })
+        .await;
+    invitee_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ chessBoard(gameId: "{game_id}") {{ activePlayer }} }}"#))
+        .await;
+    assert_eq!(response["chessBoard"]["activePlayer"].as_str().unwrap(), "TWO");
+
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+    inviter_chain.handle_received_messages().await;
+
+    let invite = sent_invite_status(&inviter_chain, application_id, &game_id).await;
+    assert_eq!(invite["status"], "COMPLETED");
+    assert_eq!(invite["winner"], "ONE");
+    assert_eq!(invite["resultReason"], "RESIGNATION");
+}
+
+/// Tests that a declined invite is cancelled on both chains
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_invite_declined() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+
+    let game_id = send_invite(&inviter_chain, &invitee_chain, application_id, GameType::Chess).await;
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::DeclineGameInvite { game_id: game_id.clone() });
+        })
+        .await;
+    inviter_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "CANCELLED");
+    assert_eq!(sent_invite_status(&inviter_chain, application_id, &game_id).await["status"], "CANCELLED");
+}
+
+/// Tests that an invite can't be accepted once it has lapsed
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_invite_expires() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+
+    let game_id = send_invite(&inviter_chain, &invitee_chain, application_id, GameType::Chess).await;
+    validator.clock().add(TimeDelta::from_secs(86_400));
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AcceptGameInvite { game_id: game_id.clone() });
+        })
+        .await;
+    inviter_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "CANCELLED");
+    assert_eq!(sent_invite_status(&inviter_chain, application_id, &game_id).await["status"], "CANCELLED");
+}
+
+/// Accepts a poker invite and returns the game id, with the inviter on the first seat
+async fn start_poker_invite(
+    inviter_chain: &ActiveChain,
+    invitee_chain: &ActiveChain,
+    application_id: ApplicationId<game_platform::GamePlatformAbi>,
+) -> String {
+    let game_id = send_invite(inviter_chain, invitee_chain, application_id, GameType::Poker).await;
+    invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AcceptGameInvite { game_id: game_id.clone() });
+        })
+        .await;
+    game_id
+}
+
+/// Tests that a table between two players is only dealt once both have committed to and revealed their seeds
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_poker_deals_after_seed_reveals() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+    let game_id = start_poker_invite(&inviter_chain, &invitee_chain, application_id).await;
+
+    // The invitee sees their own hole cards once there are any, and never the inviter's
+    let invitee = AccountOwner::from(invitee_chain.public_key());
+    let hands_query = format!(r#"query {{ pokerGame(gameId: "{game_id}", viewer: "{invitee}") {{ playerHands {{ rank }} }} }}"#);
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, hands_query.clone()).await;
+    let hands = response["pokerGame"]["playerHands"].as_array().unwrap();
+    assert!(hands.iter().all(|hand| hand.as_array().unwrap().is_empty()));
+
+    // Nobody can act before the seeds are in
+    let fold = Operation::PokerAction { game_id: game_id.clone(), action: PokerAction::Fold, bet_amount: None };
+    submit(&invitee_chain, application_id, fold).await;
+
+    let commit = |seed: u64| Operation::CommitSeed { game_id: game_id.clone(), commitment: seed_commitment(seed) };
+    let reveal = |seed: u64| Operation::RevealSeed { game_id: game_id.clone(), seed };
+    submit(&inviter_chain, application_id, commit(11)).await;
+    invitee_chain.handle_received_messages().await;
+    submit(&invitee_chain, application_id, commit(22)).await;
+    submit(&inviter_chain, application_id, reveal(11)).await;
+    invitee_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, hands_query.clone()).await;
+    assert!(response["pokerGame"]["playerHands"][1].as_array().unwrap().is_empty());
+
+    submit(&invitee_chain, application_id, reveal(22)).await;
+
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, hands_query).await;
+    let hands = response["pokerGame"]["playerHands"].as_array().unwrap();
+    assert!(hands[0].as_array().unwrap().is_empty());
+    assert_eq!(hands[1].as_array().unwrap().len(), 2);
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ pokerGame(gameId: "{game_id}") {{ playerHands {{ rank }} deck {{ rank }} }} }}"#))
+        .await;
+    let hands = response["pokerGame"]["playerHands"].as_array().unwrap();
+    assert!(hands.iter().all(|hand| hand.as_array().unwrap().is_empty()));
+    assert!(response["pokerGame"]["deck"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a player who never commits a seed forfeits once the window has closed
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_seed_holdout_forfeits() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+    let game_id = start_poker_invite(&inviter_chain, &invitee_chain, application_id).await;
+
+    inviter_chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::CommitSeed {
+                    game_id: game_id.clone(),
+                    commitment: seed_commitment(11),
+                })
+                .with_operation(application_id, Operation::ClaimTimeout { game_id: game_id.clone() });
+        })
+        .await;
+    invitee_chain.handle_received_messages().await;
+
+    // Claimed too early, the table is still waiting
+    let status_query = format!(r#"query {{ game(gameId: "{game_id}") {{ status winner resultReason }} }}"#);
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, status_query.clone()).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+
+    validator.clock().add(TimeDelta::from_secs(300));
+    inviter_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ClaimTimeout { game_id: game_id.clone() });
+        })
+        .await;
+    invitee_chain.handle_received_messages().await;
+
+    let QueryOutcome { response, .. } = invitee_chain.graphql_query(application_id, status_query).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "TIMED_OUT");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "ONE");
+    assert_eq!(response["game"]["resultReason"].as_str().unwrap(), "TIMEOUT");
+
+    inviter_chain.handle_received_messages().await;
+    assert_eq!(sent_invite_status(&inviter_chain, application_id, &game_id).await["winner"], "ONE");
+}
+
+/// Tests that a player can report an opponent once per game and the list stays hidden from non-admins
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cross_chain_report_is_filed_once() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let inviter = AccountOwner::from(inviter_chain.public_key());
+    let invitee = AccountOwner::from(invitee_chain.public_key());
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+
+    let game_id = send_invite(&inviter_chain, &invitee_chain, application_id, GameType::Chess).await;
+    let report = || Operation::ReportPlayer {
+        game_id: game_id.clone(),
+        target: inviter,
+        reason: "Stalling".to_string(),
+    };
+    let certificate = invitee_chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::AcceptGameInvite { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true })
+                .with_operation(application_id, report())
+                .with_operation(application_id, report());
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses[2], GameOutcome::InProgress);
+    assert_eq!(responses[3], GameOutcome::Rejected("Player already reported for this game".to_string()));
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ reports(viewer: "{invitee}", limit: 10) {{ reportId }} }}"#))
+        .await;
+    assert!(response["reports"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a draw offer left unanswered past its expiry can no longer be accepted
+#[tokio::test(flavor = "multi_thread")]
+async fn test_expired_draw_offer_cannot_be_accepted() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut inviter_chain = validator.new_chain().await;
+    let invitee_chain = validator.new_chain().await;
+    let application_id = inviter_chain.create_application(module_id, (), (), vec![]).await;
+
+    let game_id = send_invite(&inviter_chain, &invitee_chain, application_id, GameType::Chess).await;
+    submit(&invitee_chain, application_id, Operation::AcceptGameInvite { game_id: game_id.clone() }).await;
+    inviter_chain.handle_received_messages().await;
+
+    submit(&inviter_chain, application_id, Operation::OfferDraw { game_id: game_id.clone() }).await;
+    invitee_chain.handle_received_messages().await;
+
+    // Offers lapse after two minutes by default
+    validator.clock().add(TimeDelta::from_secs(121));
+    let certificate = invitee_chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AcceptDraw { game_id: game_id.clone() });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses, vec![GameOutcome::Rejected("No draw offer to accept".to_string())]);
+
+    let QueryOutcome { response, .. } = invitee_chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+}
@@ -0,0 +1,500 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rule tests for the poker game logic.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    combine_seeds, seed_commitment, verify_seed_reveal, BettingMode, Card, GameOutcome, Player,
+    PokerAction, PokerGame, PokerStage, PokerStakes, PokerVariant, StreetPot, Suit, MAX_POKER_SEATS,
+};
+
+/// Tests that street contributions reflect a raise
+#[test]
+fn test_street_contributions_after_raise() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    game.make_action(game.active_player, PokerAction::Raise, Some(40), 1).unwrap();
+
+    let street = game.street_contributions();
+    assert_eq!(street.current_bet, 60);
+    assert_eq!(street.contributions[0].player, 0);
+    assert_eq!(street.contributions[0].amount, 60);
+    assert_eq!(street.contributions[1].amount, 20);
+}
+
+/// Tests that identical reveals produce identical decks
+#[test]
+fn test_identical_reveals_shuffle_identically() {
+    let mut first = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 1);
+    let mut second = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 2);
+
+    first.reseed(combine_seeds(&[11, 42])).unwrap();
+    second.reseed(combine_seeds(&[11, 42])).unwrap();
+
+    assert_eq!(first.deck, second.deck);
+    assert_eq!(first.player_hands, second.player_hands);
+}
+
+/// Tests that a reveal not matching the commitment is rejected
+#[test]
+fn test_wrong_reveal_rejected() {
+    let commitment = seed_commitment(42);
+
+    assert!(verify_seed_reveal(&commitment, 42));
+    assert!(!verify_seed_reveal(&commitment, 43));
+}
+
+/// Tests that the pot is snapshotted as each street closes
+#[test]
+fn test_pot_by_street() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+    assert_eq!(game.stage, PokerStage::Flop);
+
+    game.make_action(game.active_player, PokerAction::Raise, Some(20), 3).unwrap();
+    game.make_action(game.active_player, PokerAction::Call, None, 4).unwrap();
+
+    assert_eq!(
+        game.pot_by_street,
+        vec![
+            StreetPot { stage: PokerStage::PreFlop, pot: 40 },
+            StreetPot { stage: PokerStage::Flop, pot: 80 },
+        ]
+    );
+}
+
+/// Tests that heads-up the button acts first pre-flop and the big blind first on the flop
+#[test]
+fn test_heads_up_action_order() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    assert_eq!(game.dealer, 0);
+    assert_eq!(game.player_bets, vec![10, 20]);
+    assert_eq!(game.active_player, 0);
+
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    assert_eq!(game.active_player, 1);
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+
+    assert_eq!(game.stage, PokerStage::Flop);
+    assert_eq!(game.active_player, 1);
+}
+
+/// Tests that an action stamped earlier than the previous one is rejected
+#[test]
+fn test_out_of_order_action_rejected() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    game.make_action(game.active_player, PokerAction::Call, None, 100).unwrap();
+
+    assert!(game.make_action(game.active_player, PokerAction::Check, None, 50).is_err());
+}
+
+/// Tests that a short all-in does not let the earlier raiser re-raise
+#[test]
+fn test_short_all_in_does_not_reopen_betting() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    // Short-stacked big blind: 20 posted plus 50 behind
+    game.player_chips[1] = 50;
+
+    game.make_action(game.active_player, PokerAction::Raise, Some(40), 1).unwrap();
+    game.make_action(game.active_player, PokerAction::AllIn, None, 2).unwrap();
+    assert_eq!(game.current_bet, 70);
+    assert_eq!(game.last_raiser, Some(0));
+
+    assert!(game.make_action(game.active_player, PokerAction::Raise, Some(40), 3).is_err());
+    assert!(game.make_action(game.active_player, PokerAction::Call, None, 3).is_ok());
+}
+
+/// Tests that a four-hole-card variant deals four cards to each player
+#[test]
+fn test_four_hole_card_variant_deal() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Omaha, 7);
+
+    assert_eq!(game.player_hands.len(), 2);
+    assert!(game.player_hands.iter().all(|hand| hand.len() == 4));
+    assert_eq!(game.deck.len(), 52 - 8);
+    assert_eq!(game.required_hole_cards, Some(2));
+    assert_eq!(PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7).required_hole_cards, None);
+
+    game.reseed(11).unwrap();
+    assert!(game.player_hands.iter().all(|hand| hand.len() == 4));
+    assert_eq!(game.deck.len(), 52 - 8);
+}
+
+/// Tests that spectators see neither hole cards nor the deck before showdown
+#[test]
+fn test_spectator_view_hides_hole_cards() {
+    let game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 11);
+
+    let view = game.spectator_view();
+    assert!(view.player_hands.iter().all(|hand| hand.is_empty()));
+    assert!(view.deck.is_empty());
+    assert_eq!(view.pot, game.pot);
+    assert_eq!(game.player_hands[0].len(), 2);
+}
+
+/// Tests that a hand checked down lists the board street by street
+#[test]
+fn test_board_timeline_after_showdown() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 5);
+
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+    for ts in 3..9 {
+        game.make_action(game.active_player, PokerAction::Check, None, ts).unwrap();
+    }
+    assert_eq!(game.stage, PokerStage::Showdown);
+
+    let timeline = game.board_timeline();
+    let stages: Vec<PokerStage> = timeline.iter().map(|c| c.stage).collect();
+    assert_eq!(
+        stages,
+        vec![
+            PokerStage::Flop,
+            PokerStage::Flop,
+            PokerStage::Flop,
+            PokerStage::Turn,
+            PokerStage::River,
+        ]
+    );
+    let cards: Vec<_> = timeline.iter().map(|c| c.card).collect();
+    assert_eq!(cards, game.community_cards);
+}
+
+/// Tests that pot-limit caps a raise at the pot after calling
+#[test]
+fn test_pot_limit_caps_raise() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    game.betting_mode = BettingMode::PotLimit;
+
+    // Pot of 30 plus 10 to call allows at most 40 on top
+    assert!(game.make_action(game.active_player, PokerAction::Raise, Some(41), 1).is_err());
+    game.make_action(game.active_player, PokerAction::Raise, Some(40), 1).unwrap();
+    assert_eq!(game.current_bet, 60);
+}
+
+/// Tests that a won hand nets the winner the opponent's contribution
+#[test]
+fn test_chip_deltas_after_fold() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    game.make_action(game.active_player, PokerAction::Raise, Some(40), 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Fold, None, 2).unwrap();
+
+    assert_eq!(game.pot, 0);
+    // The 40 the big blind never matched goes back to the raiser
+    assert_eq!(game.awarded_pot, 40);
+    assert_eq!(game.player_chips, vec![1020, 980]);
+    assert_eq!(game.chip_deltas(Some(Player::One)), vec![20, -20]);
+    // A split pot returns each side what it put in when contributions match
+    let mut even = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    even.make_action(even.active_player, PokerAction::Call, None, 1).unwrap();
+    assert_eq!(even.chip_deltas(None), vec![0, 0]);
+}
+
+/// Tests that raises wrapping a chip count around u64::MAX are refused and leave the table as it was
+#[test]
+fn test_overflowing_raise_rejected() {
+    let mut game = PokerGame::new(u64::MAX - 10, 10, 20, PokerVariant::Holdem, 7);
+
+    let result = game.make_action(game.active_player, PokerAction::Raise, Some(u64::MAX), 1);
+    assert_eq!(result, Err("Chip count overflow".to_string()));
+
+    game.pot = u64::MAX - 5;
+    let result = game.make_action(game.active_player, PokerAction::Raise, Some(40), 1);
+    assert_eq!(result, Err("Chip count overflow".to_string()));
+    assert_eq!(game.pot, u64::MAX - 5);
+    assert_eq!(game.player_chips[game.active_player], u64::MAX - 20);
+    assert!(game.action_history.is_empty());
+}
+
+/// Tests that when two of three seats fold the last one wins pre-flop without a showdown
+#[test]
+fn test_three_player_uncontested_pot() {
+    let mut game = PokerGame::with_seats(3, 1000, 10, 20, PokerVariant::Holdem, 7);
+
+    game.make_action(game.active_player, PokerAction::Fold, None, 1).unwrap();
+    assert!(!game.is_hand_over());
+    let outcome = game.make_action(game.active_player, PokerAction::Fold, None, 2).unwrap();
+
+    assert!(game.is_hand_over());
+    assert_eq!(game.winners, vec![2]);
+    assert_eq!(game.stage, PokerStage::PreFlop);
+    assert!(game.community_cards.is_empty());
+    assert_eq!(game.pot, 0);
+    assert_eq!(game.player_chips, vec![1000, 990, 1010]);
+    assert_eq!(outcome, GameOutcome::SeatWinners(vec![2]));
+    assert!(game.make_action(game.active_player, PokerAction::Check, None, 3).is_err());
+}
+
+/// Tests a three-handed hand where the button folds and the blinds go to showdown
+#[test]
+fn test_three_player_hand_reaches_showdown() {
+    let mut game = PokerGame::with_seats(3, 1000, 10, 20, PokerVariant::Holdem, 7);
+    assert_eq!(game.player_bets, vec![0, 10, 20]);
+    assert_eq!(game.pot, 30);
+
+    // Left of the big blind is the button
+    assert_eq!(game.active_player, 0);
+    game.make_action(game.active_player, PokerAction::Fold, None, 1).unwrap();
+    assert_eq!(game.active_player, 1);
+    game.make_action(game.active_player, PokerAction::Call, None, 2).unwrap();
+    assert_eq!(game.active_player, 2);
+    game.make_action(game.active_player, PokerAction::Check, None, 3).unwrap();
+
+    // Post-flop the first live seat after the button opens
+    assert_eq!(game.stage, PokerStage::Flop);
+    assert_eq!(game.active_player, 1);
+
+    let mut outcome = GameOutcome::InProgress;
+    for ts in 4..10 {
+        outcome = game.make_action(game.active_player, PokerAction::Check, None, ts).unwrap();
+    }
+
+    assert_eq!(game.stage, PokerStage::Showdown);
+    assert_eq!(game.pot, 0);
+    assert_eq!(game.player_chips.iter().sum::<u64>(), 3000);
+    assert!(game.is_hand_over());
+    assert!(!game.winners.is_empty() && !game.winners.contains(&0));
+    // A multi-way hand names its winning seats
+    assert_eq!(outcome, GameOutcome::SeatWinners(game.winners.clone()));
+    assert!(game.make_action(game.active_player, PokerAction::Check, None, 10).is_err());
+}
+
+/// Tests that stakes whose full table would overflow a signed chip count are refused
+#[test]
+fn test_stakes_capped_by_full_table_chips() {
+    let stakes = |starting_chips| PokerStakes { starting_chips, small_blind: 10, big_blind: 20 };
+
+    assert!(stakes(1000).is_valid());
+    assert!(stakes(i64::MAX as u64 / MAX_POKER_SEATS as u64).is_valid());
+    assert!(!stakes(i64::MAX as u64 / MAX_POKER_SEATS as u64 + 1).is_valid());
+    assert!(!stakes(u64::MAX).is_valid());
+}
+
+/// Tests that a busted seat can rebuy once the hand is over and a new hand is dealt
+#[test]
+fn test_rebuy_after_bust_deals_new_hand() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    assert!(game.rebuy(1, 1000, 1000, 8).is_err());
+
+    // Aces against seven-deuce on a board that helps nobody
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(14, Suit::Spades), card(14, Suit::Hearts)],
+        vec![card(2, Suit::Diamonds), card(7, Suit::Clubs)],
+    ];
+    game.deck = vec![
+        card(3, Suit::Clubs),
+        card(8, Suit::Spades),
+        card(9, Suit::Hearts),
+        card(12, Suit::Diamonds),
+        card(13, Suit::Clubs),
+    ];
+    game.make_action(game.active_player, PokerAction::AllIn, None, 1).unwrap();
+    let outcome = game.make_action(game.active_player, PokerAction::AllIn, None, 2).unwrap();
+    assert_eq!(outcome, GameOutcome::Winner(Player::One));
+    assert_eq!(game.player_chips[1], 0);
+
+    assert!(game.rebuy(0, 1000, 1000, 8).is_err());
+    assert!(game.rebuy(1, 1500, 1000, 8).is_err());
+    // A stack big enough to overflow the table's chip count is refused
+    assert!(game.rebuy(1, u64::MAX, u64::MAX, 8).is_err());
+    game.rebuy(1, 1000, 1000, 8).unwrap();
+
+    // The button moves to the rebuying seat, which posts the small blind
+    assert!(!game.is_hand_over());
+    assert_eq!(game.stage, PokerStage::PreFlop);
+    assert_eq!(game.dealer, 1);
+    assert_eq!(game.pot, 30);
+    assert_eq!(game.player_chips, vec![1980, 990]);
+    assert_eq!(game.active_player, 1);
+    assert!(game.player_hands.iter().all(|hand| hand.len() == 2));
+
+    // The bought-back chips are the new hand's starting stack, not winnings
+    game.make_action(game.active_player, PokerAction::Fold, None, 3).unwrap();
+    assert_eq!(game.chip_deltas(None), vec![10, -10]);
+}
+
+/// Tests that a showdown where both players play the board splits the pot between them
+#[test]
+fn test_tied_showdown_splits_pot() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    // Broadway on the board with no flush possible, so neither hand plays
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(2, Suit::Spades), card(4, Suit::Diamonds)],
+        vec![card(2, Suit::Hearts), card(4, Suit::Clubs)],
+    ];
+    game.deck = vec![
+        card(10, Suit::Clubs),
+        card(11, Suit::Diamonds),
+        card(12, Suit::Hearts),
+        card(13, Suit::Spades),
+        card(14, Suit::Clubs),
+    ];
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+    let stacks = game.player_chips.clone();
+    let pot = game.pot;
+
+    let mut outcome = GameOutcome::InProgress;
+    for ts in 3..9 {
+        outcome = game.make_action(game.active_player, PokerAction::Check, None, ts).unwrap();
+    }
+
+    assert_eq!(outcome, GameOutcome::Draw);
+    assert_eq!(game.winners, vec![0, 1]);
+    assert_eq!(game.pot, 0);
+    assert_eq!(game.player_chips, vec![stacks[0] + pot / 2, stacks[1] + pot / 2]);
+}
+
+/// Tests that of two flushes the higher card takes the whole pot instead of splitting it
+#[test]
+fn test_higher_flush_wins_pot() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    // Three hearts on the board give both players a flush, ace-high against queen-high
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(14, Suit::Hearts), card(4, Suit::Hearts)],
+        vec![card(12, Suit::Hearts), card(7, Suit::Hearts)],
+    ];
+    game.deck = vec![
+        card(3, Suit::Diamonds),
+        card(11, Suit::Clubs),
+        card(9, Suit::Hearts),
+        card(6, Suit::Hearts),
+        card(2, Suit::Hearts),
+    ];
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+    let stacks = game.player_chips.clone();
+    let pot = game.pot;
+
+    let mut outcome = GameOutcome::InProgress;
+    for ts in 3..9 {
+        outcome = game.make_action(game.active_player, PokerAction::Check, None, ts).unwrap();
+    }
+
+    assert_eq!(outcome, GameOutcome::Winner(Player::One));
+    assert_eq!(game.winners, vec![0]);
+    assert_eq!(game.player_chips, vec![stacks[0] + pot, stacks[1]]);
+}
+
+/// Tests that the kicker decides between two players holding the same pair
+#[test]
+fn test_kicker_breaks_paired_board() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    // Both play the board's pair of eights with an ace; the king outkicks the queen
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(14, Suit::Spades), card(12, Suit::Hearts)],
+        vec![card(14, Suit::Clubs), card(13, Suit::Diamonds)],
+    ];
+    game.deck = vec![
+        card(2, Suit::Clubs),
+        card(4, Suit::Diamonds),
+        card(9, Suit::Hearts),
+        card(8, Suit::Spades),
+        card(8, Suit::Clubs),
+    ];
+    game.make_action(game.active_player, PokerAction::Call, None, 1).unwrap();
+    game.make_action(game.active_player, PokerAction::Check, None, 2).unwrap();
+
+    let mut outcome = GameOutcome::InProgress;
+    for ts in 3..9 {
+        outcome = game.make_action(game.active_player, PokerAction::Check, None, ts).unwrap();
+    }
+
+    assert_eq!(outcome, GameOutcome::Winner(Player::Two));
+    assert_eq!(game.winners, vec![1]);
+}
+
+/// Tests that checking while facing the big blind is refused with its own reason
+#[test]
+fn test_check_into_bet_rejected() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+
+    let err = game.make_action(game.active_player, PokerAction::Check, None, 1).unwrap_err();
+    assert_eq!(err, "Cannot check, must call or raise");
+    assert!(game.action_history.is_empty());
+}
+
+/// Tests that a seat acting out of turn is refused and leaves the hand untouched
+#[test]
+fn test_non_active_seat_cannot_act() {
+    let mut game = PokerGame::new(1000, 10, 20, PokerVariant::Holdem, 7);
+    let waiting = (game.active_player + 1) % 2;
+    let pot = game.pot;
+
+    let err = game.make_action(waiting, PokerAction::Call, None, 1).unwrap_err();
+    assert_eq!(err, "Not your turn");
+    assert_eq!(game.pot, pot);
+    assert_eq!(game.make_action(5, PokerAction::Call, None, 1).unwrap_err(), "No such seat");
+}
+
+/// Tests that a short all-in only wins the main pot while the side pot goes to the best of the deeper stacks
+#[test]
+fn test_short_all_in_wins_main_pot_only() {
+    let mut game = PokerGame::with_seats(3, 1000, 10, 20, PokerVariant::Holdem, 7);
+    game.player_chips[0] = 100;
+
+    // Aces, then kings, then seven-deuce, on a board that helps nobody
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(14, Suit::Spades), card(14, Suit::Hearts)],
+        vec![card(13, Suit::Spades), card(13, Suit::Hearts)],
+        vec![card(2, Suit::Diamonds), card(7, Suit::Clubs)],
+    ];
+    game.deck = vec![
+        card(3, Suit::Clubs),
+        card(8, Suit::Spades),
+        card(9, Suit::Hearts),
+        card(11, Suit::Diamonds),
+        card(4, Suit::Clubs),
+    ];
+    game.make_action(0, PokerAction::AllIn, None, 1).unwrap();
+    game.make_action(1, PokerAction::AllIn, None, 2).unwrap();
+    let outcome = game.make_action(2, PokerAction::Call, None, 3).unwrap();
+
+    assert_eq!(game.stage, PokerStage::Showdown);
+    assert_eq!(game.winners, vec![0, 1]);
+    assert_eq!(game.player_chips, vec![300, 1800, 0]);
+    assert_eq!(outcome, GameOutcome::SeatWinners(vec![0, 1]));
+}
+
+/// Tests that a hand paying every seat from different pots is not a draw
+#[test]
+fn test_uneven_pots_over_every_seat_are_not_a_draw() {
+    let mut game = PokerGame::with_seats(3, 1000, 10, 20, PokerVariant::Holdem, 7);
+    game.player_chips[0] = 100;
+
+    // Aces take the main pot and the two pairs of kings split the side pot
+    let card = |rank, suit| Card { rank, suit };
+    game.player_hands = vec![
+        vec![card(14, Suit::Spades), card(14, Suit::Hearts)],
+        vec![card(13, Suit::Spades), card(13, Suit::Hearts)],
+        vec![card(13, Suit::Diamonds), card(13, Suit::Clubs)],
+    ];
+    game.deck = vec![
+        card(3, Suit::Clubs),
+        card(8, Suit::Spades),
+        card(9, Suit::Hearts),
+        card(11, Suit::Diamonds),
+        card(4, Suit::Clubs),
+    ];
+    game.make_action(0, PokerAction::AllIn, None, 1).unwrap();
+    game.make_action(1, PokerAction::AllIn, None, 2).unwrap();
+    let outcome = game.make_action(2, PokerAction::Call, None, 3).unwrap();
+
+    assert_eq!(game.winners, vec![0, 1, 2]);
+    assert_eq!(game.player_chips, vec![300, 900, 900]);
+    assert_eq!(outcome, GameOutcome::SeatWinners(vec![0, 1, 2]));
+}
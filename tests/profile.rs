@@ -0,0 +1,109 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for user profile helpers.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    check_registration, validate_avatar_url, validate_username, UserProfile, AVATAR_URL_MAX_LEN,
+};
+use linera_sdk::linera_base_types::AccountOwner;
+
+/// Tests that merging profiles sums results and keeps the best streak and ELO
+#[test]
+fn test_merge_profiles() {
+    let mut kept = UserProfile::new("Main".to_string(), "0xabc".to_string(), String::new(), 10, 1200);
+    kept.chess_wins = 3;
+    kept.chess_losses = 1;
+    kept.total_games = 4;
+    kept.best_streak = 2;
+
+    let mut duplicate = UserProfile::new("Dupe".to_string(), "0xabc".to_string(), String::new(), 5, 1200);
+    duplicate.chess_wins = 2;
+    duplicate.poker_wins = 1;
+    duplicate.chess_elo = 1350;
+    duplicate.total_games = 3;
+    duplicate.best_streak = 3;
+
+    kept.merge_from(&duplicate);
+
+    assert_eq!(kept.username, "Main");
+    assert_eq!(kept.chess_wins, 5);
+    assert_eq!(kept.chess_losses, 1);
+    assert_eq!(kept.poker_wins, 1);
+    assert_eq!(kept.total_games, 7);
+    assert_eq!(kept.best_streak, 3);
+    assert_eq!(kept.chess_elo, 1350);
+    assert_eq!(kept.created_at, 5);
+    assert!(kept.answers_to("dupe"));
+}
+
+/// Tests that a username below the minimum length is rejected
+#[test]
+fn test_username_too_short() {
+    assert!(validate_username("ab").is_err());
+    assert!(validate_username("  ab  ").is_err());
+}
+
+/// Tests that emoji and other symbols are rejected
+#[test]
+fn test_username_with_emoji() {
+    assert!(validate_username("player🎲").is_err());
+    assert!(validate_username("two words").is_err());
+}
+
+/// Tests that a valid username is accepted and trimmed
+#[test]
+fn test_username_valid() {
+    assert_eq!(validate_username("  Grand_Master-1 ").unwrap(), "Grand_Master-1");
+}
+
+/// Tests that an https avatar URL is accepted and an empty one means no avatar
+#[test]
+fn test_avatar_url_valid() {
+    assert_eq!(
+        validate_avatar_url(" https://example.com/avatar.png ").unwrap(),
+        "https://example.com/avatar.png"
+    );
+    assert_eq!(validate_avatar_url("").unwrap(), "");
+}
+
+/// Tests that a javascript: URI is rejected
+#[test]
+fn test_avatar_url_javascript_rejected() {
+    assert!(validate_avatar_url("javascript:alert(1)").is_err());
+}
+
+/// Tests that an avatar URL over the length cap is rejected
+#[test]
+fn test_avatar_url_too_long() {
+    let url = format!("https://example.com/{}", "a".repeat(AVATAR_URL_MAX_LEN));
+    assert!(validate_avatar_url(&url).is_err());
+}
+
+/// Tests that re-registering an ETH address under a new name releases the old name
+#[test]
+fn test_reregistration_frees_old_username() {
+    let alice = AccountOwner::Address20([1; 20]);
+    let profile = UserProfile::new("Alice".to_string(), "0xAbC".to_string(), String::new(), 0, 1200);
+
+    let old = check_registration(&alice, "0xabc", "Alicia", Some(&alice), None, Some(&profile)).unwrap();
+    assert_eq!(old, Some("alice".to_string()));
+    // Changing only the case keeps the same mapping
+    assert_eq!(check_registration(&alice, "0xabc", "ALICE", Some(&alice), Some(&alice), Some(&profile)).unwrap(), None);
+}
+
+/// Tests that an ETH address or username held by another owner cannot be claimed
+#[test]
+fn test_registration_conflicting_owner_rejected() {
+    let alice = AccountOwner::Address20([1; 20]);
+    let bob = AccountOwner::Address20([2; 20]);
+    let profile = UserProfile::new("Bob".to_string(), "0xdef".to_string(), String::new(), 0, 1200);
+
+    assert!(check_registration(&bob, "0xabc", "Bob", Some(&alice), None, None).is_err());
+    assert!(check_registration(&bob, "0xdef", "Alice", Some(&bob), Some(&alice), Some(&profile)).is_err());
+    // An account keeps the ETH address it registered with
+    assert!(check_registration(&bob, "0xabc", "Bob", None, Some(&bob), Some(&profile)).is_err());
+    assert_eq!(check_registration(&bob, "0xdef", "Bob", Some(&bob), Some(&bob), Some(&profile)).unwrap(), None);
+}
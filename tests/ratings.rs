@@ -0,0 +1,82 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for the Elo rating helpers.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    bot_elo_adjustment, elo_delta, expected_score, find_matchmaking_opponent, win_probability,
+    GameDefaults, GameType, UserProfile, BOT_SEED_MAX_ADJUSTMENT,
+};
+
+/// Tests that an upset win moves both ratings by the expected amount
+#[test]
+fn test_underdog_win_rating_change() {
+    let expected = expected_score(1200, 1400);
+    assert!((expected - 0.2403).abs() < 0.001);
+
+    assert_eq!(elo_delta(1200, 1400, 1.0), 24);
+    assert_eq!(elo_delta(1400, 1200, 0.0), -24);
+}
+
+/// Tests that a draw between equals leaves ratings unchanged
+#[test]
+fn test_draw_between_equals() {
+    assert_eq!(elo_delta(1500, 1500, 0.5), 0);
+}
+
+/// Tests the reduced K-factor above 2100
+#[test]
+fn test_high_rated_k_factor() {
+    assert_eq!(elo_delta(2200, 2200, 1.0), 8);
+}
+
+/// Tests that matchmaking pairs the closest player inside the window
+#[test]
+fn test_matchmaking_pairs_close_ratings() {
+    assert_eq!(find_matchmaking_opponent(1200, &[1500, 1350, 1250]), Some(2));
+    assert_eq!(find_matchmaking_opponent(1200, &[1400]), Some(0));
+}
+
+/// Tests that no match forms when the rating gap is too large
+#[test]
+fn test_matchmaking_rejects_large_gap() {
+    assert_eq!(find_matchmaking_opponent(1200, &[1401, 950]), None);
+    assert_eq!(find_matchmaking_opponent(1200, &[]), None);
+}
+
+/// Tests that equal ratings give even odds and a higher rating is favoured
+#[test]
+fn test_win_probability() {
+    let a = UserProfile::new("alice".to_string(), "0xa".to_string(), String::new(), 0, 1200);
+    let mut b = UserProfile::new("bob".to_string(), "0xb".to_string(), String::new(), 0, 1200);
+
+    assert_eq!(win_probability(&a, &b, GameType::Chess), Some(0.5));
+
+    b.chess_elo = 1400;
+    assert!(win_probability(&b, &a, GameType::Chess).unwrap() > 0.5);
+    assert!(win_probability(&a, &b, GameType::Chess).unwrap() < 0.5);
+    assert_eq!(win_probability(&a, &b, GameType::Poker), None);
+}
+
+/// Tests that a bot record shifts the seed in proportion to margin and volume, within bounds
+#[test]
+fn test_bot_elo_adjustment_is_bounded() {
+    assert_eq!(bot_elo_adjustment(0, 0), 0);
+    assert_eq!(bot_elo_adjustment(5, 0), 50);
+    assert_eq!(bot_elo_adjustment(5, 5), 0);
+    assert_eq!(bot_elo_adjustment(100, 0), BOT_SEED_MAX_ADJUSTMENT);
+    assert_eq!(bot_elo_adjustment(0, 100), -BOT_SEED_MAX_ADJUSTMENT);
+}
+
+/// Tests that rating updates are held between the configured floor and ceiling
+#[test]
+fn test_elo_floor_and_ceiling() {
+    let defaults = GameDefaults { elo_floor: 400, elo_ceiling: 2500, ..GameDefaults::default() };
+
+    assert_eq!(defaults.apply_elo_delta(400, -16), 400);
+    assert_eq!(defaults.apply_elo_delta(410, -16), 400);
+    assert_eq!(defaults.apply_elo_delta(2490, 16), 2500);
+    assert_eq!(defaults.apply_elo_delta(1500, 16), 1516);
+}
@@ -5,8 +5,15 @@
 
 #![cfg(not(target_arch = "wasm32"))]
 
-use game_platform::{GameType, GameMode, Operation};
-use linera_sdk::test::{QueryOutcome, TestValidator};
+use game_platform::{
+    BlackjackStakes, GameCompletedEvent, GameDefaults, GameEndReason, GameMode, GameOutcome,
+    GameType, Operation, Player, PokerAction, PokerStakes, AVAILABILITY_TTL_MICROS,
+    LOBBY_TTL_MICROS,
+};
+use linera_sdk::{
+    linera_base_types::{Account, AccountOwner, Amount},
+    test::{QueryOutcome, TestValidator},
+};
 
 /// Tests user registration and querying
 #[tokio::test(flavor = "multi_thread")]
@@ -83,6 +90,11 @@ async fn test_create_lobby() {
                 is_public: true,
                 password: None,
                 time_control: 300,
+                max_players: 2,
+                wager: None,
+                creator_color: None,
+                poker_stakes: None,
+                allow_hints: None,
             });
         })
         .await;
@@ -97,6 +109,56 @@ async fn test_create_lobby() {
     assert_eq!(lobbies[0]["creatorName"].as_str().unwrap(), "LobbyCreator");
 }
 
+/// Tests that each lobby a player opens gets its own id
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lobby_ids_are_unique() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "TwoLobbies".to_string(),
+                eth_address: "0x4949494949494949494949494949494949494949".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    for game_type in [GameType::Chess, GameType::Poker] {
+        validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(1));
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateLobby {
+                    game_type,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+
+    let lobbies = response["openLobbies"].as_array().unwrap();
+    assert_eq!(lobbies.len(), 2);
+    assert_ne!(lobbies[0]["lobbyId"], lobbies[1]["lobbyId"]);
+}
+
 /// Tests recording bot game results
 #[tokio::test(flavor = "multi_thread")]
 async fn test_record_bot_game() {
@@ -126,6 +188,7 @@ async fn test_record_bot_game() {
                 game_type: GameType::Chess,
                 won: true,
                 moves: 25,
+                eth_address: "0x9876543210fedcba9876543210fedcba98765432".to_string(),
             });
         })
         .await;
@@ -137,6 +200,7 @@ async fn test_record_bot_game() {
                 game_type: GameType::Chess,
                 won: false,
                 moves: 30,
+                eth_address: "0x9876543210fedcba9876543210fedcba98765432".to_string(),
             });
         })
         .await;
@@ -192,3 +256,3826 @@ async fn test_global_stats() {
 
     assert_eq!(response["totalUsers"].as_i64().unwrap(), 1);
 }
+
+/// Tests that new users start at the configured ELO
+#[tokio::test(flavor = "multi_thread")]
+async fn test_configurable_starting_elo() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::UpdateGameDefaults {
+                defaults: GameDefaults { starting_elo: 800, ..GameDefaults::default() },
+            });
+        })
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Beginner".to_string(),
+                eth_address: "0x2222222222222222222222222222222222222222".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { userByEthAddress(ethAddress: "0x2222222222222222222222222222222222222222") { chessElo } }"#)
+        .await;
+
+    assert_eq!(response["userByEthAddress"]["chessElo"].as_i64().unwrap(), 800);
+}
+
+/// Tests that an admin force-resolve completes a game and records stats once
+#[tokio::test(flavor = "multi_thread")]
+async fn test_force_resolve() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3333333333333333333333333333333333333333";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "StuckPlayer".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateGame {
+                game_type: GameType::Chess,
+                game_mode: GameMode::VsFriend,
+                opponent: None,
+                creator_color: None,
+                timeouts: None,
+                allow_hints: None,
+                betting_mode: None,
+                poker_stakes: None,
+                hit_split_aces: None,
+                blackjack_stakes: None,
+                wager: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    for _ in 0..2 {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::ForceResolve {
+                    game_id: game_id.clone(),
+                    winner_seat: Some(0),
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} userByEthAddress(ethAddress: "{eth_address}") {{ chessWins totalGames }} }}"#))
+        .await;
+
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "ONE");
+    assert_eq!(response["userByEthAddress"]["chessWins"].as_i64().unwrap(), 1);
+    assert_eq!(response["userByEthAddress"]["totalGames"].as_i64().unwrap(), 1);
+}
+
+/// Tests resolving a username to every identifier of the user
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resolve_identity() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x4444444444444444444444444444444444444444";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Resolver".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { resolveIdentity(query: "resolver") { owner ethAddress username } }"#)
+        .await;
+    let by_name = &response["resolveIdentity"];
+    assert_eq!(by_name["ethAddress"].as_str().unwrap(), eth_address);
+    assert_eq!(by_name["username"].as_str().unwrap(), "Resolver");
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ resolveIdentity(query: "{eth_address}") {{ owner }} }}"#))
+        .await;
+    assert_eq!(response["resolveIdentity"]["owner"], by_name["owner"]);
+}
+
+/// Tests joining and leaving the matchmaking queue
+#[tokio::test(flavor = "multi_thread")]
+async fn test_matchmaking_queue() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Queued".to_string(),
+                eth_address: "0x5555555555555555555555555555555555555555".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::JoinMatchmaking {
+                game_type: GameType::Chess,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, "query { matchmakingQueue(gameType: CHESS) }")
+        .await;
+    assert_eq!(response["matchmakingQueue"].as_array().unwrap().len(), 1);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::LeaveMatchmaking {
+                game_type: GameType::Chess,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, "query { matchmakingQueue(gameType: CHESS) }")
+        .await;
+    assert!(response["matchmakingQueue"].as_array().unwrap().is_empty());
+}
+
+/// Tests that matchmaking compares the rating of the game queued for: a chess rating gap keeps players apart at
+/// the chess queue but not at the unrated poker one
+#[tokio::test(flavor = "multi_thread")]
+async fn test_matchmaking_uses_game_rating() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let first = AccountOwner::from(chain.public_key());
+
+    // A second key signs the other player's blocks on the same chain
+    let second_key = validator.new_chain().await.key_pair().copy();
+    let second = AccountOwner::from(second_key.public());
+    let first_key = chain.key_pair().copy();
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    // The first player registers at the default 1200, the second at 1500
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Novice".to_string(),
+                    eth_address: "0x4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::UpdateGameDefaults {
+                    defaults: GameDefaults { starting_elo: 1500, ..GameDefaults::default() },
+                });
+        })
+        .await;
+
+    chain.set_key_pair(second_key);
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Veteran".to_string(),
+                    eth_address: "0x5050505050505050505050505050505050505050".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Chess })
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Poker });
+        })
+        .await;
+
+    chain.set_key_pair(first_key);
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Chess })
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Poker });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    chess: matchmakingQueue(gameType: CHESS)
+                    poker: matchmakingQueue(gameType: POKER)
+                    playerActiveGames(owner: "{first}") {{ gameType }}
+                }}"#
+            ),
+        )
+        .await;
+    let chess_queue: Vec<&str> = response["chess"].as_array().unwrap().iter().map(|o| o.as_str().unwrap()).collect();
+    assert_eq!(chess_queue, vec![second.to_string(), first.to_string()]);
+    assert!(response["poker"].as_array().unwrap().is_empty());
+    let games = response["playerActiveGames"].as_array().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["gameType"].as_str().unwrap(), "POKER");
+
+    // A finished chess game between them moves the chess ratings
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateGame {
+                game_type: GameType::Chess,
+                game_mode: GameMode::VsFriend,
+                opponent: Some(second),
+                creator_color: None,
+                timeouts: None,
+                allow_hints: None,
+                betting_mode: None,
+                poker_stakes: None,
+                hit_split_aces: None,
+                blackjack_stakes: None,
+                wager: None,
+            });
+        })
+        .await;
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGames(owner: "{first}") {{ gameId gameType }} }}"#))
+        .await;
+    let chess_id = response["playerActiveGames"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|game| game["gameType"].as_str() == Some("CHESS"))
+        .unwrap()["gameId"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: chess_id.clone(), confirm: true });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            r#"query {
+                novice: userByEthAddress(ethAddress: "0x4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f4f") { chessElo }
+                veteran: userByEthAddress(ethAddress: "0x5050505050505050505050505050505050505050") { chessElo }
+            }"#,
+        )
+        .await;
+    assert!(response["novice"]["chessElo"].as_u64().unwrap() < 1200);
+    assert!(response["veteran"]["chessElo"].as_u64().unwrap() > 1500);
+}
+
+/// Tests that a rematch starts a new game with the seats swapped
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rematch_swaps_players() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x6666666666666666666666666666666666666666";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Rematcher".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    // The chain owner plays both seats
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateGame {
+                game_type: GameType::Chess,
+                game_mode: GameMode::VsFriend,
+                opponent: Some(owner),
+                creator_color: None,
+                timeouts: None,
+                allow_hints: None,
+                betting_mode: None,
+                poker_stakes: None,
+                hit_split_aces: None,
+                blackjack_stakes: None,
+                wager: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::ForceResolve {
+                    game_id: game_id.clone(),
+                    winner_seat: Some(0),
+                })
+                .with_operation(application_id, Operation::RequestRematch {
+                    game_id: game_id.clone(),
+                    swap_colors: true,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ players rematchGameId }} }}"#))
+        .await;
+    let original_players = response["game"]["players"].as_array().unwrap().clone();
+    let rematch_id = response["game"]["rematchGameId"].as_str().expect("Rematch not created").to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{rematch_id}") {{ players status }} }}"#))
+        .await;
+    let mut reversed = original_players;
+    reversed.reverse();
+    assert_eq!(response["game"]["players"].as_array().unwrap(), &reversed);
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+}
+
+/// Tests that a poker rematch is dealt at the finished table's blinds and buy-in
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rematch_keeps_poker_stakes() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x5454545454545454545454545454545454545454";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "HighRoller".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: Some(PokerStakes { starting_chips: 500, small_blind: 25, big_blind: 50 }),
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::ForceResolve {
+                    game_id: game_id.clone(),
+                    winner_seat: Some(0),
+                })
+                .with_operation(application_id, Operation::RequestRematch {
+                    game_id: game_id.clone(),
+                    swap_colors: true,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ rematchGameId }} }}"#))
+        .await;
+    let rematch_id = response["game"]["rematchGameId"].as_str().expect("Rematch not created").to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(r#"query {{ game(gameId: "{rematch_id}") {{ rules {{ poker {{ smallBlind bigBlind buyIn }} }} }} }}"#),
+        )
+        .await;
+    let poker = &response["game"]["rules"]["poker"];
+    assert_eq!(poker["smallBlind"].as_u64().unwrap(), 25);
+    assert_eq!(poker["bigBlind"].as_u64().unwrap(), 50);
+    assert_eq!(poker["buyIn"].as_u64().unwrap(), 500);
+}
+
+/// Creates a self-opponent chess game for the chain owner and returns its id
+async fn create_self_chess_game(
+    chain: &mut linera_sdk::test::ActiveChain,
+    application_id: linera_sdk::linera_base_types::ApplicationId<game_platform::GamePlatformAbi>,
+    username: &str,
+    eth_address: &str,
+) -> String {
+    let owner = AccountOwner::from(chain.public_key());
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: username.to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string()
+}
+
+/// Tests that declining a draw offer clears it
+#[tokio::test(flavor = "multi_thread")]
+async fn test_decline_draw() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Decliner",
+        "0x7777777777777777777777777777777777777777",
+    )
+    .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() });
+        })
+        .await;
+
+    let query = format!(r#"query {{ game(gameId: "{game_id}") {{ drawOfferedBy status }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["game"]["drawOfferedBy"].as_str(), Some("ONE"));
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::DeclineDraw { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["game"]["drawOfferedBy"].is_null());
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+}
+
+/// Tests that a chess move by the offering side withdraws its draw offer
+#[tokio::test(flavor = "multi_thread")]
+async fn test_draw_offer_cleared_by_move() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Mover",
+        "0x8888888888888888888888888888888888888888",
+    )
+    .await;
+
+    // White offers a draw, then plays e2-e4
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::ChessMove {
+                    game_id: game_id.clone(),
+                    from_square: 12,
+                    to_square: 28,
+                    promotion: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ drawOfferedBy }} }}"#))
+        .await;
+    assert!(response["game"]["drawOfferedBy"].is_null());
+}
+
+/// Tests that recent performance only counts games completed inside the window
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_recent_performance() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x9999999999999999999999999999999999999999";
+    let old_game_id = create_self_chess_game(&mut chain, application_id, "Recent", eth_address).await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ForceResolve {
+                game_id: old_game_id.clone(),
+                winner_seat: Some(0),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{old_game_id}") {{ updatedAt }} }}"#))
+        .await;
+    let since = response["game"]["updatedAt"].as_u64().unwrap();
+
+    // A second game finished later ends in a draw
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(60));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateGame {
+                game_type: GameType::Chess,
+                game_mode: GameMode::VsFriend,
+                opponent: Some(owner),
+                creator_color: None,
+                timeouts: None,
+                allow_hints: None,
+                betting_mode: None,
+                poker_stakes: None,
+                hit_split_aces: None,
+                blackjack_stakes: None,
+                wager: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let new_game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ForceResolve {
+                game_id: new_game_id.clone(),
+                winner_seat: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ playerRecentPerformance(ethAddress: "{eth_address}", sinceMicros: {since}) {{ wins losses draws games }} }}"#
+        ))
+        .await;
+    let performance = &response["playerRecentPerformance"];
+    assert_eq!(performance["games"].as_u64().unwrap(), 1);
+    assert_eq!(performance["wins"].as_u64().unwrap(), 0);
+    assert_eq!(performance["draws"].as_u64().unwrap(), 1);
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ playerRecentPerformance(ethAddress: "{eth_address}", sinceMicros: 0) {{ wins games }} }}"#
+        ))
+        .await;
+    assert_eq!(response["playerRecentPerformance"]["games"].as_u64().unwrap(), 2);
+    assert_eq!(response["playerRecentPerformance"]["wins"].as_u64().unwrap(), 1);
+}
+
+/// Tests that a three-seat lobby stays open until the third player joins
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lobby_waits_for_all_seats() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "TableHost".to_string(),
+                    eth_address: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 3,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+    let query = format!(r#"query {{ lobby(lobbyId: "{lobby_id}") {{ status players gameId }} }}"#);
+
+    // The chain owner fills the remaining seats one at a time
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::JoinLobby {
+                lobby_id: lobby_id.clone(),
+                password: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "OPEN");
+    assert_eq!(response["lobby"]["players"].as_array().unwrap().len(), 2);
+    assert!(response["lobby"]["gameId"].is_null());
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::JoinLobby {
+                lobby_id: lobby_id.clone(),
+                password: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "STARTED");
+    assert_eq!(response["lobby"]["players"].as_array().unwrap().len(), 3);
+    let game_id = response["lobby"]["gameId"].as_str().expect("Game not started").to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ players }} }}"#))
+        .await;
+    assert_eq!(response["game"]["players"].as_array().unwrap().len(), 3);
+}
+
+/// Tests that a poker player who lets the clock run out loses on a timeout claim
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poker_timeout_claim() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Staller".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    // The small blind calls, leaving the big blind to act
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::PokerAction {
+                game_id: game_id.clone(),
+                action: PokerAction::Call,
+                bet_amount: None,
+            });
+        })
+        .await;
+
+    // Claiming before the clock runs out does nothing
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ClaimTimeout { game_id: game_id.clone() });
+        })
+        .await;
+
+    let query = format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(400));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ClaimTimeout { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "TIMED_OUT");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "ONE");
+}
+
+/// Tests that the contract answers a human chess move with a bot move
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bot_replies_to_chess_move() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0xcccccccccccccccccccccccccccccccccccccccc";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "BotChallenger".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsBot,
+                    opponent: None,
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ChessMove {
+                game_id: game_id.clone(),
+                from_square: 12,
+                to_square: 28,
+                promotion: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ chessBoard(gameId: "{game_id}") {{ activePlayer moveHistory {{ fromSquare }} }} }}"#))
+        .await;
+    assert_eq!(response["chessBoard"]["activePlayer"].as_str().unwrap(), "ONE");
+    let history = response["chessBoard"]["moveHistory"].as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(history[1]["fromSquare"].as_u64().unwrap() >= 48);
+}
+
+/// Tests that resigning emits a completion event naming the winner
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resignation_emits_completion_event() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Resigner",
+        "0xdddddddddddddddddddddddddddddddddddddddd",
+    )
+    .await;
+
+    let certificate = chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let events: Vec<GameCompletedEvent> = certificate
+        .inner()
+        .block()
+        .body
+        .events
+        .iter()
+        .flatten()
+        .map(|event| bcs::from_bytes(&event.value).expect("Failed to decode event"))
+        .collect();
+
+    assert_eq!(events, vec![GameCompletedEvent {
+        game_id,
+        game_type: GameType::Chess,
+        winner: Some(Player::Two),
+        winning_seats: vec![1],
+        reason: GameEndReason::Resignation,
+    }]);
+}
+
+/// Tests that aborting a fresh game cancels it without recording a result
+#[tokio::test(flavor = "multi_thread")]
+async fn test_abort_fresh_game() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+    let game_id = create_self_chess_game(&mut chain, application_id, "Aborter", eth_address).await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AbortGame { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "CANCELLED");
+    assert!(response["game"]["winner"].is_null());
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ userByEthAddress(ethAddress: "{eth_address}") {{ chessWins chessLosses totalGames }} }}"#))
+        .await;
+    let profile = &response["userByEthAddress"];
+    assert_eq!(profile["chessWins"].as_u64().unwrap(), 0);
+    assert_eq!(profile["chessLosses"].as_u64().unwrap(), 0);
+    assert_eq!(profile["totalGames"].as_u64().unwrap(), 0);
+}
+
+/// Tests that a poker game's blinds and buy-in are stored in its rules
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poker_game_rules() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0xffffffffffffffffffffffffffffffffffffffff";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "RulesReader".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ gameRules(gameId: "{game_id}") {{ rated poker {{ smallBlind bigBlind buyIn holeCards }} blackjack {{ decks }} }} }}"#
+        ))
+        .await;
+    let rules = &response["gameRules"];
+    assert!(rules["rated"].as_bool().unwrap());
+    assert_eq!(rules["poker"]["smallBlind"].as_u64().unwrap(), 10);
+    assert_eq!(rules["poker"]["bigBlind"].as_u64().unwrap(), 20);
+    assert_eq!(rules["poker"]["buyIn"].as_u64().unwrap(), 1000);
+    assert_eq!(rules["poker"]["holeCards"].as_u64().unwrap(), 2);
+    assert!(rules["blackjack"].is_null());
+}
+
+/// Tests that a poker game created with custom stakes posts those blinds from those stacks
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poker_game_custom_stakes() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3535353535353535353535353535353535353535";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "HighRoller".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: Some(PokerStakes { starting_chips: 500, small_blind: 25, big_blind: 50 }),
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ pokerGame(gameId: "{game_id}") {{ pot playerChips smallBlind bigBlind }} gameRules(gameId: "{game_id}") {{ poker {{ buyIn }} }} }}"#
+        ))
+        .await;
+    let poker = &response["pokerGame"];
+    assert_eq!(poker["smallBlind"].as_u64().unwrap(), 25);
+    assert_eq!(poker["bigBlind"].as_u64().unwrap(), 50);
+    assert_eq!(poker["pot"].as_u64().unwrap(), 75);
+    // Heads-up the button posts the small blind
+    let chips: Vec<u64> = poker["playerChips"].as_array().unwrap().iter().map(|c| c.as_u64().unwrap()).collect();
+    assert_eq!(chips, vec![475, 450]);
+    assert_eq!(response["gameRules"]["poker"]["buyIn"].as_u64().unwrap(), 500);
+}
+
+/// Tests that a blackjack game created with a custom bet draws it from the chosen bankroll
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blackjack_game_custom_stakes() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3636363636363636363636363636363636363636";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "CardCounter".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Blackjack,
+                    game_mode: GameMode::VsBot,
+                    opponent: None,
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: Some(BlackjackStakes { bet: 50, bankroll: 250 }),
+                    wager: None,
+                });
+        })
+        .await;
+
+    // A natural on the opening deal finishes the game straight away
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ playerActiveGames(owner: "{owner}") {{ gameId }} playerGameHistory(owner: "{owner}", limit: 1) {{ gameId }} }}"#
+        ))
+        .await;
+    let game = response["playerActiveGames"].get(0).unwrap_or(&response["playerGameHistory"][0]);
+    let game_id = game["gameId"].as_str().unwrap().to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(
+            r#"query {{ blackjackGame(gameId: "{game_id}") {{ bets playerChips results }} gameRules(gameId: "{game_id}") {{ blackjack {{ bet bankroll }} }} }}"#
+        ))
+        .await;
+    let rules = &response["gameRules"]["blackjack"];
+    assert_eq!(rules["bet"].as_u64().unwrap(), 50);
+    assert_eq!(rules["bankroll"].as_u64().unwrap(), 250);
+    let blackjack = &response["blackjackGame"];
+    assert_eq!(blackjack["bets"][0].as_u64().unwrap(), 50);
+    // Until the hand settles the bet is still on the table; a natural settled on the deal has been paid out
+    let expected_chips = match blackjack["results"][0].as_str() {
+        Some("BLACKJACK") => 325,
+        Some("PUSH") => 250,
+        _ => 200,
+    };
+    assert_eq!(blackjack["playerChips"].as_u64().unwrap(), expected_chips);
+}
+
+/// Tests that a chess move record carries the signer who made it
+#[tokio::test(flavor = "multi_thread")]
+async fn test_move_record_carries_signer() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Signer",
+        "0x1212121212121212121212121212121212121212",
+    )
+    .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ChessMove {
+                game_id: game_id.clone(),
+                from_square: 12,
+                to_square: 28,
+                promotion: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ chessBoard(gameId: "{game_id}") {{ moveHistory {{ byOwner }} }} }}"#))
+        .await;
+    let expected = format!("{:?}", owner);
+    assert_eq!(response["chessBoard"]["moveHistory"][0]["byOwner"].as_str(), Some(expected.as_str()));
+}
+
+/// Tests that a private lobby rejects a wrong password and accepts the right one, starting a game with its rules
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lobby_password_checked() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Gatekeeper".to_string(),
+                    eth_address: "0x1313131313131313131313131313131313131313".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: Some("open-sesame".to_string()),
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: Some(false),
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+    let query = format!(r#"query {{ lobby(lobbyId: "{lobby_id}") {{ status players gameId }} }}"#);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::JoinLobby {
+                lobby_id: lobby_id.clone(),
+                password: Some("wrong".to_string()),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "OPEN");
+    assert_eq!(response["lobby"]["players"].as_array().unwrap().len(), 1);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::JoinLobby {
+                lobby_id: lobby_id.clone(),
+                password: Some("open-sesame".to_string()),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "STARTED");
+
+    // The lobby's hint setting carries over to the game it started
+    let game_id = response["lobby"]["gameId"].as_str().unwrap();
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ gameRules(gameId: "{game_id}") {{ allowHints }} }}"#))
+        .await;
+    assert!(!response["gameRules"]["allowHints"].as_bool().unwrap());
+}
+
+/// Tests that a player blocked by the lobby's creator cannot take a seat
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blocked_player_cannot_join_lobby() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    // A second key signs the joiner's blocks on the same chain
+    let joiner_key = validator.new_chain().await.key_pair().copy();
+    let joiner = AccountOwner::from(joiner_key.public());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Bouncer".to_string(),
+                    eth_address: "0x4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c4c".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::BlockPlayer { target: joiner })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+
+    chain.set_key_pair(joiner_key);
+    let certificate = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Gatecrasher".to_string(),
+                    eth_address: "0x4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d4d".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::JoinLobby { lobby_id: lobby_id.clone(), password: None });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses[1], GameOutcome::Rejected("A block is in place with a lobby member".to_string()));
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ lobby(lobbyId: "{lobby_id}") {{ status players }} }}"#))
+        .await;
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "OPEN");
+    assert_eq!(response["lobby"]["players"].as_array().unwrap().len(), 1);
+}
+
+/// Tests that platform stats agree with the individual counters
+#[tokio::test(flavor = "multi_thread")]
+async fn test_platform_stats() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Economist".to_string(),
+                    eth_address: "0x1414141414141414141414141414141414141414".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    // Game ids come from the block time, so create one game per block
+    for (game_type, game_mode, opponent) in [
+        (GameType::Chess, GameMode::VsFriend, Some(owner)),
+        (GameType::Poker, GameMode::VsFriend, Some(owner)),
+        (GameType::Blackjack, GameMode::VsBot, None),
+    ] {
+        validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(1));
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateGame {
+                    game_type,
+                    game_mode,
+                    opponent,
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query {
+            totalUsers
+            totalGamesPlayed
+            openLobbies { lobbyId }
+            platformStats {
+                totalUsers totalGames chessGames pokerGames blackjackGames
+                activeLobbies chipsInPlay houseBalance gamesScanned
+            }
+        }"#)
+        .await;
+
+    let stats = &response["platformStats"];
+    assert_eq!(stats["totalUsers"].as_u64(), response["totalUsers"].as_u64());
+    assert_eq!(stats["totalGames"].as_u64(), response["totalGamesPlayed"].as_u64());
+    assert_eq!(stats["totalGames"].as_u64().unwrap(), 3);
+    assert_eq!(stats["chessGames"].as_u64().unwrap(), 1);
+    assert_eq!(stats["pokerGames"].as_u64().unwrap(), 1);
+    assert_eq!(stats["blackjackGames"].as_u64().unwrap(), 1);
+    assert_eq!(stats["activeLobbies"].as_u64().unwrap() as usize, response["openLobbies"].as_array().unwrap().len());
+    assert_eq!(stats["gamesScanned"].as_u64().unwrap(), 3);
+    // Two poker stacks of 1000 plus a blackjack bankroll of 1000
+    assert_eq!(stats["chipsInPlay"].as_u64().unwrap(), 3000);
+    assert_eq!(stats["houseBalance"].as_i64().unwrap(), 0);
+}
+
+/// Tests that a lobby past its TTL is expired and leaves the open list
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lobby_expires() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Impatient".to_string(),
+                    eth_address: "0x1515151515151515151515151515151515151515".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_micros(LOBBY_TTL_MICROS + 1_000_000));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::PruneLobbies);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ openLobbies {{ lobbyId }} lobby(lobbyId: "{lobby_id}") {{ status }} }}"#))
+        .await;
+    assert!(response["openLobbies"].as_array().unwrap().is_empty());
+    assert_eq!(response["lobby"]["status"].as_str().unwrap(), "EXPIRED");
+}
+
+/// Tests that a game created without hints refuses to list valid moves
+#[tokio::test(flavor = "multi_thread")]
+async fn test_hints_disabled_hides_valid_moves() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "NoHints".to_string(),
+                    eth_address: "0x1616161616161616161616161616161616161616".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: Some(false),
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { playerActiveGamesByEth(ethAddress: "0x1616161616161616161616161616161616161616") { gameId } }"#)
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    // e2 has two legal pawn pushes, but hints are off for this game
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ chessValidMoves(gameId: "{game_id}", square: 12) }}"#))
+        .await;
+    assert!(response["chessValidMoves"].as_array().unwrap().is_empty());
+}
+
+/// Tests that spectating a chess game adds the caller to its spectator list
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spectate_game() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Watcher",
+        "0x1717171717171717171717171717171717171717",
+    )
+    .await;
+
+    // The test chain has a single signer, so it stands in for the watching account
+    let watcher = AccountOwner::from(chain.public_key());
+    for _ in 0..2 {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::SpectateGame { game_id: game_id.clone() });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ spectators(gameId: "{game_id}") }}"#))
+        .await;
+    let spectators = response["spectators"].as_array().unwrap();
+    assert_eq!(spectators.len(), 1);
+    assert_eq!(spectators[0].as_str().unwrap(), watcher.to_string());
+}
+
+/// Tests that the admin can add time to a player's clock
+#[tokio::test(flavor = "multi_thread")]
+async fn test_add_time_extends_clock() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Disputed",
+        "0x1818181818181818181818181818181818181818",
+    )
+    .await;
+
+    let query = format!(r#"query {{ timeRemaining(gameId: "{game_id}") }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    let before = response["timeRemaining"][1].as_i64().unwrap();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AddTime {
+                game_id: game_id.clone(),
+                seat: 1,
+                seconds: 60,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["timeRemaining"][1].as_i64().unwrap(), before + 60);
+}
+
+/// Tests that the admin can reach the third seat of a poker table and settle every seat
+#[tokio::test(flavor = "multi_thread")]
+async fn test_force_resolve_three_seat_table() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "ThirdSeat".to_string(),
+                    eth_address: "0x5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 3,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+
+    for _ in 0..2 {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::JoinLobby {
+                    lobby_id: lobby_id.clone(),
+                    password: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ lobby(lobbyId: "{lobby_id}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["lobby"]["gameId"].as_str().expect("Game not started").to_string();
+
+    let query = format!(r#"query {{ timeRemaining(gameId: "{game_id}") }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    let before = response["timeRemaining"][2].as_i64().unwrap();
+
+    let (_, certificate) = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::AddTime {
+                    game_id: game_id.clone(),
+                    seat: 2,
+                    seconds: 60,
+                })
+                .with_operation(application_id, Operation::AddTime {
+                    game_id: game_id.clone(),
+                    seat: 3,
+                    seconds: 60,
+                });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses[1], GameOutcome::Rejected("No such seat".to_string()));
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["timeRemaining"][2].as_i64().unwrap(), before + 60);
+
+    let (_, certificate) = chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ForceResolve {
+                game_id: game_id.clone(),
+                winner_seat: Some(2),
+            });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses, vec![GameOutcome::SeatWinners(vec![2])]);
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+}
+
+/// Tests that a three-seat table takes no draw offers and a resignation gives the win to every other seat
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resign_three_seat_table() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let second_key = validator.new_chain().await.key_pair().copy();
+    let third_key = validator.new_chain().await.key_pair().copy();
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "ResignHost".to_string(),
+                    eth_address: "0x5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f5f".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 3,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId } }"#)
+        .await;
+    let lobby_id = response["openLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+
+    for (key, username, eth_address) in [
+        (second_key.copy(), "ResignSecond", "0x6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a6a"),
+        (third_key, "ResignThird", "0x6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b6b"),
+    ] {
+        chain.set_key_pair(key);
+        chain
+            .add_block(|block| {
+                block
+                    .with_operation(application_id, Operation::RegisterUser {
+                        username: username.to_string(),
+                        eth_address: eth_address.to_string(),
+                        avatar_url: "".to_string(),
+                    })
+                    .with_operation(application_id, Operation::JoinLobby {
+                        lobby_id: lobby_id.clone(),
+                        password: None,
+                    });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ lobby(lobbyId: "{lobby_id}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["lobby"]["gameId"].as_str().expect("Game not started").to_string();
+
+    // The middle seat cannot settle the table with a draw, so it resigns
+    chain.set_key_pair(second_key);
+    let (_, certificate) = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::ResignGame {
+                    game_id: game_id.clone(),
+                    confirm: true,
+                });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(
+        responses,
+        vec![
+            GameOutcome::Rejected("Draws can only be agreed heads-up".to_string()),
+            GameOutcome::SeatWinners(vec![0, 2]),
+        ],
+    );
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+}
+
+/// Tests that chat messages are stored and read back in order
+#[tokio::test(flavor = "multi_thread")]
+async fn test_game_chat_in_order() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Chatty",
+        "0x1919191919191919191919191919191919191919",
+    )
+    .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::SendChatMessage {
+                    game_id: game_id.clone(),
+                    text: "good luck".to_string(),
+                })
+                .with_operation(application_id, Operation::SendChatMessage {
+                    game_id: game_id.clone(),
+                    text: "have fun".to_string(),
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ gameChat(gameId: "{game_id}", limit: 10) {{ senderName text }} }}"#))
+        .await;
+    let chat = response["gameChat"].as_array().unwrap();
+    assert_eq!(chat.len(), 2);
+    assert_eq!(chat[0]["text"].as_str().unwrap(), "good luck");
+    assert_eq!(chat[1]["text"].as_str().unwrap(), "have fun");
+    assert_eq!(chat[0]["senderName"].as_str().unwrap(), "Chatty");
+}
+
+/// Tests that the fifty-move rule can be claimed once fifty moves pass without a capture or pawn move, and not before
+#[tokio::test(flavor = "multi_thread")]
+async fn test_claim_fifty_move_rule() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Patient",
+        "0x4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e4e",
+    )
+    .await;
+
+    // Each side tours a knight around its own squares; the tours only line up again after 56 moves, so no
+    // position repeats on the way to the hundredth halfmove
+    let white_tour = [6, 23, 38, 28, 34, 19, 36, 21];
+    let black_tour = [62, 47, 37, 43, 26, 41, 35, 25, 42, 27, 44, 29, 39, 45];
+    let halfmoves: Vec<(u8, u8)> = (0..50)
+        .flat_map(|i| {
+            let white = (white_tour[i % white_tour.len()], white_tour[(i + 1) % white_tour.len()]);
+            let black = (black_tour[i % black_tour.len()], black_tour[(i + 1) % black_tour.len()]);
+            [white, black]
+        })
+        .collect();
+    let chess_move = |(from_square, to_square): (u8, u8)| Operation::ChessMove {
+        game_id: game_id.clone(),
+        from_square,
+        to_square,
+        promotion: None,
+    };
+    let claim = || Operation::ClaimFiftyMoveRule { game_id: game_id.clone() };
+
+    // One halfmove short, the claim is refused
+    let certificate = chain
+        .add_block(|block| {
+            for &halfmove in &halfmoves[..99] {
+                block.with_operation(application_id, chess_move(halfmove));
+            }
+            block.with_operation(application_id, claim());
+        })
+        .await;
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert!(responses[..99].iter().all(|outcome| *outcome == GameOutcome::InProgress));
+    assert_eq!(
+        responses[99],
+        GameOutcome::Rejected("Fifty moves have not passed without a capture or pawn move".to_string()),
+    );
+
+    let certificate = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, chess_move(halfmoves[99]))
+                .with_operation(application_id, claim());
+        })
+        .await;
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses, vec![GameOutcome::InProgress, GameOutcome::Draw]);
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status winner resultReason }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+    assert!(response["game"]["winner"].is_null());
+    assert_eq!(response["game"]["resultReason"].as_str().unwrap(), "FIFTY_MOVE");
+}
+
+/// Tests that draw status reports a threefold repetition in chess and nothing for poker
+#[tokio::test(flavor = "multi_thread")]
+async fn test_game_draw_status() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x2020202020202020202020202020202020202020";
+    let chess_id = create_self_chess_game(&mut chain, application_id, "Shuffler", eth_address).await;
+
+    // Both knights go out and back twice, repeating the starting position a third time
+    let knight_moves = [(6, 21), (62, 45), (21, 6), (45, 62)];
+    chain
+        .add_block(|block| {
+            for _ in 0..2 {
+                for (from_square, to_square) in knight_moves {
+                    block.with_operation(application_id, Operation::ChessMove {
+                        game_id: chess_id.clone(),
+                        from_square,
+                        to_square,
+                        promotion: None,
+                    });
+                }
+            }
+        })
+        .await;
+
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(1));
+    let owner = AccountOwner::from(chain.public_key());
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateGame {
+                game_type: GameType::Poker,
+                game_mode: GameMode::VsFriend,
+                opponent: Some(owner),
+                creator_color: None,
+                timeouts: None,
+                allow_hints: None,
+                betting_mode: None,
+                poker_stakes: None,
+                hit_split_aces: None,
+                blackjack_stakes: None,
+                wager: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId gameType }} }}"#))
+        .await;
+    let poker_id = response["playerActiveGamesByEth"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|g| g["gameType"] == "POKER")
+        .unwrap()["gameId"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let query = |game_id: &str| format!(r#"query {{ gameDrawStatus(gameId: "{game_id}") {{ conditions repetitionCount }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query(&chess_id)).await;
+    assert_eq!(response["gameDrawStatus"]["repetitionCount"].as_u64().unwrap(), 3);
+    assert_eq!(response["gameDrawStatus"]["conditions"][0].as_str().unwrap(), "THREEFOLD_REPETITION");
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query(&poker_id)).await;
+    assert!(response["gameDrawStatus"]["conditions"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a wagered game escrows both stakes and pays the pot to the winner
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wager_pays_winner() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let chain_id = chain.id();
+    let eth_address = "0x2121212121212121212121212121212121212121";
+    chain
+        .add_block(|block| {
+            block
+                .with_native_token_transfer(
+                    AccountOwner::CHAIN,
+                    Account { chain_id, owner },
+                    Amount::from_tokens(5),
+                )
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "HighRoller".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: Some(Amount::ONE),
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId status }} }}"#))
+        .await;
+    let game = &response["playerActiveGamesByEth"][0];
+    assert_eq!(game["status"].as_str().unwrap(), "WAITING_FOR_OPPONENT");
+    let game_id = game["gameId"].as_str().unwrap().to_string();
+
+    // The opponent seat escrows its stake, which starts the game
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::FundWager { game_id: game_id.clone() });
+        })
+        .await;
+    let funded_balance = chain.owner_balance(&owner).await.unwrap();
+    assert_eq!(funded_balance, Amount::from_tokens(3));
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let final_balance = chain.owner_balance(&owner).await.unwrap();
+    assert_eq!(final_balance, funded_balance.saturating_add(Amount::from_tokens(2)));
+}
+
+/// Tests that a settled wagered game takes no draw offers and refunds nothing more
+#[tokio::test(flavor = "multi_thread")]
+async fn test_finished_wager_rejects_draw() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let chain_id = chain.id();
+    let eth_address = "0x5151515151515151515151515151515151515151";
+    chain
+        .add_block(|block| {
+            block
+                .with_native_token_transfer(
+                    AccountOwner::CHAIN,
+                    Account { chain_id, owner },
+                    Amount::from_tokens(5),
+                )
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "LateDrawer".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: Some(Amount::ONE),
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    // An offer left open when the game is resigned goes with it
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::FundWager { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+    let settled_balance = chain.owner_balance(&owner).await.unwrap();
+
+    let certificate = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::AcceptDraw { game_id: game_id.clone() });
+        })
+        .await;
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert!(responses
+        .iter()
+        .all(|outcome| *outcome == GameOutcome::Rejected("Game is not in progress".to_string())));
+    assert_eq!(responses.len(), 2);
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status drawOfferedBy }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+    assert!(response["game"]["drawOfferedBy"].is_null());
+    assert_eq!(chain.owner_balance(&owner).await.unwrap(), settled_balance);
+}
+
+/// Tests that the first win unlocks its achievement exactly once
+#[tokio::test(flavor = "multi_thread")]
+async fn test_first_win_achievement_unlocks_once() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let eth_address = "0x2222333322223333222233332222333322223333";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Rookie".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    for _ in 0..2 {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::RecordBotGame {
+                    game_type: GameType::Chess,
+                    won: true,
+                    moves: 30,
+                    eth_address: eth_address.to_string(),
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerAchievements(owner: "{owner}") {{ kind }} }}"#))
+        .await;
+    let achievements = response["playerAchievements"].as_array().unwrap();
+    assert_eq!(achievements.len(), 1);
+    assert_eq!(achievements[0]["kind"].as_str().unwrap(), "FIRST_WIN");
+}
+
+/// Tests that an unconfirmed resign is ignored and a confirmed one ends the game
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resign_requires_confirmation() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Hesitant",
+        "0x2323232323232323232323232323232323232323",
+    )
+    .await;
+    let query = format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} }}"#);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: false });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+    assert!(response["game"]["winner"].is_null());
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "COMPLETED");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "TWO");
+}
+
+/// Tests filtering games down to in-progress chess
+#[tokio::test(flavor = "multi_thread")]
+async fn test_games_by_filter() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let resigned_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Filterer",
+        "0x2424242424242424242424242424242424242424",
+    )
+    .await;
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: resigned_id.clone(), confirm: true });
+        })
+        .await;
+
+    // Game ids come from the block time, so create one game per block
+    let owner = AccountOwner::from(chain.public_key());
+    for game_type in [GameType::Chess, GameType::Poker] {
+        validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(1));
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateGame {
+                    game_type,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { gamesByFilter(gameType: CHESS, status: IN_PROGRESS, limit: 10) { gameId gameType status } }"#)
+        .await;
+    let games = response["gamesByFilter"].as_array().unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0]["gameType"].as_str().unwrap(), "CHESS");
+    assert_eq!(games[0]["status"].as_str().unwrap(), "IN_PROGRESS");
+    assert_ne!(games[0]["gameId"].as_str().unwrap(), resigned_id);
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { gamesByFilter(limit: 10) { gameId } }"#)
+        .await;
+    assert_eq!(response["gamesByFilter"].as_array().unwrap().len(), 3);
+}
+
+/// Tests that deleting an account frees its username and removes the profile
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delete_account_frees_username() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Leaving".to_string(),
+                eth_address: "0x2525252525252525252525252525252525252525".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    let query = r#"query {
+        isUsernameAvailable(username: "Leaving")
+        userByEthAddress(ethAddress: "0x2525252525252525252525252525252525252525") { username }
+        totalUsers
+    }"#;
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(!response["isUsernameAvailable"].as_bool().unwrap());
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::DeleteAccount);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["isUsernameAvailable"].as_bool().unwrap());
+    assert!(response["userByEthAddress"].is_null());
+    assert_eq!(response["totalUsers"].as_u64().unwrap(), 0);
+}
+
+/// Tests that deleting an account cancels its lobby and withdraws it from queues and tournament registration
+#[tokio::test(flavor = "multi_thread")]
+async fn test_delete_account_withdraws_from_lobbies_queues_and_tournaments() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Quitter".to_string(),
+                    eth_address: "0x5151515151515151515151515151515151515151".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                })
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Poker });
+        })
+        .await;
+
+    // Past the rate limit, so the tournament is not turned away
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(2));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::CreateTournament {
+                game_type: GameType::Chess,
+                max_participants: 4,
+                allow_hints: None,
+            });
+        })
+        .await;
+
+    let query = r#"query {
+        openLobbies { lobbyId }
+        matchmakingQueue(gameType: POKER)
+        activeTournaments { registeredPlayers }
+    }"#;
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["openLobbies"].as_array().unwrap().len(), 1);
+    assert_eq!(response["matchmakingQueue"].as_array().unwrap().len(), 1);
+    assert_eq!(response["activeTournaments"][0]["registeredPlayers"].as_u64().unwrap(), 1);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::DeleteAccount);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["openLobbies"].as_array().unwrap().is_empty());
+    assert!(response["matchmakingQueue"].as_array().unwrap().is_empty());
+    assert_eq!(response["activeTournaments"][0]["registeredPlayers"].as_u64().unwrap(), 0);
+}
+
+/// Tests that merging accounts points the merged username at the kept account and moves its game seats,
+/// achievements and blocks, once the merged account has left the matchmaking queue
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merge_accounts_repoints_username() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let alt_key = validator.new_chain().await.key_pair().copy();
+    let alt = AccountOwner::from(alt_key.public());
+    let rival = AccountOwner::from(validator.new_chain().await.public_key());
+    let main_key = chain.key_pair().copy();
+    let main = AccountOwner::from(main_key.public());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Main".to_string(),
+                eth_address: "0x5252525252525252525252525252525252525252".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    chain.set_key_pair(alt_key);
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Alt".to_string(),
+                    eth_address: "0x5353535353535353535353535353535353535353".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsBot,
+                    opponent: None,
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                })
+                .with_operation(application_id, Operation::RecordBotGame {
+                    game_type: GameType::Chess,
+                    won: true,
+                    moves: 20,
+                    eth_address: "0x5353535353535353535353535353535353535353".to_string(),
+                })
+                .with_operation(application_id, Operation::BlockPlayer { target: rival })
+                .with_operation(application_id, Operation::JoinMatchmaking { game_type: GameType::Chess });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGames(owner: "{alt}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGames"][0]["gameId"].as_str().unwrap().to_string();
+
+    // The chain creator is the admin; a queued account can't be merged away
+    chain.set_key_pair(main_key.copy());
+    let merge = Operation::MergeAccounts { keep_owner: main, merge_owner: alt };
+    let certificate = chain
+        .add_block(|block| {
+            block.with_operation(application_id, merge.clone());
+        })
+        .await;
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(
+        responses,
+        vec![GameOutcome::Rejected("Account is still in a lobby, queue, tournament or match".to_string())],
+    );
+
+    chain.set_key_pair(alt_key);
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::LeaveMatchmaking { game_type: GameType::Chess });
+        })
+        .await;
+
+    chain.set_key_pair(main_key);
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, merge);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    userByUsername(username: "Alt") {{ username }}
+                    isUsernameAvailable(username: "Alt")
+                    game(gameId: "{game_id}") {{ playerNames }}
+                }}"#
+            ),
+        )
+        .await;
+    assert_eq!(response["userByUsername"]["username"].as_str().unwrap(), "Main");
+    assert!(!response["isUsernameAvailable"].as_bool().unwrap());
+    assert_eq!(response["game"]["playerNames"][0].as_str().unwrap(), "Main");
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    playerAchievements(owner: "{main}") {{ kind }}
+                    blockedPlayers(owner: "{main}")
+                    altAchievements: playerAchievements(owner: "{alt}") {{ kind }}
+                }}"#
+            ),
+        )
+        .await;
+    assert_eq!(response["playerAchievements"][0]["kind"].as_str().unwrap(), "FIRST_WIN");
+    assert_eq!(response["blockedPlayers"][0].as_str().unwrap(), rival.to_string());
+    assert!(response["altAchievements"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a second lobby created within the rate-limit interval is ignored
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rapid_lobby_creation_is_rate_limited() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Spammer".to_string(),
+                eth_address: "0x2626262626262626262626262626262626262626".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    for time_control in [300, 600] {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId timeControl } }"#)
+        .await;
+
+    let lobbies = response["openLobbies"].as_array().unwrap();
+    assert_eq!(lobbies.len(), 1);
+    assert_eq!(lobbies[0]["timeControl"].as_u64().unwrap(), 300);
+}
+
+/// Tests that a rejected lobby does not count against the rate limit
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rejected_lobby_is_not_rate_limited() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "Retrier".to_string(),
+                eth_address: "0x4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b4b".to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    // Chess has no seven-seat table, so the first attempt is refused before anything is created
+    for max_players in [7, 2] {
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: true,
+                    password: None,
+                    time_control: 300,
+                    max_players,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, r#"query { openLobbies { lobbyId maxPlayers } }"#)
+        .await;
+
+    let lobbies = response["openLobbies"].as_array().unwrap();
+    assert_eq!(lobbies.len(), 1);
+    assert_eq!(lobbies[0]["maxPlayers"].as_u64().unwrap(), 2);
+}
+
+/// Tests that an accepted takeback restores the board from before the move
+#[tokio::test(flavor = "multi_thread")]
+async fn test_takeback_restores_board() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Undoer",
+        "0x2727272727272727272727272727272727272727",
+    )
+    .await;
+
+    let query = format!(
+        r#"query {{ chessBoard(gameId: "{game_id}") {{ activePlayer squares {{ pieceType owner hasMoved }} moveHistory {{ notation }} }} }}"#
+    );
+    let QueryOutcome { response: before, .. } = chain.graphql_query(application_id, query.clone()).await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::ChessMove {
+                    game_id: game_id.clone(),
+                    from_square: 12,
+                    to_square: 28,
+                    promotion: None,
+                })
+                .with_operation(application_id, Operation::RequestTakeback { game_id: game_id.clone() })
+                .with_operation(application_id, Operation::AcceptTakeback { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response: after, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(after, before);
+    assert_eq!(after["chessBoard"]["activePlayer"].as_str().unwrap(), "ONE");
+    assert!(after["chessBoard"]["moveHistory"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a match game can't be aborted, which would leave the match waiting on it for good
+#[tokio::test(flavor = "multi_thread")]
+async fn test_match_game_cannot_be_aborted() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let eth_address = "0x4848484848484848484848484848484848484848";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Aborter".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateMatch {
+                    opponent: owner,
+                    game_type: GameType::Chess,
+                    best_of: 3,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::AbortGame { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "IN_PROGRESS");
+}
+
+/// Tests that two players creating games at the same block time each keep their own game
+#[tokio::test(flavor = "multi_thread")]
+async fn test_game_ids_are_unique() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let second_key = validator.new_chain().await.key_pair().copy();
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let mut game_ids = vec![];
+    for (key, username, eth_address) in [
+        (chain.key_pair().copy(), "FirstCreator", "0x3939393939393939393939393939393939393939"),
+        (second_key, "SecondCreator", "0x3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a3a"),
+    ] {
+        chain.set_key_pair(key);
+        chain
+            .add_block(|block| {
+                block
+                    .with_operation(application_id, Operation::RegisterUser {
+                        username: username.to_string(),
+                        eth_address: eth_address.to_string(),
+                        avatar_url: "".to_string(),
+                    })
+                    .with_operation(application_id, Operation::CreateGame {
+                        game_type: GameType::Chess,
+                        game_mode: GameMode::VsFriend,
+                        opponent: None,
+                        creator_color: None,
+                        timeouts: None,
+                        allow_hints: None,
+                        betting_mode: None,
+                        poker_stakes: None,
+                        hit_split_aces: None,
+                        blackjack_stakes: None,
+                        wager: None,
+                    });
+            })
+            .await;
+
+        let QueryOutcome { response, .. } = chain
+            .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+            .await;
+        game_ids.push(response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string());
+    }
+
+    assert_ne!(game_ids[0], game_ids[1]);
+
+    // The first game is still stored under its own id
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{}") {{ playerNames }} }}"#, game_ids[0]))
+        .await;
+    assert_eq!(response["game"]["playerNames"][0].as_str().unwrap(), "FirstCreator");
+}
+
+/// Tests that two players starting matches at the same block time each get their own
+#[tokio::test(flavor = "multi_thread")]
+async fn test_match_ids_are_unique() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let second_key = validator.new_chain().await.key_pair().copy();
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let mut match_ids = vec![];
+    for (key, username, eth_address) in [
+        (chain.key_pair().copy(), "FirstMatcher", "0x3737373737373737373737373737373737373737"),
+        (second_key, "SecondMatcher", "0x3838383838383838383838383838383838383838"),
+    ] {
+        let owner = AccountOwner::from(key.public());
+        chain.set_key_pair(key);
+        chain
+            .add_block(|block| {
+                block
+                    .with_operation(application_id, Operation::RegisterUser {
+                        username: username.to_string(),
+                        eth_address: eth_address.to_string(),
+                        avatar_url: "".to_string(),
+                    })
+                    .with_operation(application_id, Operation::CreateMatch {
+                        opponent: owner,
+                        game_type: GameType::Chess,
+                        best_of: 3,
+                    });
+            })
+            .await;
+
+        let QueryOutcome { response, .. } = chain
+            .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+            .await;
+        let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+        let QueryOutcome { response, .. } = chain
+            .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ matchId }} }}"#))
+            .await;
+        match_ids.push(response["game"]["matchId"].as_str().unwrap().to_string());
+    }
+
+    assert_ne!(match_ids[0], match_ids[1]);
+}
+
+/// Tests that a best-of-3 match ends once one player has won two games
+#[tokio::test(flavor = "multi_thread")]
+async fn test_best_of_three_match() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let eth_address = "0x2828282828282828282828282828282828282828";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Matcher".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateMatch {
+                    opponent: owner,
+                    game_type: GameType::Chess,
+                    best_of: 3,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let first_game = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{first_game}") {{ matchId }} }}"#))
+        .await;
+    let match_id = response["game"]["matchId"].as_str().unwrap().to_string();
+    let query = format!(r#"query {{ matchState(matchId: "{match_id}") {{ scores gameIds status winner }} }}"#);
+
+    // The first seat wins game one; seats swap, so the second seat wins game two for the same player
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ForceResolve {
+                game_id: first_game.clone(),
+                winner_seat: Some(0),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["matchState"]["status"].as_str().unwrap(), "IN_PROGRESS");
+    let second_game = response["matchState"]["gameIds"][1].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ForceResolve {
+                game_id: second_game.clone(),
+                winner_seat: Some(1),
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    let state = &response["matchState"];
+    assert_eq!(state["status"].as_str().unwrap(), "COMPLETED");
+    assert_eq!(state["scores"][0].as_u64().unwrap(), 2);
+    assert_eq!(state["scores"][1].as_u64().unwrap(), 0);
+    assert_eq!(state["gameIds"].as_array().unwrap().len(), 2);
+    assert_eq!(state["winner"].as_str().unwrap(), format!("{:?}", owner));
+}
+
+/// Tests that a move attempted after the mover's clock ran out ends the game as a timeout loss
+#[tokio::test(flavor = "multi_thread")]
+async fn test_late_move_resolves_timeout() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Flagged",
+        "0x2929292929292929292929292929292929292929",
+    )
+    .await;
+
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(400));
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ timeRemaining(gameId: "{game_id}") }}"#))
+        .await;
+    assert_eq!(response["timeRemaining"][0].as_i64().unwrap(), 0);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ChessMove {
+                game_id: game_id.clone(),
+                from_square: 12,
+                to_square: 28,
+                promotion: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} chessBoard(gameId: "{game_id}") {{ moveHistory {{ notation }} }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "TIMED_OUT");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "TWO");
+    // The late move never landed, and the finished game's board is dropped with its history
+    assert!(response["chessBoard"].is_null());
+}
+
+/// Tests that a strong bot record seeds a higher starting ELO exactly once
+#[tokio::test(flavor = "multi_thread")]
+async fn test_seed_elo_from_bots_applies_once() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3030303030303030303030303030303030303030";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "BotCrusher".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+            for _ in 0..5 {
+                block.with_operation(application_id, Operation::RecordBotGame {
+                    game_type: GameType::Chess,
+                    won: true,
+                    moves: 30,
+                    eth_address: eth_address.to_string(),
+                });
+            }
+            block.with_operation(application_id, Operation::SeedEloFromBots);
+        })
+        .await;
+
+    let query = format!(r#"query {{ userByEthAddress(ethAddress: "{eth_address}") {{ chessElo eloSeeded }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert_eq!(response["userByEthAddress"]["chessElo"].as_u64().unwrap(), 1250);
+    assert!(response["userByEthAddress"]["eloSeeded"].as_bool().unwrap());
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RecordBotGame {
+                    game_type: GameType::Chess,
+                    won: true,
+                    moves: 30,
+                    eth_address: eth_address.to_string(),
+                })
+                .with_operation(application_id, Operation::SeedEloFromBots);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert_eq!(response["userByEthAddress"]["chessElo"].as_u64().unwrap(), 1250);
+}
+
+/// Tests that a chess game the contract plays against its bot counts toward the seed
+#[tokio::test(flavor = "multi_thread")]
+async fn test_seed_elo_from_contract_bot_game() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3232303032323030323230303232303032323030";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "BotResigner".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsBot,
+                    opponent: None,
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true })
+                .with_operation(application_id, Operation::SeedEloFromBots);
+        })
+        .await;
+
+    let query = format!(r#"query {{ userByEthAddress(ethAddress: "{eth_address}") {{ chessElo chessLosses botChessLosses eloSeeded }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    let profile = &response["userByEthAddress"];
+    assert_eq!(profile["chessLosses"].as_u64().unwrap(), 1);
+    assert_eq!(profile["botChessLosses"].as_u64().unwrap(), 1);
+    assert_eq!(profile["chessElo"].as_u64().unwrap(), 1190);
+    assert!(profile["eloSeeded"].as_bool().unwrap());
+}
+
+/// Tests that withdrawing an unfunded challenge cancels it and drops it from the target's incoming challenges
+#[tokio::test(flavor = "multi_thread")]
+async fn test_withdraw_challenge() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let chain_id = chain.id();
+    chain
+        .add_block(|block| {
+            block
+                .with_native_token_transfer(
+                    AccountOwner::CHAIN,
+                    Account { chain_id, owner },
+                    Amount::from_tokens(5),
+                )
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Challenger".to_string(),
+                    eth_address: "0x3131313131313131313131313131313131313131".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: Some(Amount::ONE),
+                });
+        })
+        .await;
+
+    let query = format!(r#"query {{ incomingChallenges(owner: "{owner}") {{ gameId status }} }}"#);
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    let challenges = response["incomingChallenges"].as_array().unwrap();
+    assert_eq!(challenges.len(), 1);
+    let game_id = challenges[0]["gameId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::WithdrawChallenge { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["incomingChallenges"].as_array().unwrap().is_empty());
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "CANCELLED");
+    assert_eq!(chain.owner_balance(&owner).await.unwrap(), Amount::from_tokens(5));
+}
+
+/// Tests that a player cannot report themselves and that reports are only listed for the admin
+#[tokio::test(flavor = "multi_thread")]
+async fn test_report_player_after_game() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Reporter",
+        "0x3232323232323232323232323232323232323232",
+    )
+    .await;
+
+    let certificate = chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true })
+                // Both seats are the reporter's own
+                .with_operation(application_id, Operation::ReportPlayer {
+                    game_id: game_id.clone(),
+                    target: owner,
+                    reason: "  Abusive chat  ".to_string(),
+                })
+                // Not a game anyone played in, so it is refused
+                .with_operation(application_id, Operation::ReportPlayer {
+                    game_id: "missing".to_string(),
+                    target: owner,
+                    reason: "Stalling".to_string(),
+                });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses[1], GameOutcome::Rejected("Players cannot report themselves".to_string()));
+    assert_eq!(responses[2], GameOutcome::Rejected("Game not found".to_string()));
+
+    // The application creator is the admin; anyone else gets an empty list
+    let stranger = "0x4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a4a";
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(r#"query {{ admin: reports(viewer: "{owner}", limit: 10) {{ reportId }} stranger: reports(viewer: "{stranger}", limit: 10) {{ reportId }} }}"#),
+        )
+        .await;
+    assert!(response["admin"].as_array().unwrap().is_empty());
+    assert!(response["stranger"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a new tournament is listed as active with its creator registered
+#[tokio::test(flavor = "multi_thread")]
+async fn test_active_tournaments_lists_new_tournament() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Organizer".to_string(),
+                    eth_address: "0x3434343434343434343434343434343434343434".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateTournament {
+                    game_type: GameType::Chess,
+                    max_participants: 4,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            "query { activeTournaments { gameType status registeredPlayers maxPlayers currentRound } }",
+        )
+        .await;
+    let tournaments = response["activeTournaments"].as_array().unwrap();
+    assert_eq!(tournaments.len(), 1);
+    assert_eq!(tournaments[0]["gameType"].as_str().unwrap(), "CHESS");
+    assert_eq!(tournaments[0]["status"].as_str().unwrap(), "REGISTERING");
+    assert_eq!(tournaments[0]["registeredPlayers"].as_u64().unwrap(), 1);
+    assert_eq!(tournaments[0]["maxPlayers"].as_u64().unwrap(), 4);
+    assert_eq!(tournaments[0]["currentRound"].as_u64().unwrap(), 0);
+}
+
+/// Tests that two players opening tournaments at the same block time each get their own
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tournament_ids_are_unique() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let second_key = validator.new_chain().await.key_pair().copy();
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    for (username, eth_address) in [
+        ("FirstOrganizer", "0x3535353535353535353535353535353535353535"),
+        ("SecondOrganizer", "0x3636363636363636363636363636363636363636"),
+    ] {
+        chain
+            .add_block(|block| {
+                block
+                    .with_operation(application_id, Operation::RegisterUser {
+                        username: username.to_string(),
+                        eth_address: eth_address.to_string(),
+                        avatar_url: "".to_string(),
+                    })
+                    .with_operation(application_id, Operation::CreateTournament {
+                        game_type: GameType::Chess,
+                        max_participants: 4,
+                        allow_hints: None,
+                    });
+            })
+            .await;
+        chain.set_key_pair(second_key.copy());
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, "query { activeTournaments { tournamentId } }")
+        .await;
+    let tournaments = response["activeTournaments"].as_array().unwrap();
+    assert_eq!(tournaments.len(), 2);
+    assert_ne!(tournaments[0]["tournamentId"], tournaments[1]["tournamentId"]);
+}
+
+/// Tests that a player looking for chess is listed until they clear the flag or the announcement lapses
+#[tokio::test(flavor = "multi_thread")]
+async fn test_available_players_follows_availability_flag() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "OpenSeat".to_string(),
+                    eth_address: "0x3737373737373737373737373737373737373737".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::SetAvailability {
+                    looking_for_game: true,
+                    game_type: Some(GameType::Chess),
+                    message: Some("  Up for a blitz game  ".to_string()),
+                });
+        })
+        .await;
+
+    let query = r#"query {
+        chess: availablePlayers(gameType: CHESS) { username statusMessage }
+        poker: availablePlayers(gameType: POKER) { username }
+    }"#;
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    let chess = response["chess"].as_array().unwrap();
+    assert_eq!(chess.len(), 1);
+    assert_eq!(chess[0]["username"].as_str().unwrap(), "OpenSeat");
+    assert_eq!(chess[0]["statusMessage"].as_str().unwrap(), "Up for a blitz game");
+    assert!(response["poker"].as_array().unwrap().is_empty());
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::SetAvailability {
+                looking_for_game: false,
+                game_type: None,
+                message: None,
+            });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["chess"].as_array().unwrap().is_empty());
+
+    // An announcement nobody renews drops off the list once it lapses
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::SetAvailability {
+                looking_for_game: true,
+                game_type: Some(GameType::Chess),
+                message: None,
+            });
+        })
+        .await;
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_micros(AVAILABILITY_TTL_MICROS + 1));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::PruneLobbies);
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["chess"].as_array().unwrap().is_empty());
+}
+
+/// Tests that a poker player idle past the inactivity timeout forfeits while their clock still has time
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poker_inactivity_claim() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x3838383838383838383838383838383838383838";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Sleeper".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    // The small blind calls, leaving the big blind to act
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::PokerAction {
+                game_id: game_id.clone(),
+                action: PokerAction::Call,
+                bet_amount: None,
+            });
+        })
+        .await;
+
+    // A draw offer along the way is not play, so the inactivity timeout still runs from the call
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(40));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::OfferDraw { game_id: game_id.clone() });
+        })
+        .await;
+
+    // Past the 60s inactivity timeout but well inside the 300s clock
+    validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(21));
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ClaimTimeout { game_id: game_id.clone() });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ game(gameId: "{game_id}") {{ status winner }} }}"#))
+        .await;
+    assert_eq!(response["game"]["status"].as_str().unwrap(), "TIMED_OUT");
+    assert_eq!(response["game"]["winner"].as_str().unwrap(), "ONE");
+}
+
+/// Tests that moving the opponent's piece is answered with a rejection saying why
+#[tokio::test(flavor = "multi_thread")]
+async fn test_illegal_chess_move_is_rejected() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Fumbler",
+        "0x3939393939393939393939393939393939393939",
+    )
+    .await;
+
+    // White to move, but e7 holds a black pawn
+    let certificate = chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ChessMove {
+                game_id: game_id.clone(),
+                from_square: 52,
+                to_square: 36,
+                promotion: None,
+            });
+        })
+        .await;
+
+    let responses: Vec<GameOutcome> = certificate
+        .inner()
+        .block()
+        .body
+        .operation_results
+        .iter()
+        .map(|result| bcs::from_bytes(&result.0).expect("Failed to decode response"))
+        .collect();
+    assert_eq!(responses, vec![GameOutcome::Rejected("Not your piece".to_string())]);
+}
+
+/// Tests that the stats summary reports the chess win rate from wins, losses and draws
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_stats_summary_win_rate() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Summarizer",
+        "0x4040404040404040404040404040404040404040",
+    )
+    .await;
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{ playerStatsSummary(owner: "{owner}") {{
+                    byGame {{ gameType gamesPlayed wins losses draws winRate currentStreak bestStreak }}
+                    overallWinRate gamesPlayed
+                }} }}"#
+            ),
+        )
+        .await;
+    let summary = &response["playerStatsSummary"];
+    let chess = summary["byGame"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["gameType"].as_str() == Some("CHESS"))
+        .unwrap();
+    let wins = chess["wins"].as_f64().unwrap();
+    let losses = chess["losses"].as_f64().unwrap();
+    let draws = chess["draws"].as_f64().unwrap();
+    assert!(wins + losses + draws > 0.0);
+    let expected = wins / (wins + losses + draws) * 100.0;
+    assert!((chess["winRate"].as_f64().unwrap() - expected).abs() < 1e-9);
+
+    // Streaks are kept per game type, so the chess result leaves the poker record untouched
+    assert_eq!(chess["bestStreak"].as_u64().unwrap(), 1);
+    let poker = summary["byGame"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["gameType"].as_str() == Some("POKER"))
+        .unwrap();
+    assert_eq!(poker["gamesPlayed"].as_u64().unwrap(), 0);
+    assert_eq!(poker["currentStreak"].as_i64().unwrap(), 0);
+    assert_eq!(poker["bestStreak"].as_u64().unwrap(), 0);
+}
+
+/// Tests that a finished poker game keeps a summary but no longer stores its deck
+#[tokio::test(flavor = "multi_thread")]
+async fn test_completed_poker_game_drops_deck() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x4141414141414141414141414141414141414141";
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Archivist".to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: None,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+        .await;
+    let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+    // The deck is never shown, but a player sees their own hole cards
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(r#"query {{ pokerGame(gameId: "{game_id}", viewer: "{owner}") {{ playerHands {{ rank }} deck {{ rank }} }} }}"#),
+        )
+        .await;
+    assert!(response["pokerGame"]["deck"].as_array().unwrap().is_empty());
+    assert_eq!(response["pokerGame"]["playerHands"][0].as_array().unwrap().len(), 2);
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    pokerGame(gameId: "{game_id}") {{ deck {{ rank }} }}
+                    gameSummary(gameId: "{game_id}") {{ gameType status winner }}
+                    playerGameHistory(owner: "{owner}", limit: 10) {{ gameId }}
+                }}"#
+            ),
+        )
+        .await;
+    assert!(response["pokerGame"]["deck"].as_array().unwrap().is_empty());
+    assert_eq!(response["gameSummary"]["gameType"].as_str().unwrap(), "POKER");
+    assert_eq!(response["gameSummary"]["status"].as_str().unwrap(), "COMPLETED");
+    assert_eq!(response["gameSummary"]["winner"].as_str().unwrap(), "TWO");
+    assert_eq!(response["playerGameHistory"][0]["gameId"].as_str().unwrap(), game_id);
+}
+
+/// Tests that poker stats count each hand and keep the largest pot taken
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poker_biggest_pot_won() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x4646464646464646464646464646464646464646";
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::RegisterUser {
+                username: "PotHunter".to_string(),
+                eth_address: eth_address.to_string(),
+                avatar_url: "".to_string(),
+            });
+        })
+        .await;
+
+    // A 10/20 hand and then a 25/50 hand, each ended by the button folding its small blind
+    for stakes in [None, Some(PokerStakes { starting_chips: 500, small_blind: 25, big_blind: 50 })] {
+        validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(2));
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::CreateGame {
+                    game_type: GameType::Poker,
+                    game_mode: GameMode::VsFriend,
+                    opponent: Some(owner),
+                    creator_color: None,
+                    timeouts: None,
+                    allow_hints: None,
+                    betting_mode: None,
+                    poker_stakes: stakes,
+                    hit_split_aces: None,
+                    blackjack_stakes: None,
+                    wager: None,
+                });
+            })
+            .await;
+
+        let QueryOutcome { response, .. } = chain
+            .graphql_query(application_id, format!(r#"query {{ playerActiveGamesByEth(ethAddress: "{eth_address}") {{ gameId }} }}"#))
+            .await;
+        let game_id = response["playerActiveGamesByEth"][0]["gameId"].as_str().unwrap().to_string();
+
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::PokerAction {
+                    game_id: game_id.clone(),
+                    action: PokerAction::Fold,
+                    bet_amount: None,
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(r#"query {{ playerStats(owner: "{owner}") {{ pokerHandsPlayed pokerBiggestPotWon }} }}"#),
+        )
+        .await;
+    assert_eq!(response["playerStats"]["pokerHandsPlayed"].as_u64().unwrap(), 2);
+    // The big blind's unmatched half comes back rather than counting as pot won
+    assert_eq!(response["playerStats"]["pokerBiggestPotWon"].as_u64().unwrap(), 50);
+}
+
+/// Tests that a private lobby is listed as joinable for an invited player only
+#[tokio::test(flavor = "multi_thread")]
+async fn test_private_lobby_joinable_by_invitee() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let invitee = AccountOwner::from(validator.new_chain().await.public_key());
+    let outsider = AccountOwner::from(validator.new_chain().await.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    chain
+        .add_block(|block| {
+            block
+                .with_operation(application_id, Operation::RegisterUser {
+                    username: "Host".to_string(),
+                    eth_address: "0x4242424242424242424242424242424242424242".to_string(),
+                    avatar_url: "".to_string(),
+                })
+                .with_operation(application_id, Operation::CreateLobby {
+                    game_type: GameType::Chess,
+                    game_mode: GameMode::VsFriend,
+                    is_public: false,
+                    password: Some("members-only".to_string()),
+                    time_control: 300,
+                    max_players: 2,
+                    wager: None,
+                    creator_color: None,
+                    poker_stakes: None,
+                    allow_hints: None,
+                });
+        })
+        .await;
+
+    let owner = AccountOwner::from(chain.public_key());
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(application_id, format!(r#"query {{ playerLobbies(owner: "{owner}") {{ lobbyId }} }}"#))
+        .await;
+    let lobby_id = response["playerLobbies"][0]["lobbyId"].as_str().unwrap().to_string();
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::InviteToLobby { lobby_id: lobby_id.clone(), invitee });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    invited: joinableLobbies(owner: "{invitee}") {{ lobbyId }}
+                    outsider: joinableLobbies(owner: "{outsider}") {{ lobbyId }}
+                }}"#
+            ),
+        )
+        .await;
+    let invited = response["invited"].as_array().unwrap();
+    assert_eq!(invited.len(), 1);
+    assert_eq!(invited[0]["lobbyId"].as_str().unwrap(), lobby_id);
+    assert!(response["outsider"].as_array().unwrap().is_empty());
+}
+
+/// Tests that registering the same ETH address under a new name moves the account and frees the old name
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reregistering_eth_frees_old_username() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let eth_address = "0x4343434343434343434343434343434343434343";
+    for username in ["FirstName", "SecondName"] {
+        validator.clock().add(linera_sdk::linera_base_types::TimeDelta::from_secs(2));
+        chain
+            .add_block(|block| {
+                block.with_operation(application_id, Operation::RegisterUser {
+                    username: username.to_string(),
+                    eth_address: eth_address.to_string(),
+                    avatar_url: "".to_string(),
+                });
+            })
+            .await;
+    }
+
+    let QueryOutcome { response, .. } = chain
+        .graphql_query(
+            application_id,
+            format!(
+                r#"query {{
+                    oldFree: isUsernameAvailable(username: "FirstName")
+                    newFree: isUsernameAvailable(username: "SecondName")
+                    userByEthAddress(ethAddress: "{eth_address}") {{ username }}
+                    totalUsers
+                }}"#
+            ),
+        )
+        .await;
+    assert!(response["oldFree"].as_bool().unwrap());
+    assert!(!response["newFree"].as_bool().unwrap());
+    assert_eq!(response["userByEthAddress"]["username"].as_str().unwrap(), "SecondName");
+    assert_eq!(response["totalUsers"].as_u64().unwrap(), 1);
+}
+
+/// Tests that a resigned game reports it is over and why
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resigned_game_reports_result_reason() {
+    let (validator, module_id) =
+        TestValidator::with_current_module::<game_platform::GamePlatformAbi, (), ()>().await;
+    let mut chain = validator.new_chain().await;
+    let owner = AccountOwner::from(chain.public_key());
+
+    let application_id = chain
+        .create_application(module_id, (), (), vec![])
+        .await;
+
+    let game_id = create_self_chess_game(
+        &mut chain,
+        application_id,
+        "Quitter",
+        "0x4545454545454545454545454545454545454545",
+    )
+    .await;
+
+    let query = format!(
+        r#"query {{
+            game(gameId: "{game_id}") {{ isGameOver resultReason }}
+            playerGameHistory(owner: "{owner}", limit: 1) {{ resultReason }}
+        }}"#
+    );
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query.clone()).await;
+    assert!(!response["game"]["isGameOver"].as_bool().unwrap());
+    assert!(response["game"]["resultReason"].is_null());
+
+    chain
+        .add_block(|block| {
+            block.with_operation(application_id, Operation::ResignGame { game_id: game_id.clone(), confirm: true });
+        })
+        .await;
+
+    let QueryOutcome { response, .. } = chain.graphql_query(application_id, query).await;
+    assert!(response["game"]["isGameOver"].as_bool().unwrap());
+    assert_eq!(response["game"]["resultReason"].as_str().unwrap(), "RESIGNATION");
+    assert_eq!(response["playerGameHistory"][0]["resultReason"].as_str().unwrap(), "RESIGNATION");
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for the state audit report.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{StateAuditReport, UserProfile};
+
+/// Tests that a username mapping without a matching profile is reported
+#[test]
+fn test_orphaned_username_is_reported() {
+    let profile = UserProfile::new("Alice".to_string(), "0xabc".to_string(), String::new(), 0, 1200);
+    let mut report = StateAuditReport::default();
+
+    report.check_username("alice", Some(&profile));
+    assert!(report.is_clean());
+
+    // Mapping left behind with no profile, and one pointing at a renamed profile
+    report.check_username("ghost", None);
+    report.check_username("oldname", Some(&profile));
+
+    assert!(!report.is_clean());
+    assert_eq!(report.orphaned_usernames, vec!["ghost".to_string(), "oldname".to_string()]);
+}
+
+/// Tests that active lobby ids must point at open lobbies
+#[test]
+fn test_missing_lobby_is_stale() {
+    let mut report = StateAuditReport::default();
+
+    report.check_active_lobby("lobby_1", None);
+
+    assert_eq!(report.stale_lobby_ids, vec!["lobby_1".to_string()]);
+    assert!(report.orphaned_usernames.is_empty());
+}
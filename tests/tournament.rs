@@ -0,0 +1,68 @@
+// Copyright (c) Zefchain Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests for single-elimination tournament brackets.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use game_platform::{
+    is_valid_bracket_size, GameType, Tournament, TournamentPairing, TournamentStatus,
+};
+
+/// Pairs the next round, naming each game after its round and board
+fn start_round(tournament: &mut Tournament) -> Vec<String> {
+    let round = tournament.rounds.len();
+    let pairings: Vec<TournamentPairing> = tournament
+        .next_round_pairs()
+        .into_iter()
+        .enumerate()
+        .map(|(board, (a, b))| TournamentPairing {
+            game_id: format!("r{round}b{board}"),
+            players: vec![a, b],
+            winner: None,
+        })
+        .collect();
+    let ids = pairings.iter().map(|p| p.game_id.clone()).collect();
+    tournament.add_round(pairings);
+    ids
+}
+
+/// Tests that a four-player bracket runs to a single champion
+#[test]
+fn test_four_player_bracket_crowns_champion() {
+    let mut tournament = Tournament::new("t1".to_string(), GameType::Chess, "alice".to_string(), 4, 0);
+    for name in ["alice", "bob", "carol", "dave"] {
+        tournament.join(name.to_string()).unwrap();
+    }
+    assert!(tournament.join("erin".to_string()).is_err());
+
+    let semis = start_round(&mut tournament);
+    assert_eq!(semis.len(), 2);
+    assert_eq!(tournament.status, TournamentStatus::InProgress);
+
+    assert!(!tournament.record_result(&semis[0], "bob".to_string()));
+    assert!(tournament.next_round_pairs().is_empty());
+    assert!(tournament.record_result(&semis[1], "carol".to_string()));
+
+    let final_round = start_round(&mut tournament);
+    assert_eq!(final_round.len(), 1);
+    assert_eq!(tournament.rounds[1][0].players, vec!["bob".to_string(), "carol".to_string()]);
+
+    // A player who isn't in the game can't be recorded as its winner
+    assert!(!tournament.record_result(&final_round[0], "alice".to_string()));
+    assert!(tournament.record_result(&final_round[0], "carol".to_string()));
+
+    assert_eq!(tournament.status, TournamentStatus::Completed);
+    assert_eq!(tournament.champion.as_deref(), Some("carol"));
+    assert!(tournament.next_round_pairs().is_empty());
+}
+
+/// Tests that only power-of-two fields are accepted
+#[test]
+fn test_bracket_sizes() {
+    assert!(is_valid_bracket_size(2));
+    assert!(is_valid_bracket_size(8));
+    assert!(!is_valid_bracket_size(6));
+    assert!(!is_valid_bracket_size(1));
+    assert!(!is_valid_bracket_size(128));
+}